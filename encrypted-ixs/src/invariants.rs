@@ -0,0 +1,164 @@
+//! The invariants `enforce_vote_invariants` in the `circuits` module checks after each vote,
+//! on a plaintext tally.
+//!
+//! Circuits can't be run outside MPC, so the checks are kept here as well, where they can be
+//! unit tested against crafted tallies no correct vote could produce. A change to one is a
+//! change to the other.
+
+/// A plaintext `PollTally`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tally {
+    pub vote_counts: Vec<u64>,
+    pub total_weight: u64,
+    pub spoiled: u64,
+}
+
+/// Checks a vote's invariants between the tally before it (`before`) and after it (`after`),
+/// which must have as many counters, and keeps `after` only if they all hold:
+///
+/// 1. No counter ever decreases.
+/// 2. At most one counter changes, and only one of the poll's `num_options` options.
+/// 3. The counters' sum grows by exactly `weight`, or not at all. It grows by less only if the
+///    counter saturated at `u64::MAX`.
+/// 4. `total_weight` grows by exactly `weight`, sticking at `u64::MAX`.
+/// 5. `spoiled` grows by one or not at all, and never along with a counter.
+///
+/// # Returns
+/// The tally to store (`before` if an invariant was broken), and whether the invariants held
+pub fn enforce_vote_invariants(
+    before: &Tally,
+    after: &Tally,
+    num_options: u8,
+    weight: u64,
+) -> (Tally, bool) {
+    let counters = before.vote_counts.iter().zip(&after.vote_counts);
+    let before_sum: u128 = before.vote_counts.iter().map(|&count| count as u128).sum();
+    let after_sum: u128 = after.vote_counts.iter().map(|&count| count as u128).sum();
+    let changed: Vec<(usize, u64)> = counters
+        .clone()
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(option, (_, &new))| (option, new))
+        .collect();
+    let never_decreases = counters.clone().all(|(old, new)| new >= old);
+    let only_options_change = changed
+        .iter()
+        .all(|&(option, _)| option < num_options as usize);
+    let saturated = changed.iter().any(|&(_, new)| new == u64::MAX);
+
+    let weight = weight as u128;
+    let sum_grows_by_weight = after_sum == before_sum
+        || after_sum == before_sum + weight
+        || (saturated && after_sum < before_sum + weight);
+
+    let expected_total = before.total_weight as u128 + weight;
+    let total_grows_by_weight = after.total_weight as u128 == expected_total
+        || (after.total_weight == u64::MAX && expected_total > u64::MAX as u128);
+
+    let spoiled_grows_by_one = after.spoiled == before.spoiled
+        || (after.spoiled as u128 == before.spoiled as u128 + 1 && changed.is_empty());
+
+    let holds = never_decreases
+        && changed.len() <= 1
+        && only_options_change
+        && sum_grows_by_weight
+        && total_grows_by_weight
+        && spoiled_grows_by_one;
+    let kept = if holds { after } else { before };
+    (kept.clone(), holds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(vote_counts: &[u64], total_weight: u64, spoiled: u64) -> Tally {
+        Tally {
+            vote_counts: vote_counts.to_vec(),
+            total_weight,
+            spoiled,
+        }
+    }
+
+    fn assert_aborted(before: &Tally, after: &Tally, num_options: u8, weight: u64) {
+        assert_eq!(
+            enforce_vote_invariants(before, after, num_options, weight),
+            (before.clone(), false)
+        );
+    }
+
+    #[test]
+    fn counted_votes_keep_the_new_tally() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        let after = tally(&[3, 6, 0, 0], 9, 0);
+        assert_eq!(
+            enforce_vote_invariants(&before, &after, 3, 5),
+            (after, true)
+        );
+    }
+
+    #[test]
+    fn spoiled_votes_and_full_options_keep_the_new_tally() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        let after = tally(&[3, 1, 0, 0], 5, 1);
+        assert_eq!(
+            enforce_vote_invariants(&before, &after, 3, 1),
+            (after, true)
+        );
+    }
+
+    #[test]
+    fn saturated_counters_keep_the_new_tally() {
+        let before = tally(&[u64::MAX - 1, 0, 0, 0], u64::MAX - 1, 0);
+        let after = tally(&[u64::MAX, 0, 0, 0], u64::MAX, 0);
+        assert_eq!(
+            enforce_vote_invariants(&before, &after, 2, 10),
+            (after, true)
+        );
+    }
+
+    #[test]
+    fn a_decreasing_counter_aborts() {
+        // Moves a vote from option 0 to option 1: the sum and total still add up
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        let after = tally(&[2, 3, 0, 0], 5, 0);
+        assert_aborted(&before, &after, 3, 1);
+    }
+
+    #[test]
+    fn two_changed_counters_abort() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        let after = tally(&[4, 2, 0, 0], 5, 0);
+        assert_aborted(&before, &after, 3, 1);
+    }
+
+    #[test]
+    fn counting_past_the_poll_options_aborts() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        let after = tally(&[3, 1, 0, 1], 5, 0);
+        assert_aborted(&before, &after, 3, 1);
+    }
+
+    #[test]
+    fn counters_growing_by_more_or_less_than_the_weight_abort() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        assert_aborted(&before, &tally(&[3, 3, 0, 0], 5, 0), 3, 1);
+        // Less than the weight without saturating
+        assert_aborted(&before, &tally(&[3, 2, 0, 0], 9, 0), 3, 5);
+    }
+
+    #[test]
+    fn a_wrong_total_weight_aborts() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        assert_aborted(&before, &tally(&[4, 1, 0, 0], 4, 0), 3, 1);
+        // Sticking at u64::MAX early
+        assert_aborted(&before, &tally(&[4, 1, 0, 0], u64::MAX, 0), 3, 1);
+    }
+
+    #[test]
+    fn a_counted_spoiled_vote_aborts() {
+        let before = tally(&[3, 1, 0, 0], 4, 0);
+        assert_aborted(&before, &tally(&[4, 1, 0, 0], 5, 1), 3, 1);
+        assert_aborted(&before, &tally(&[3, 1, 0, 0], 5, 2), 3, 1);
+    }
+}
@@ -1,91 +1,1053 @@
 use arcis::*;
 
+pub mod invariants;
+pub mod winner;
+
 #[encrypted]
 mod circuits {
     use arcis::*;
 
+    /// Number of vote counters every poll carries, whatever its actual option count.
+    ///
+    /// Circuits always loop over all of them so the MPC cost (and therefore timing) doesn't
+    /// leak how many options a poll has. Each extra counter adds one ciphertext to the poll
+    /// account and one oblivious compare-and-add per vote, so the cost of `vote` and
+    /// `reveal_result` grows linearly with this value.
+    /// Must match `MAX_OPTIONS` in programs/election/src/constants.rs.
+    const MAX_OPTIONS: usize = 16;
+
+    /// Most options `reveal_top_k` can rank.
+    /// Must match `MAX_TOP_K` in programs/election/src/constants.rs.
+    const MAX_TOP_K: usize = 3;
+
+    /// Most ballots `vote_many` counts in one computation.
+    /// Must match `MAX_BATCH_BALLOTS` in programs/election/src/constants.rs.
+    const MAX_BATCH_BALLOTS: usize = 10;
+
+    /// Length in bytes of a vote's encrypted comment.
+    /// Must match `MAX_COMMENT_LENGTH` in programs/election/src/constants.rs.
+    const MAX_COMMENT_LENGTH: usize = 8;
+
+    /// Most votes `reveal_sample` draws.
+    /// Must match `MAX_SAMPLE_SIZE` in programs/election/src/constants.rs.
+    const MAX_SAMPLE_SIZE: usize = 32;
+
+    /// The linear congruential generator `reveal_sample` draws with: each draw's state is
+    /// `(LCG_MULTIPLIER * state + LCG_INCREMENT) % LCG_MODULUS`.
+    /// Must match the constants in `winner.rs`.
+    const LCG_MULTIPLIER: u64 = 1_664_525;
+    const LCG_INCREMENT: u64 = 1_013_904_223;
+    const LCG_MODULUS: u64 = 1 << 32;
+
     /// Tracks the encrypted vote tallies for a poll.
-    /// Three voting options: 0 = Neo robot, 1 = Humane AI PIN, 2 = friend.com
-    pub type VoteCounts = [u64; 3];
+    /// Only the first `num_options` counters of a poll are meaningful.
+    ///
+    /// Each counter is encrypted as one element of the ~255-bit base field, so one 32-byte
+    /// ciphertext holds a whole `u64`: up to 2^64 - 1 votes, or token weight, per option.
+    /// Counters only outgrow that if summed weights exceed it, e.g. very large balances
+    /// of a high-decimal mint; widening them to `u128` would still fit one element.
+    pub type VoteCounts = [u64; MAX_OPTIONS];
 
-    /// Represents a single encrypted choice.
-    /// 0 = Neo robot, 1 = Humane AI PIN, 2 = friend.com
+    /// Everything a poll accumulates while voting is open, encrypted under a single nonce.
+    ///
+    /// `vote_counts` comes first so circuits that only need the per-option tallies
+    /// can read just that prefix of the stored ciphertexts.
+    pub struct PollTally {
+        vote_counts: VoteCounts,
+        /// Sum of the weights of every counted vote
+        total_weight: u64,
+        /// Number of votes whose choice wasn't one of the poll's options
+        spoiled: u64,
+    }
+
+    /// Represents a single encrypted choice: the index of the chosen option.
     pub struct UserChoice {
         choice: u8,
     }
 
+    /// A quadratic vote: the chosen option and how many of the voter's credits they spend on it.
+    pub struct QuadraticChoice {
+        choice: u8,
+        credits: u64,
+    }
+
+    /// Adds two counters, sticking at `u64::MAX` rather than wrapping around,
+    /// so an overflow can never turn a large count into a small one.
+    fn saturating_add(a: u64, b: u64) -> u64 {
+        if b > u64::MAX - a {
+            u64::MAX
+        } else {
+            a + b
+        }
+    }
+
+    /// Adds `amount` to the counter at `idx`, leaving every other counter unchanged.
+    ///
+    /// Every counter is visited, so the work done doesn't depend on the (secret) index.
+    /// An index that isn't one of the poll's `num_options` options changes nothing.
+    /// Additions saturate so a huge amount can't wrap a counter round to a small value.
+    fn masked_index_increment(
+        counts: VoteCounts,
+        idx: u8,
+        num_options: u8,
+        amount: u64,
+    ) -> VoteCounts {
+        let mut counts = counts;
+        for option in 0..MAX_OPTIONS {
+            if idx as usize == option && (option as u8) < num_options {
+                counts[option] = saturating_add(counts[option], amount);
+            }
+        }
+        counts
+    }
+
+    /// Whether the counter at `idx` has reached `option_quota`, so the option takes no more votes.
+    ///
+    /// Every counter is visited, so the work done doesn't depend on the (secret) index.
+    /// Never true when `option_quota` is 0 (no quota) or `idx` isn't one of the poll's options.
+    fn option_full(counts: VoteCounts, idx: u8, num_options: u8, option_quota: u64) -> bool {
+        let mut full = false;
+        for option in 0..MAX_OPTIONS {
+            if idx as usize == option
+                && (option as u8) < num_options
+                && option_quota > 0
+                && counts[option] >= option_quota
+            {
+                full = true;
+            }
+        }
+        full
+    }
+
+    /// Counts one vote into a tally: adds `weight` to the chosen option's counter and to the
+    /// total weight. A choice that isn't one of the poll's options, or is for an option that
+    /// has reached its quota, is counted as spoiled instead. Shared by `vote` and `vote_many`.
+    ///
+    /// With `is_ballot` false the same work is done but nothing changes, so padding in a batch
+    /// costs the same as a vote.
+    fn count_ballot(
+        poll_tally: PollTally,
+        choice: u8,
+        num_options: u8,
+        weight: u64,
+        option_quota: u64,
+        is_ballot: bool,
+    ) -> PollTally {
+        let mut poll_tally = poll_tally;
+        let weight = if is_ballot { weight } else { 0 };
+        let choice_full = option_full(poll_tally.vote_counts, choice, num_options, option_quota);
+        // With no options in range, the increment still visits every counter but changes none
+        let counted_options = if choice_full || !is_ballot { 0 } else { num_options };
+        poll_tally.vote_counts =
+            masked_index_increment(poll_tally.vote_counts, choice, counted_options, weight);
+        if is_ballot && (choice >= num_options || choice_full) {
+            poll_tally.spoiled = saturating_add(poll_tally.spoiled, 1);
+        }
+
+        poll_tally.total_weight = saturating_add(poll_tally.total_weight, weight);
+        poll_tally
+    }
+
+    /// Checks `vote`'s invariants between the tally before a vote (`before`) and after it
+    /// (`after`), and keeps `after` only if they all hold:
+    ///
+    /// 1. No counter ever decreases.
+    /// 2. At most one counter changes, and only one of the poll's `num_options` options.
+    /// 3. The counters' sum grows by exactly `weight`, or not at all (a spoiled vote, a full
+    ///    option or a weight of 0). It grows by less only if the counter saturated at
+    ///    `u64::MAX`.
+    /// 4. `total_weight` grows by exactly `weight`, sticking at `u64::MAX`.
+    /// 5. `spoiled` grows by one or not at all, and never along with a counter: a spoiled vote
+    ///    isn't counted for any option.
+    ///
+    /// They hold for every vote `count_ballot` counts, so a violation means a bug in the
+    /// circuit or a tampered tally, and the vote is aborted with the tally left as it was.
+    /// Every check runs on every counter, with no early exit, so the work done doesn't depend
+    /// on the (secret) tally or choice. `invariants::enforce_vote_invariants` mirrors this on
+    /// plaintext, where it's unit tested, so a change to one is a change to the other.
+    ///
+    /// # Returns
+    /// The tally to store, and whether the invariants held
+    fn enforce_vote_invariants(
+        before: PollTally,
+        after: PollTally,
+        num_options: u8,
+        weight: u64,
+    ) -> (PollTally, bool) {
+        let mut before_sum = 0u128;
+        let mut after_sum = 0u128;
+        let mut changed = 0u8;
+        let mut never_decreases = true;
+        let mut only_options_change = true;
+        let mut saturated = false;
+        for option in 0..MAX_OPTIONS {
+            let old = before.vote_counts[option];
+            let new = after.vote_counts[option];
+            before_sum += old as u128;
+            after_sum += new as u128;
+            if new < old {
+                never_decreases = false;
+            }
+            if new != old {
+                changed += 1;
+                if option as u8 >= num_options {
+                    only_options_change = false;
+                }
+                if new == u64::MAX {
+                    saturated = true;
+                }
+            }
+        }
+        let weight = weight as u128;
+        let sum_grows_by_weight = after_sum == before_sum
+            || after_sum == before_sum + weight
+            || (saturated && after_sum < before_sum + weight);
+
+        let expected_total = before.total_weight as u128 + weight;
+        let total_grows_by_weight = after.total_weight as u128 == expected_total
+            || (after.total_weight == u64::MAX && expected_total > u64::MAX as u128);
+
+        let spoiled_grew = after.spoiled != before.spoiled;
+        let spoiled_grows_by_one = !spoiled_grew
+            || (after.spoiled as u128 == before.spoiled as u128 + 1 && changed == 0);
+
+        let holds = never_decreases
+            && changed <= 1
+            && only_options_change
+            && sum_grows_by_weight
+            && total_grows_by_weight
+            && spoiled_grows_by_one;
+
+        let mut kept = before;
+        for option in 0..MAX_OPTIONS {
+            if holds {
+                kept.vote_counts[option] = after.vote_counts[option];
+            }
+        }
+        if holds {
+            kept.total_weight = after.total_weight;
+            kept.spoiled = after.spoiled;
+        }
+        (kept, holds)
+    }
+
+    /// The integer square root of `n`, rounded down.
+    ///
+    /// Works out one bit of the root per round, from the highest, so it always runs the same
+    /// 32 rounds whatever `n` is. A `u64` root has at most 32 bits, and squaring a 32-bit
+    /// candidate can't overflow.
+    fn integer_sqrt(n: u64) -> u64 {
+        let mut root = 0u64;
+        for round in 0..32 {
+            let candidate = root | (1u64 << (31 - round));
+            if candidate * candidate <= n {
+                root = candidate;
+            }
+        }
+        root
+    }
+
+    /// Finds the option with the most votes among the poll's `num_options`, leaving out
+    /// `abstain_option` (`u8::MAX` if the poll has none), which can't win.
+    ///
+    /// Every counter is visited unconditionally, so the comparisons on encrypted counts become
+    /// oblivious selects. In case of a tie, the winner is the first tied option counting up
+    /// (and wrapping around) from `tie_break_offset`.
+    /// `winner::determine_winner` applies the same rule to plaintext counts, where it's unit
+    /// tested, so a change to the rule must be made to both.
+    ///
+    /// # Returns
+    /// The index of the winning option, its count, and how many options share that count
+    fn find_winner(
+        vote_counts: VoteCounts,
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+    ) -> (u8, u64, u8) {
+        let mut max_count = 0u64;
+        for option in 0..MAX_OPTIONS {
+            let is_side = (option as u8) < num_options && (option as u8) != abstain_option;
+            if is_side && vote_counts[option] > max_count {
+                max_count = vote_counts[option];
+            }
+        }
+
+        // Among the options with the most votes, pick the one closest after the offset
+        let mut winner = 0u8;
+        let mut winner_distance = MAX_OPTIONS as u8;
+        let mut tied_options = 0u8;
+        for option in 0..MAX_OPTIONS {
+            if (option as u8) < num_options && (option as u8) != abstain_option {
+                let distance = ((option as u8) + num_options - tie_break_offset) % num_options;
+                if vote_counts[option] == max_count {
+                    tied_options += 1;
+                    if distance < winner_distance {
+                        winner = option as u8;
+                        winner_distance = distance;
+                    }
+                }
+            }
+        }
+
+        (winner, max_count, tied_options)
+    }
+
+    /// One bubble pass of the fixed compare-exchange network `reveal_top_k` and
+    /// `reveal_full_ranking` sort with: carries the best option not yet in place from the back
+    /// of the list to the front.
+    ///
+    /// Options with more votes rank higher, with ties going to the lower option. Counters
+    /// beyond the poll's options always sort last, whatever they hold.
+    fn ranking_pass(
+        mut counts: VoteCounts,
+        mut options: [u8; MAX_OPTIONS],
+        num_options: u8,
+    ) -> (VoteCounts, [u8; MAX_OPTIONS]) {
+        for step in 1..MAX_OPTIONS {
+            let later = MAX_OPTIONS - step;
+            let earlier = later - 1;
+            let later_in_range = options[later] < num_options;
+            let earlier_in_range = options[earlier] < num_options;
+            let later_ranks_higher = later_in_range
+                && (!earlier_in_range
+                    || counts[later] > counts[earlier]
+                    || (counts[later] == counts[earlier] && options[later] < options[earlier]));
+            if later_ranks_higher {
+                let count = counts[earlier];
+                counts[earlier] = counts[later];
+                counts[later] = count;
+                let option = options[earlier];
+                options[earlier] = options[later];
+                options[later] = option;
+            }
+        }
+        (counts, options)
+    }
+
     /// Initializes encrypted vote counters for a new poll.
     ///
-    /// Creates a VoteCounts structure with zero counts for all three voting options.
+    /// Creates a PollTally with zero counts for every option, zero total weight and no spoiled votes.
     /// The counters remain encrypted and can only be updated through MPC operations.
     #[instruction]
-    pub fn create_poll(mxe: Mxe) -> Enc<Mxe, VoteCounts> {
-        let vote_counts: VoteCounts = [0, 0, 0];
-        mxe.from_arcis(vote_counts)
+    pub fn create_poll(mxe: Mxe) -> Enc<Mxe, PollTally> {
+        let poll_tally = PollTally {
+            vote_counts: [0; MAX_OPTIONS],
+            total_weight: 0,
+            spoiled: 0,
+        };
+        mxe.from_arcis(poll_tally)
+    }
+
+    /// Re-encrypts a poll's tally under a fresh nonce, leaving every count unchanged.
+    ///
+    /// Used to rotate a long-lived poll's nonce, e.g. before a sensitive reveal or after the
+    /// old nonce may have leaked. Nothing is revealed.
+    ///
+    /// # Arguments
+    /// * `mxe` - The MXE, carrying the new nonce
+    /// * `poll_tally_ctx` - Current encrypted vote tallies, total weight and spoiled count
+    ///
+    /// # Returns
+    /// The same tally, encrypted under the new nonce
+    #[instruction]
+    pub fn rotate_nonce(mxe: Mxe, poll_tally_ctx: Enc<Mxe, PollTally>) -> Enc<Mxe, PollTally> {
+        let poll_tally = poll_tally_ctx.to_arcis();
+        mxe.from_arcis(poll_tally)
     }
 
     /// Processes an encrypted vote and updates the running tallies.
     ///
-    /// Takes an individual vote and adds it to the appropriate counter
-    /// without revealing the choice value. The updated vote statistics remain encrypted
-    /// and can only be revealed by the poll authority.
+    /// Takes an individual vote and adds its weight to the appropriate counter
+    /// without revealing the choice value. A choice that isn't one of the poll's options,
+    /// or is for an option that has reached its quota, is counted as spoiled instead.
+    /// The updated vote statistics remain encrypted and can only be revealed by the poll authority.
+    ///
+    /// The updated tally is checked against the invariants of `enforce_vote_invariants`. If
+    /// one is broken, the vote is aborted: the tally comes back unchanged and the revealed
+    /// flag is false. In a correct circuit the flag is always true, so revealing it says
+    /// nothing about the vote.
     ///
     /// # Arguments
-    /// * `choice_ctx` - The encrypted choice to be counted (0, 1, or 2)
-    /// * `vote_counts_ctx` - Current encrypted vote tallies
+    /// * `choice_ctx` - The encrypted choice to be counted (an option index)
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `weight` - How much this vote adds to its option's counter and to the poll's total weight
+    /// * `option_quota` - Most votes an option can take, 0 for no quota
+    /// * `poll_tally_ctx` - Current encrypted vote tallies and total weight
     ///
     /// # Returns
-    /// Updated encrypted vote statistics with the new vote included
+    /// Updated encrypted vote statistics with the new vote included, and whether the vote's
+    /// invariants held (if not, the statistics are unchanged)
     #[instruction]
     pub fn vote(
         choice_ctx: Enc<Shared, UserChoice>,
-        vote_counts_ctx: Enc<Mxe, VoteCounts>,
-    ) -> Enc<Mxe, VoteCounts> {
+        num_options: u8,
+        weight: u64,
+        option_quota: u64,
+        poll_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> (Enc<Mxe, PollTally>, bool) {
         let user_choice = choice_ctx.to_arcis();
-        let mut vote_counts = vote_counts_ctx.to_arcis();
+        let poll_tally = poll_tally_ctx.to_arcis();
+        let before = PollTally {
+            vote_counts: poll_tally.vote_counts,
+            total_weight: poll_tally.total_weight,
+            spoiled: poll_tally.spoiled,
+        };
+        let after = count_ballot(
+            poll_tally,
+            user_choice.choice,
+            num_options,
+            weight,
+            option_quota,
+            true,
+        );
+        let (poll_tally, invariants_hold) =
+            enforce_vote_invariants(before, after, num_options, weight);
+
+        (poll_tally_ctx.owner.from_arcis(poll_tally), invariants_hold.reveal())
+    }
+
+    /// Counts a batch of encrypted votes into the tally in one computation, so high-volume
+    /// polls pay the fixed cost of a computation (queuing it, decrypting and re-encrypting the
+    /// tally, and the callback) once per batch rather than once per vote.
+    ///
+    /// Each ballot is counted exactly as `vote` counts one, in order, so quotas fill the same
+    /// way. Ballots from `ballot_count` on are padding and change nothing. Every ballot is
+    /// decrypted and counted either way, so the work done doesn't depend on how many were sent.
+    ///
+    /// # Arguments
+    /// * `ballot_0` to `ballot_9` - The encrypted choices, each under its own voter's key
+    /// * `ballot_count` - How many of the ballots are real, from 1 to `MAX_BATCH_BALLOTS`
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `weights` - Each ballot's weight, in the same order
+    /// * `option_quota` - Most votes an option can take, 0 for no quota
+    /// * `poll_tally_ctx` - Current encrypted vote tallies and total weight
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics with every real ballot included
+    #[instruction]
+    pub fn vote_many(
+        ballot_0: Enc<Shared, UserChoice>,
+        ballot_1: Enc<Shared, UserChoice>,
+        ballot_2: Enc<Shared, UserChoice>,
+        ballot_3: Enc<Shared, UserChoice>,
+        ballot_4: Enc<Shared, UserChoice>,
+        ballot_5: Enc<Shared, UserChoice>,
+        ballot_6: Enc<Shared, UserChoice>,
+        ballot_7: Enc<Shared, UserChoice>,
+        ballot_8: Enc<Shared, UserChoice>,
+        ballot_9: Enc<Shared, UserChoice>,
+        ballot_count: u8,
+        num_options: u8,
+        weights: [u64; MAX_BATCH_BALLOTS],
+        option_quota: u64,
+        poll_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> Enc<Mxe, PollTally> {
+        // Encrypted arguments can't be passed as an array, so the ballots are gathered here
+        let choices = [
+            ballot_0.to_arcis().choice,
+            ballot_1.to_arcis().choice,
+            ballot_2.to_arcis().choice,
+            ballot_3.to_arcis().choice,
+            ballot_4.to_arcis().choice,
+            ballot_5.to_arcis().choice,
+            ballot_6.to_arcis().choice,
+            ballot_7.to_arcis().choice,
+            ballot_8.to_arcis().choice,
+            ballot_9.to_arcis().choice,
+        ];
+
+        let mut poll_tally = poll_tally_ctx.to_arcis();
+        for ballot in 0..MAX_BATCH_BALLOTS {
+            poll_tally = count_ballot(
+                poll_tally,
+                choices[ballot],
+                num_options,
+                weights[ballot],
+                option_quota,
+                (ballot as u8) < ballot_count,
+            );
+        }
+
+        poll_tally_ctx.owner.from_arcis(poll_tally)
+    }
+
+    /// Processes an encrypted quadratic vote: the voter's chosen option gains the square root
+    /// of the credits they spend, so strong preferences cost more than they count.
+    ///
+    /// Both the choice and the credits stay encrypted. A vote spending more than the poll's
+    /// `credit_budget` is counted as spoiled rather than rejected, since rejecting it would
+    /// reveal something about the credits.
+    ///
+    /// # Arguments
+    /// * `choice_ctx` - The encrypted choice and credits spent
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `credit_budget` - Most credits a voter can spend
+    /// * `poll_tally_ctx` - Current encrypted vote tallies and total weight
+    ///
+    /// # Returns
+    /// Updated encrypted vote statistics with the new vote included
+    #[instruction]
+    pub fn vote_quadratic(
+        choice_ctx: Enc<Shared, QuadraticChoice>,
+        num_options: u8,
+        credit_budget: u64,
+        poll_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> Enc<Mxe, PollTally> {
+        let quadratic_choice = choice_ctx.to_arcis();
+        let mut poll_tally = poll_tally_ctx.to_arcis();
 
-        // "Arcis automatically converts secret-indexed array access to oblivious operations."
-        // https://docs.arcium.com/developers/arcis/operations
-        vote_counts[user_choice.choice as usize] += 1;
+        let within_budget = quadratic_choice.credits <= credit_budget;
+        // Pointing an over-budget vote past the options spoils it
+        let choice = if within_budget { quadratic_choice.choice } else { num_options };
+        let votes = if within_budget { integer_sqrt(quadratic_choice.credits) } else { 0 };
 
-        vote_counts_ctx.owner.from_arcis(vote_counts)
+        poll_tally.vote_counts =
+            masked_index_increment(poll_tally.vote_counts, choice, num_options, votes);
+        if choice >= num_options {
+            poll_tally.spoiled = saturating_add(poll_tally.spoiled, 1);
+        }
+
+        poll_tally.total_weight = saturating_add(poll_tally.total_weight, votes);
+
+        poll_tally_ctx.owner.from_arcis(poll_tally)
     }
 
     /// Reveals the final result of the poll by comparing vote tallies.
     ///
-    /// Decrypts the vote counters and determines which option received the most votes.
-    /// Only the final result (winner) is revealed, not the actual vote counts.
+    /// Compares the encrypted vote counters and determines which option received the most votes.
+    /// Only the final result (winner), whether it was a tie and how many votes were spoiled are
+    /// revealed, plus the winner's count if the poll opted in, never the other options' counts.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `tie_break_offset` - The option ties are resolved from (derived from the poll's tie-break seed)
+    /// * `abstain_option` - The option recording abstentions, which can't win (`u8::MAX` if none)
+    /// * `reveal_winner_count` - Whether to also reveal how many votes the winner got
+    /// * `option_quota` - Most votes an option can take, 0 for no quota
+    /// * `poll_tally_ctx` - Encrypted vote tallies to be revealed
+    ///
+    /// # Returns
+    /// The index of the winning option, whether several options tied for the most votes,
+    /// the number of spoiled votes, the winner's count (0 unless `reveal_winner_count`),
+    /// and which options have reached their quota, as a bitmask with bit `i` for option `i`.
+    /// In case of a tie, returns the first tied option counting up (and wrapping around)
+    /// from `tie_break_offset`.
+    #[instruction]
+    pub fn reveal_result(
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+        reveal_winner_count: bool,
+        option_quota: u64,
+        poll_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> (u8, bool, u64, u64, u16) {
+        let poll_tally = poll_tally_ctx.to_arcis();
+
+        // Note: Can't use early returns in Arcis, so `find_winner` visits every counter
+        let (winner, max_count, tied_options) =
+            find_winner(poll_tally.vote_counts, num_options, tie_break_offset, abstain_option);
+
+        // The winner always has the top count, so no per-option selection is needed
+        let winner_count = if reveal_winner_count { max_count } else { 0 };
+
+        let mut full_options = 0u16;
+        for option in 0..MAX_OPTIONS {
+            let in_range = (option as u8) < num_options;
+            if in_range && option_quota > 0 && poll_tally.vote_counts[option] >= option_quota {
+                full_options += 1u16 << option;
+            }
+        }
+
+        (
+            winner.reveal(),
+            (tied_options > 1).reveal(),
+            poll_tally.spoiled.reveal(),
+            winner_count.reveal(),
+            full_options.reveal(),
+        )
+    }
+
+    /// Works out the winner of the poll like `reveal_result`, but encrypts it for one recipient
+    /// instead of revealing it, e.g. so an auditor learns the result before it's published.
     ///
     /// # Arguments
-    /// * `vote_counts_ctx` - Encrypted vote tallies to be revealed
+    /// * `recipient` - The x25519 key (and nonce) to encrypt the winner for
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `tie_break_offset` - The option ties are resolved from (derived from the poll's tie-break seed)
+    /// * `abstain_option` - The option recording abstentions, which can't win (`u8::MAX` if none)
+    /// * `vote_counts_ctx` - Encrypted vote tallies
     ///
     /// # Returns
-    /// The winning option: 0 = Neo robot, 1 = Humane AI PIN, 2 = friend.com
-    /// In case of a tie, returns the option with the lower index that tied.
+    /// The index of the winning option, encrypted for `recipient`
     #[instruction]
-    pub fn reveal_result(vote_counts_ctx: Enc<Mxe, VoteCounts>) -> u8 {
+    pub fn reveal_to(
+        recipient: Shared,
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> Enc<Shared, u8> {
         let vote_counts = vote_counts_ctx.to_arcis();
+        let (winner, _, _) =
+            find_winner(vote_counts, num_options, tie_break_offset, abstain_option);
+        recipient.from_arcis(winner)
+    }
 
-        // Reveal all vote counts first (must be unconditional)
-        let count0 = vote_counts[0].reveal();
-        let count1 = vote_counts[1].reveal();
-        let count2 = vote_counts[2].reveal();
-
-        // Find the maximum count using chained .max() calls.
-        // Note: Arcis only supports `use arcis::*`, so std imports like
-        // `use std::cmp;` are not available. Chaining .max() is the idiomatic
-        // Rust approach for finding the max of 3+ values when std::cmp::max
-        // or iterator methods are unavailable.
-        let max_count = count0.max(count1).max(count2);
-
-        // Return the index of the maximum (first match in case of ties)
-        // Note: Can't use early returns in Arcis, so we use if-else-if chain as an expression
-        if count0 == max_count {
-            0u8
-        } else if count1 == max_count {
-            1u8
-        } else {
-            2u8
+    /// Reveals the `k` options with the most votes, in descending order, e.g. to elect several
+    /// board members from one poll.
+    ///
+    /// The options are ranked with a fixed network of compare-exchanges, so the work done
+    /// never depends on the (secret) counts. Only the ranking is revealed, not the counts.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `k` - How many options to rank, at most `MAX_TOP_K`
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The indices of the top `k` options, most votes first, with ties going to the lower
+    /// option. Entries past `k` are `u8::MAX`.
+    #[instruction]
+    pub fn reveal_top_k(
+        num_options: u8,
+        k: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> [u8; MAX_TOP_K] {
+        let mut counts = vote_counts_ctx.to_arcis();
+        let mut options = [0u8; MAX_OPTIONS];
+        for option in 0..MAX_OPTIONS {
+            options[option] = option as u8;
+        }
+
+        // One bubble pass per ranked place
+        for _ in 0..MAX_TOP_K {
+            let (ranked_counts, ranked_options) = ranking_pass(counts, options, num_options);
+            counts = ranked_counts;
+            options = ranked_options;
+        }
+
+        let mut ranking = [u8::MAX; MAX_TOP_K];
+        for place in 0..MAX_TOP_K {
+            if (place as u8) < k {
+                ranking[place] = options[place];
+            }
+        }
+
+        ranking.reveal()
+    }
+
+    /// Reveals every option's vote count, ranked from most votes to fewest, for organizers
+    /// who want the complete results in one computation.
+    ///
+    /// The counts are sorted with the same fixed compare-exchange network as `reveal_top_k`,
+    /// run to completion, so the work done never depends on the (secret) counts. Only the
+    /// sorted result is revealed.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The option indices, most votes first with ties going to the lower option, and each
+    /// one's count. Places past the poll's options hold `u8::MAX` and 0.
+    #[instruction]
+    pub fn reveal_full_ranking(
+        num_options: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> ([u8; MAX_OPTIONS], [u64; MAX_OPTIONS]) {
+        let mut counts = vote_counts_ctx.to_arcis();
+        let mut options = [0u8; MAX_OPTIONS];
+        for option in 0..MAX_OPTIONS {
+            options[option] = option as u8;
+        }
+
+        // After one pass per place but the last, the last place holds what's left
+        for _ in 1..MAX_OPTIONS {
+            let (ranked_counts, ranked_options) = ranking_pass(counts, options, num_options);
+            counts = ranked_counts;
+            options = ranked_options;
         }
+
+        for place in 0..MAX_OPTIONS {
+            if (place as u8) >= num_options {
+                options[place] = u8::MAX;
+                counts[place] = 0;
+            }
+        }
+
+        (options.reveal(), counts.reveal())
+    }
+
+    /// Reveals whether a poll's outcome is already decided, so it can be called early.
+    ///
+    /// The result is decided once the leader has more votes than the runner-up could reach
+    /// even if every remaining voter backed them. Only the leader and that comparison are
+    /// revealed, never the counts or the margin.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `remaining_votes` - How many votes can still be cast (expected voters minus votes so far)
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The index of the leading option, and whether it can no longer be overtaken.
+    /// While undecided the leader is only provisional, and ties go to the lowest option.
+    #[instruction]
+    pub fn reveal_decided(
+        num_options: u8,
+        remaining_votes: u64,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> (u8, bool) {
+        let vote_counts = vote_counts_ctx.to_arcis();
+
+        // Track the top two counts in one pass, visiting every counter unconditionally
+        let mut leader = 0u8;
+        let mut leader_count = 0u64;
+        let mut runner_up_count = 0u64;
+        for option in 0..MAX_OPTIONS {
+            if (option as u8) < num_options {
+                if vote_counts[option] > leader_count {
+                    runner_up_count = leader_count;
+                    leader_count = vote_counts[option];
+                    leader = option as u8;
+                } else if vote_counts[option] > runner_up_count {
+                    runner_up_count = vote_counts[option];
+                }
+            }
+        }
+
+        let decided = leader_count > saturating_add(runner_up_count, remaining_votes);
+
+        (leader.reveal(), decided.reveal())
+    }
+
+    /// Reveals whether a yes/no referendum passed, i.e. whether its yes share reached a threshold.
+    ///
+    /// Option 0 is no and option 1 is yes. The pass check compares `yes * 100` with
+    /// `threshold * total` rather than the rounded percentage, so a referendum needing 60%
+    /// can't pass on 59.5%. A referendum nobody voted in fails.
+    ///
+    /// # Arguments
+    /// * `pass_threshold_pct` - The share of votes, in percent, yes needs to pass
+    /// * `vote_counts_ctx` - The poll's encrypted vote counts
+    ///
+    /// # Returns
+    /// Whether the referendum passed, and the yes share in whole percent (0 if nobody voted)
+    #[instruction]
+    pub fn reveal_referendum(
+        pass_threshold_pct: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> (bool, u8) {
+        let vote_counts = vote_counts_ctx.to_arcis();
+        let no_votes = vote_counts[0] as u128;
+        let yes_votes = vote_counts[1] as u128;
+        let total_votes = no_votes + yes_votes;
+
+        let passed =
+            total_votes > 0 && yes_votes * 100 >= pass_threshold_pct as u128 * total_votes;
+        // Divide by 1 when nobody voted, so both cases do the same work
+        let divisor = if total_votes == 0 { 1 } else { total_votes };
+        let yes_pct = (yes_votes * 100 / divisor) as u8;
+
+        (passed.reveal(), yes_pct.reveal())
+    }
+
+    /// Reveals how spread out the votes are: the gap between the most and least voted options.
+    ///
+    /// A large spread means opinion was concentrated on some options, a small one that it was
+    /// split evenly. Only the spread is revealed, never which options have the extremes or
+    /// their counts.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The highest option count minus the lowest
+    #[instruction]
+    pub fn reveal_spread(num_options: u8, vote_counts_ctx: Enc<Mxe, VoteCounts>) -> u64 {
+        let vote_counts = vote_counts_ctx.to_arcis();
+
+        // Every counter is visited unconditionally, whatever the poll's option count
+        let mut max_count = vote_counts[0];
+        let mut min_count = vote_counts[0];
+        for option in 1..MAX_OPTIONS {
+            if (option as u8) < num_options {
+                if vote_counts[option] > max_count {
+                    max_count = vote_counts[option];
+                }
+                if vote_counts[option] < min_count {
+                    min_count = vote_counts[option];
+                }
+            }
+        }
+
+        (max_count - min_count).reveal()
+    }
+
+    /// Reveals the winner of a sampled poll along with a margin of error, for straw polls that
+    /// only ask part of the electorate.
+    ///
+    /// The margin of error is the usual 95% estimate for a proportion, at most about
+    /// 1/sqrt(n) for a sample of n votes, so it shrinks as more votes are counted. Only the
+    /// winner and the margin are revealed, not the sample size or any counts.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `tie_break_offset` - The option ties are resolved from (derived from the poll's tie-break seed)
+    /// * `abstain_option` - The option recording abstentions, which can't win (`u8::MAX` if none)
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The index of the winning option, and the margin of error in whole percent,
+    /// `100 / floor(sqrt(n))` rounded down, or 100 if nobody voted
+    #[instruction]
+    pub fn reveal_with_interval(
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> (u8, u8) {
+        let vote_counts = vote_counts_ctx.to_arcis();
+        let (winner, _, _) =
+            find_winner(vote_counts, num_options, tie_break_offset, abstain_option);
+
+        let mut sample_size = 0u64;
+        for option in 0..MAX_OPTIONS {
+            if (option as u8) < num_options {
+                sample_size = saturating_add(sample_size, vote_counts[option]);
+            }
+        }
+
+        // Divide by 1 when nobody voted, so both cases do the same work and the margin is 100%
+        let root = integer_sqrt(sample_size);
+        let divisor = if root == 0 { 1 } else { root };
+        let moe_pct = (100 / divisor) as u8;
+
+        (winner.reveal(), moe_pct.reveal())
+    }
+
+    /// Reveals the winner of a pseudo-random sample of a poll's votes, an estimate of the real
+    /// winner for organizers who only want a projection.
+    ///
+    /// Each of `sample_size` draws picks one counted vote uniformly, with replacement, by
+    /// walking the counters to the vote at a position drawn from `seed`. All `MAX_SAMPLE_SIZE`
+    /// draws are made and every counter visited on each, whatever the sample size or counts,
+    /// so the work done leaks neither; draws past the sample size just aren't counted. The
+    /// winner of the sample is found with the poll's usual rule. Only that winner is revealed.
+    /// `winner::sample_counts` draws the same sample from plaintext counts, where it's unit
+    /// tested, so a change to the sampling must be made to both.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `tie_break_offset` - The option ties are resolved from (derived from the poll's tie-break seed)
+    /// * `abstain_option` - The option recording abstentions, which can't win (`u8::MAX` if none)
+    /// * `sample_size` - How many votes to draw, at most `MAX_SAMPLE_SIZE`
+    /// * `seed` - Seeds the draws: the same seed and counts always draw the same sample
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The index of the option that won the sample, and the sample size and seed it was drawn
+    /// with, so the signed output says which sample the winner is of
+    #[instruction]
+    pub fn reveal_sample(
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+        sample_size: u8,
+        seed: u64,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> (u8, u8, u64) {
+        let vote_counts = vote_counts_ctx.to_arcis();
+
+        let mut total = 0u64;
+        for option in 0..MAX_OPTIONS {
+            if (option as u8) < num_options {
+                total = saturating_add(total, vote_counts[option]);
+            }
+        }
+
+        let mut sample = [0u64; MAX_OPTIONS];
+        let mut state = (seed ^ (seed >> 32)) % LCG_MODULUS;
+        for draw in 0..MAX_SAMPLE_SIZE {
+            state = (LCG_MULTIPLIER * state + LCG_INCREMENT) % LCG_MODULUS;
+            // Scales the 32-bit state down to a vote position below the total
+            let position = ((state as u128 * total as u128) >> 32) as u64;
+
+            // The drawn vote belongs to the option whose votes span its position
+            let mut before = 0u64;
+            for option in 0..MAX_OPTIONS {
+                let in_range = (option as u8) < num_options;
+                let after = saturating_add(before, vote_counts[option]);
+                if in_range
+                    && (draw as u8) < sample_size
+                    && position >= before
+                    && position < after
+                {
+                    sample[option] += 1;
+                }
+                if in_range {
+                    before = after;
+                }
+            }
+        }
+
+        let (winner, _, _) = find_winner(sample, num_options, tie_break_offset, abstain_option);
+        (winner.reveal(), sample_size.reveal(), seed.reveal())
+    }
+
+    /// Reveals whether option 1 beat option 0 in a two-option poll, for yes/no questions where
+    /// a winner index would have to be mapped back to yes or no.
+    ///
+    /// # Arguments
+    /// * `vote_counts_ctx` - Encrypted vote tallies, of which only the first two are used
+    ///
+    /// # Returns
+    /// Whether option 1 (yes) got more votes than option 0 (no), and whether they tied.
+    /// A tie isn't a win for yes.
+    #[instruction]
+    pub fn reveal_bool(vote_counts_ctx: Enc<Mxe, VoteCounts>) -> (bool, bool) {
+        let vote_counts = vote_counts_ctx.to_arcis();
+        let yes_wins = vote_counts[1] > vote_counts[0];
+        let is_tie = vote_counts[1] == vote_counts[0];
+
+        (yes_wins.reveal(), is_tie.reveal())
+    }
+
+    /// Hands a poll's whole tally to the holder of an x25519 key, instead of revealing a winner.
+    ///
+    /// Used by polls whose results are read by a committee rather than the poll authority.
+    /// Nothing is revealed publicly: the tally is re-encrypted so only `recipient` can decrypt it.
+    ///
+    /// # Arguments
+    /// * `recipient` - The x25519 key (and nonce) to re-encrypt the tally for
+    /// * `poll_tally_ctx` - Encrypted vote tallies, total weight and spoiled count
+    ///
+    /// # Returns
+    /// The poll's tally, encrypted for `recipient`
+    #[instruction]
+    pub fn reveal_tally_shared(
+        recipient: Shared,
+        poll_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> Enc<Shared, PollTally> {
+        let poll_tally = poll_tally_ctx.to_arcis();
+        recipient.from_arcis(poll_tally)
+    }
+
+    /// Reveals whether a poll's total vote weight reached its quorum.
+    ///
+    /// Only the comparison result is revealed, never the total weight itself. The threshold
+    /// is fixed when the poll is created, so repeated reveals can't be used to narrow it down.
+    ///
+    /// # Arguments
+    /// * `weight_quorum` - The minimum total weight for the poll to be quorate
+    /// * `poll_tally_ctx` - Encrypted vote tallies and total weight
+    ///
+    /// # Returns
+    /// `true` if the total weight is at least `weight_quorum`
+    #[instruction]
+    pub fn reveal_weight_quorum(weight_quorum: u64, poll_tally_ctx: Enc<Mxe, PollTally>) -> bool {
+        let poll_tally = poll_tally_ctx.to_arcis();
+        (poll_tally.total_weight >= weight_quorum).reveal()
+    }
+
+    /// Adds a child poll's encrypted tallies into its parent's, e.g. a precinct into its region.
+    ///
+    /// Sums are computed entirely on encrypted values, so neither poll's counts are revealed.
+    /// Several children are aggregated by running this once per child.
+    ///
+    /// # Arguments
+    /// * `parent_tally_ctx` - The parent poll's encrypted vote tallies and total weight
+    /// * `child_tally_ctx` - The child poll's encrypted vote tallies and total weight
+    ///
+    /// # Returns
+    /// The parent's tallies with the child's added, re-encrypted for the parent
+    #[instruction]
+    pub fn aggregate_child(
+        parent_tally_ctx: Enc<Mxe, PollTally>,
+        child_tally_ctx: Enc<Mxe, PollTally>,
+    ) -> Enc<Mxe, PollTally> {
+        let mut parent_tally = parent_tally_ctx.to_arcis();
+        let child_tally = child_tally_ctx.to_arcis();
+
+        for option in 0..MAX_OPTIONS {
+            parent_tally.vote_counts[option] =
+                saturating_add(parent_tally.vote_counts[option], child_tally.vote_counts[option]);
+        }
+        parent_tally.total_weight =
+            saturating_add(parent_tally.total_weight, child_tally.total_weight);
+        parent_tally.spoiled = saturating_add(parent_tally.spoiled, child_tally.spoiled);
+
+        parent_tally_ctx.owner.from_arcis(parent_tally)
+    }
+
+    /// Checks a voter's claim about how they voted against the encrypted choice in their
+    /// `VoterRecord`, for settling disputes.
+    ///
+    /// Only whether the claim matches is revealed: a wrong claim is shown to be wrong without
+    /// revealing the real choice.
+    ///
+    /// # Arguments
+    /// * `choice_ctx` - The vote's encrypted choice, as stored when it was cast
+    /// * `claimed_choice` - The option the voter claims to have chosen
+    ///
+    /// # Returns
+    /// Whether the vote's choice is `claimed_choice`, and the claim itself, so the signed output
+    /// says which claim was checked
+    #[instruction]
+    pub fn verify_vote_claim(
+        choice_ctx: Enc<Shared, UserChoice>,
+        claimed_choice: u8,
+    ) -> (bool, u8) {
+        let user_choice = choice_ctx.to_arcis();
+        let matches = user_choice.choice == claimed_choice;
+
+        (matches.reveal(), claimed_choice.reveal())
+    }
+
+    /// Hands a vote's comment to the holder of an x25519 key, normally the poll authority.
+    ///
+    /// Comments are only ever re-encrypted, never revealed, and never touch the tally, so
+    /// they can't affect any result.
+    ///
+    /// # Arguments
+    /// * `comment_ctx` - The comment as the voter encrypted it
+    /// * `recipient` - The x25519 key (and nonce) to re-encrypt the comment for
+    ///
+    /// # Returns
+    /// The comment, encrypted for `recipient`
+    #[instruction]
+    pub fn reveal_comment(
+        comment_ctx: Enc<Shared, [u8; MAX_COMMENT_LENGTH]>,
+        recipient: Shared,
+    ) -> Enc<Shared, [u8; MAX_COMMENT_LENGTH]> {
+        let comment = comment_ctx.to_arcis();
+        recipient.from_arcis(comment)
+    }
+
+    /// Reveals which option is currently in the lead, for live results while voting is open.
+    ///
+    /// Picks the winner exactly as `reveal_result` does, but reveals nothing else: not whether
+    /// it's a tie, nor any count. The callback compares it with the previous live winner.
+    ///
+    /// # Arguments
+    /// * `num_options` - How many of the counters belong to real options
+    /// * `tie_break_offset` - The option ties are resolved from (derived from the poll's tie-break seed)
+    /// * `abstain_option` - The option recording abstentions, which can't win (`u8::MAX` if none)
+    /// * `vote_counts_ctx` - Encrypted vote tallies
+    ///
+    /// # Returns
+    /// The index of the option in the lead
+    #[instruction]
+    pub fn reveal_delta(
+        num_options: u8,
+        tie_break_offset: u8,
+        abstain_option: u8,
+        vote_counts_ctx: Enc<Mxe, VoteCounts>,
+    ) -> u8 {
+        let vote_counts = vote_counts_ctx.to_arcis();
+        let (winner, _, _) =
+            find_winner(vote_counts, num_options, tie_break_offset, abstain_option);
+        winner.reveal()
     }
 }
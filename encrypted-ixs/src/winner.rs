@@ -0,0 +1,177 @@
+//! The winner rule of `find_winner` in the `circuits` module, on plaintext counts, and the
+//! sampling of `reveal_sample`.
+//!
+//! Circuits can't be run outside MPC, so the rules are kept here as well, where they can be
+//! unit tested. Each must stay in step with its circuit: a change to one is a change to the
+//! other.
+
+/// Finds the option with the most votes, leaving out `abstain_option` (`u8::MAX` if the poll
+/// has none), which can't win.
+///
+/// In case of a tie, the winner is the first tied option counting up (and wrapping around)
+/// from `tie_break_offset`. With no votes at all, every option ties.
+///
+/// # Arguments
+/// * `counts` - The vote count of each of the poll's options, at most `u8::MAX` of them
+/// * `tie_break_offset` - The option tie-breaks start from, below `counts.len()`
+/// * `abstain_option` - The option that can't win
+///
+/// # Returns
+/// The index of the winning option, and whether another option has as many votes
+pub fn determine_winner(counts: &[u64], tie_break_offset: u8, abstain_option: u8) -> (u8, bool) {
+    let num_options = counts.len();
+    let is_side = |option: usize| option != abstain_option as usize;
+    let max_count = (0..num_options)
+        .filter(|&option| is_side(option))
+        .map(|option| counts[option])
+        .max()
+        .unwrap_or(0);
+
+    let tied: Vec<usize> = (0..num_options)
+        .filter(|&option| is_side(option) && counts[option] == max_count)
+        .collect();
+    let distance = |option: usize| (option + num_options - tie_break_offset as usize) % num_options;
+    // As in the circuit, option 0 if every option abstains
+    let winner = tied.iter().copied().min_by_key(|&option| distance(option)).unwrap_or(0);
+
+    (winner as u8, tied.len() > 1)
+}
+
+/// The linear congruential generator `reveal_sample` draws with.
+/// Must match the constants in the `circuits` module.
+const LCG_MULTIPLIER: u64 = 1_664_525;
+const LCG_INCREMENT: u64 = 1_013_904_223;
+const LCG_MODULUS: u64 = 1 << 32;
+
+/// Draws `sample_size` of the votes in `counts` as `reveal_sample` does, uniformly and with
+/// replacement, and counts how many of the draws went to each option.
+///
+/// # Arguments
+/// * `counts` - The vote count of each of the poll's options
+/// * `sample_size` - How many votes to draw
+/// * `seed` - Seeds the draws
+///
+/// # Returns
+/// The sample's count for each option, summing to `sample_size` unless nobody voted
+pub fn sample_counts(counts: &[u64], sample_size: u8, seed: u64) -> Vec<u64> {
+    let total = counts.iter().fold(0u64, |total, &count| total.saturating_add(count));
+    let mut sample = vec![0; counts.len()];
+    let mut state = (seed ^ (seed >> 32)) % LCG_MODULUS;
+    for _ in 0..sample_size {
+        state = (LCG_MULTIPLIER * state + LCG_INCREMENT) % LCG_MODULUS;
+        let position = ((state as u128 * total as u128) >> 32) as u64;
+
+        let mut before = 0u64;
+        for (option, &count) in counts.iter().enumerate() {
+            let after = before.saturating_add(count);
+            if position >= before && position < after {
+                sample[option] += 1;
+            }
+            before = after;
+        }
+    }
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_ABSTAIN: u8 = u8::MAX;
+
+    #[test]
+    fn the_option_with_the_most_votes_wins() {
+        assert_eq!(determine_winner(&[4, 9, 1], 0, NO_ABSTAIN), (1, false));
+        assert_eq!(determine_winner(&[3, 5], 0, NO_ABSTAIN), (1, false));
+        assert_eq!(determine_winner(&[7, 0, 0, 0], 2, NO_ABSTAIN), (0, false));
+    }
+
+    #[test]
+    fn ties_go_to_the_first_option_from_the_offset() {
+        assert_eq!(determine_winner(&[5, 2, 5], 0, NO_ABSTAIN), (0, true));
+        assert_eq!(determine_winner(&[5, 2, 5], 1, NO_ABSTAIN), (2, true));
+        assert_eq!(determine_winner(&[5, 2, 5], 2, NO_ABSTAIN), (2, true));
+        // Wraps around past the last option
+        assert_eq!(determine_winner(&[5, 5, 2], 2, NO_ABSTAIN), (0, true));
+    }
+
+    #[test]
+    fn every_option_ties_with_no_votes() {
+        assert_eq!(determine_winner(&[0, 0, 0], 1, NO_ABSTAIN), (1, true));
+    }
+
+    #[test]
+    fn the_abstain_option_never_wins() {
+        assert_eq!(determine_winner(&[1, 9, 4], 0, 1), (2, false));
+        // Nor does it tie
+        assert_eq!(determine_winner(&[4, 4, 2], 0, 1), (0, false));
+    }
+
+    #[test]
+    fn a_sample_draws_one_vote_per_draw() {
+        for seed in 0..50 {
+            let sample = sample_counts(&[5, 0, 12], 32, seed);
+            assert_eq!(sample.iter().sum::<u64>(), 32);
+            // An option nobody voted for is never drawn
+            assert_eq!(sample[1], 0);
+        }
+        assert_eq!(sample_counts(&[0, 0, 0], 32, 7), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn the_same_seed_draws_the_same_sample() {
+        assert_eq!(sample_counts(&[40, 35, 25], 32, 99), sample_counts(&[40, 35, 25], 32, 99));
+        assert_ne!(sample_counts(&[40, 35, 25], 32, 1), sample_counts(&[40, 35, 25], 32, 2));
+    }
+
+    #[test]
+    fn a_skewed_poll_samples_to_its_true_winner() {
+        let counts = [150, 700, 100, 50];
+        let (true_winner, _) = determine_winner(&counts, 0, NO_ABSTAIN);
+        for seed in 0..200 {
+            let sample = sample_counts(&counts, 32, seed);
+            assert_eq!(determine_winner(&sample, 0, NO_ABSTAIN).0, true_winner, "seed {seed}");
+        }
+    }
+
+    /// Checks the rule against its definition for every count up to 3, across 2 to 4 options,
+    /// every tie-break offset and every abstain option
+    #[test]
+    fn exhaustively_matches_the_definition() {
+        for num_options in 2..=4usize {
+            for encoded in 0..4usize.pow(num_options as u32) {
+                let counts: Vec<u64> = (0..num_options)
+                    .map(|option| ((encoded / 4usize.pow(option as u32)) % 4) as u64)
+                    .collect();
+                for tie_break_offset in 0..num_options as u8 {
+                    for abstain_option in (0..num_options as u8).chain([NO_ABSTAIN]) {
+                        let (winner, is_tie) =
+                            determine_winner(&counts, tie_break_offset, abstain_option);
+                        let sides: Vec<usize> = (0..num_options)
+                            .filter(|&option| option != abstain_option as usize)
+                            .collect();
+                        let max_count = sides.iter().map(|&option| counts[option]).max().unwrap();
+                        let tied = sides
+                            .iter()
+                            .filter(|&&option| counts[option] == max_count)
+                            .count();
+
+                        assert_ne!(winner, abstain_option);
+                        assert_eq!(counts[winner as usize], max_count);
+                        assert_eq!(is_tie, tied > 1);
+                        // No tied option comes between the offset and the winner
+                        let mut option = tie_break_offset as usize;
+                        while option != winner as usize {
+                            assert!(
+                                option == abstain_option as usize || counts[option] < max_count,
+                                "{counts:?} offset {tie_break_offset}: option {option} \
+                                 should have won"
+                            );
+                            option = (option + 1) % num_options;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
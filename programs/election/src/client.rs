@@ -0,0 +1,255 @@
+//! Encodings shared by the program and off-chain Rust clients.
+//!
+//! The program builds its encrypted instructions' arguments from these functions, so a client
+//! that uses them queues the same arguments, in the same order and with the same account
+//! offsets, and derives the same addresses. Nothing here needs the Solana runtime, so it works
+//! off-chain: depend on this crate with the `client` feature, which leaves out the program's
+//! entrypoint.
+
+use anchor_lang::{prelude::Pubkey, AnchorSerialize, Discriminator};
+use solana_sha256_hasher::hashv;
+
+use crate::constants::MAX_BATCH_BALLOTS;
+use crate::state::{Poll, PollSettings, VoteComment};
+
+/// Where a poll's `PollTally` ciphertexts start in the poll account: after the discriminator
+/// and the bump
+pub const POLL_TALLY_OFFSET: u32 = (Poll::DISCRIMINATOR.len() + 1) as u32;
+
+/// Length of a poll's `PollTally` in bytes, each field stored as a 32-byte ciphertext
+pub const POLL_TALLY_LENGTH: u32 = (32 * Poll::POLL_TALLY_CIPHERTEXTS) as u32;
+
+/// Where a vote comment's ciphertexts start in its account: after the discriminator and the
+/// bump
+pub const VOTE_COMMENT_OFFSET: u32 = (VoteComment::DISCRIMINATOR.len() + 1) as u32;
+
+/// One argument of an encrypted instruction. Circuits take their arguments in order, so a
+/// list of these is the whole encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argument {
+    X25519Pubkey([u8; 32]),
+    PlaintextBool(bool),
+    PlaintextU8(u8),
+    PlaintextU64(u64),
+    PlaintextU128(u128),
+    EncryptedU8([u8; 32]),
+    /// Ciphertexts the cluster reads straight from an account: `length` bytes from `offset`
+    Account {
+        pubkey: Pubkey,
+        offset: u32,
+        length: u32,
+    },
+}
+
+/// Derives a poll's address from its authority and id
+pub fn poll_address(authority: &Pubkey, id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"poll", authority.as_ref(), id.to_le_bytes().as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of a voter's `VoterRecord` in a poll
+pub fn voter_record_address(poll: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"voter", poll.as_ref(), voter.as_ref()], &crate::ID)
+}
+
+/// The `voter_root` of a poll whose turnout is `voters`, in the order they were counted (their
+/// records' `turnout_index`), to check against what `prove_turnout` returns
+pub fn turnout_root<'a>(voters: impl IntoIterator<Item = &'a Pubkey>) -> [u8; 32] {
+    voters
+        .into_iter()
+        .fold([0; 32], |voter_root, voter| Poll::next_voter_root(&voter_root, voter))
+}
+
+/// The message a poll authority signs with Ed25519 to let a relayer create a poll for them with
+/// `create_poll_relayed`: `sha256("create_poll_relayed" || program id || authority || id ||
+/// relay_nonce || num_options || settings || question)`. The integers are little-endian (u32,
+/// u64, u8), the settings Borsh-encoded, and the question's UTF-8 bytes come last.
+///
+/// `relay_nonce` must be the authority's next one, from their `AuthorityState` (0 if they have
+/// none yet), so each consent creates one poll.
+pub fn relayed_poll_consent(
+    authority: &Pubkey,
+    id: u32,
+    relay_nonce: u64,
+    question: &str,
+    num_options: u8,
+    settings: &PollSettings,
+) -> [u8; 32] {
+    let mut settings_bytes = Vec::new();
+    settings
+        .serialize(&mut settings_bytes)
+        .expect("writing to a Vec can't fail");
+    hashv(&[
+        b"create_poll_relayed",
+        crate::ID.as_ref(),
+        authority.as_ref(),
+        &id.to_le_bytes(),
+        &relay_nonce.to_le_bytes(),
+        &[num_options],
+        &settings_bytes,
+        question.as_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// The arguments of the `vote` circuit for a vote in `poll`, stored at `poll_key`.
+///
+/// `vote_nonce` must be the nonce `choice` is encrypted with: in polls with `derive_nonces`,
+/// the poll's next derived nonce. `weight` is 1 unless the poll is token-weighted.
+pub fn vote_arguments(
+    poll_key: Pubkey,
+    poll: &Poll,
+    choice: [u8; 32],
+    vote_encryption_pubkey: [u8; 32],
+    vote_nonce: u128,
+    weight: u64,
+) -> [Argument; 8] {
+    [
+        Argument::X25519Pubkey(vote_encryption_pubkey),
+        Argument::PlaintextU128(vote_nonce),
+        Argument::EncryptedU8(choice),
+        Argument::PlaintextU8(poll.num_options),
+        // Token balances are public onchain, so encrypting the weight wouldn't hide anything
+        Argument::PlaintextU64(weight),
+        Argument::PlaintextU64(poll.settings.option_quota),
+        Argument::PlaintextU128(poll.nonce),
+        Argument::Account {
+            pubkey: poll_key,
+            offset: POLL_TALLY_OFFSET,
+            length: POLL_TALLY_LENGTH,
+        },
+    ]
+}
+
+/// One committed vote in a `vote_many` batch, as stored in its `VoterRecord`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ballot {
+    pub choice: [u8; 32],
+    pub vote_encryption_pubkey: [u8; 32],
+    pub vote_nonce: u128,
+    pub weight: u64,
+}
+
+/// The arguments of the `vote_many` circuit for a batch of 1 to `MAX_BATCH_BALLOTS` committed
+/// votes in `poll`, stored at `poll_key`.
+///
+/// The circuit always takes `MAX_BATCH_BALLOTS` ballots, so a smaller batch is padded by
+/// repeating its first ballot with no weight; the circuit leaves padding out of the tally.
+pub fn vote_many_arguments(poll_key: Pubkey, poll: &Poll, ballots: &[Ballot]) -> Vec<Argument> {
+    assert!(
+        (1..=MAX_BATCH_BALLOTS).contains(&ballots.len()),
+        "a batch holds 1 to MAX_BATCH_BALLOTS ballots"
+    );
+    let padded = |slot: usize| {
+        ballots.get(slot).copied().unwrap_or(Ballot {
+            weight: 0,
+            ..ballots[0]
+        })
+    };
+
+    let mut arguments = Vec::with_capacity(3 * MAX_BATCH_BALLOTS + MAX_BATCH_BALLOTS + 5);
+    for slot in 0..MAX_BATCH_BALLOTS {
+        let ballot = padded(slot);
+        arguments.push(Argument::X25519Pubkey(ballot.vote_encryption_pubkey));
+        arguments.push(Argument::PlaintextU128(ballot.vote_nonce));
+        arguments.push(Argument::EncryptedU8(ballot.choice));
+    }
+    arguments.push(Argument::PlaintextU8(ballots.len() as u8));
+    arguments.push(Argument::PlaintextU8(poll.num_options));
+    for slot in 0..MAX_BATCH_BALLOTS {
+        arguments.push(Argument::PlaintextU64(padded(slot).weight));
+    }
+    arguments.push(Argument::PlaintextU64(poll.settings.option_quota));
+    arguments.push(Argument::PlaintextU128(poll.nonce));
+    arguments.push(Argument::Account {
+        pubkey: poll_key,
+        offset: POLL_TALLY_OFFSET,
+        length: POLL_TALLY_LENGTH,
+    });
+    arguments
+}
+
+/// The seed `reveal_sample` draws a poll's sample with: the latest slot hash when the sample
+/// was queued, mixed with the poll's address so polls sampled in the same slot draw
+/// differently. `SampleRevealEvent` reports it, so anyone can redo the draw.
+pub fn sample_seed(slot_hash: &[u8; 32], poll_key: &Pubkey) -> u64 {
+    let digest = hashv(&[b"sample", slot_hash, poll_key.as_ref()]).to_bytes();
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+/// The arguments of the `reveal_result` circuit for `poll`, stored at `poll_key`
+pub fn reveal_result_arguments(poll_key: Pubkey, poll: &Poll) -> [Argument; 7] {
+    [
+        Argument::PlaintextU8(poll.num_options),
+        Argument::PlaintextU8(poll.tie_break_offset()),
+        Argument::PlaintextU8(poll.abstain_index()),
+        Argument::PlaintextBool(poll.settings.reveal_winner_count),
+        Argument::PlaintextU64(poll.settings.option_quota),
+        Argument::PlaintextU128(poll.nonce),
+        // The whole PollTally, since the spoiled count comes after the vote counters
+        Argument::Account {
+            pubkey: poll_key,
+            offset: POLL_TALLY_OFFSET,
+            length: POLL_TALLY_LENGTH,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_address_matches_the_program_seeds() {
+        let authority = Pubkey::new_unique();
+        let id = 42u32;
+        // As in the `seeds` constraint on every instruction's `poll_account`
+        let expected = Pubkey::find_program_address(
+            &[b"poll", authority.as_ref(), id.to_le_bytes().as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(poll_address(&authority, id), expected);
+    }
+
+    #[test]
+    fn voter_record_address_matches_the_program_seeds() {
+        let poll = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        // As in the `seeds` constraint on `vote`'s `voter_record`
+        let expected = Pubkey::find_program_address(
+            &[b"voter", poll.as_ref(), voter.as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(voter_record_address(&poll, &voter), expected);
+    }
+
+    #[test]
+    fn turnout_root_changes_with_each_voter_and_their_order() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        let mut poll_root = [0; 32];
+        let mut roots = vec![turnout_root([])];
+        for voter in [&first, &second] {
+            poll_root = Poll::next_voter_root(&poll_root, voter);
+            roots.push(poll_root);
+        }
+        assert_eq!(roots[0], [0; 32]);
+        assert_eq!(turnout_root([&first]), roots[1]);
+        assert_eq!(turnout_root([&first, &second]), roots[2]);
+        assert_ne!(roots[1], roots[0]);
+        assert_ne!(roots[2], roots[1]);
+        assert_ne!(turnout_root([&second, &first]), roots[2]);
+    }
+
+    #[test]
+    fn sample_seeds_differ_between_slots_and_polls() {
+        let poll = Pubkey::new_unique();
+        let seed = sample_seed(&[1; 32], &poll);
+        assert_eq!(sample_seed(&[1; 32], &poll), seed);
+        assert_ne!(sample_seed(&[2; 32], &poll), seed);
+        assert_ne!(sample_seed(&[1; 32], &Pubkey::new_unique()), seed);
+    }
+}
@@ -4,3 +4,125 @@ use arcium_anchor::prelude::*;
 pub const COMP_DEF_OFFSET_CREATE_POLL: u32 = comp_def_offset("create_poll");
 pub const COMP_DEF_OFFSET_VOTE: u32 = comp_def_offset("vote");
 pub const COMP_DEF_OFFSET_REVEAL: u32 = comp_def_offset("reveal_result");
+pub const COMP_DEF_OFFSET_REVEAL_WEIGHT_QUORUM: u32 = comp_def_offset("reveal_weight_quorum");
+pub const COMP_DEF_OFFSET_AGGREGATE_CHILD: u32 = comp_def_offset("aggregate_child");
+pub const COMP_DEF_OFFSET_REVEAL_DECIDED: u32 = comp_def_offset("reveal_decided");
+pub const COMP_DEF_OFFSET_REVEAL_TOP_K: u32 = comp_def_offset("reveal_top_k");
+pub const COMP_DEF_OFFSET_REVEAL_TALLY_SHARED: u32 = comp_def_offset("reveal_tally_shared");
+pub const COMP_DEF_OFFSET_REVEAL_REFERENDUM: u32 = comp_def_offset("reveal_referendum");
+pub const COMP_DEF_OFFSET_REVEAL_TO: u32 = comp_def_offset("reveal_to");
+pub const COMP_DEF_OFFSET_REVEAL_SPREAD: u32 = comp_def_offset("reveal_spread");
+pub const COMP_DEF_OFFSET_VOTE_QUADRATIC: u32 = comp_def_offset("vote_quadratic");
+pub const COMP_DEF_OFFSET_REVEAL_WITH_INTERVAL: u32 = comp_def_offset("reveal_with_interval");
+pub const COMP_DEF_OFFSET_REVEAL_BOOL: u32 = comp_def_offset("reveal_bool");
+pub const COMP_DEF_OFFSET_REVEAL_FULL_RANKING: u32 = comp_def_offset("reveal_full_ranking");
+pub const COMP_DEF_OFFSET_ROTATE_NONCE: u32 = comp_def_offset("rotate_nonce");
+pub const COMP_DEF_OFFSET_REVEAL_SAMPLE: u32 = comp_def_offset("reveal_sample");
+pub const COMP_DEF_OFFSET_VOTE_MANY: u32 = comp_def_offset("vote_many");
+pub const COMP_DEF_OFFSET_VERIFY_VOTE_CLAIM: u32 = comp_def_offset("verify_vote_claim");
+pub const COMP_DEF_OFFSET_REVEAL_COMMENT: u32 = comp_def_offset("reveal_comment");
+pub const COMP_DEF_OFFSET_REVEAL_DELTA: u32 = comp_def_offset("reveal_delta");
+
+/// Number of encrypted vote counters every poll carries. A poll's `num_options` can't exceed it.
+/// Must match `MAX_OPTIONS` in encrypted-ixs/src/lib.rs.
+pub const MAX_OPTIONS: usize = 16;
+
+/// Most options `reveal_top_k` can rank. Must match `MAX_TOP_K` in encrypted-ixs/src/lib.rs.
+pub const MAX_TOP_K: usize = 3;
+
+/// Most votes `reveal_sample` can draw. Must match `MAX_SAMPLE_SIZE` in encrypted-ixs/src/lib.rs.
+pub const MAX_SAMPLE_SIZE: usize = 32;
+
+/// Fewest votes `reveal_sample` draws. Below this the estimate is too noisy to be worth
+/// revealing, and the authority could probe the tally a few votes at a time.
+pub const MIN_SAMPLE_SIZE: usize = 8;
+
+/// Fewest options a poll can have, since a single option isn't a choice
+pub const MIN_OPTIONS: usize = 2;
+
+/// Most polls `aggregate_polls` sums in one call, keeping the transaction within account limits
+pub const MAX_AGGREGATE_POLLS: usize = 20;
+
+/// Most voter records `batch_check_voted` checks in one call, one bit of its result each
+pub const MAX_BATCH_VOTER_RECORDS: usize = 32;
+
+/// Most committed votes `vote_many` counts in one computation.
+/// Must match `MAX_BATCH_BALLOTS` in encrypted-ixs/src/lib.rs.
+pub const MAX_BATCH_BALLOTS: usize = 10;
+
+/// Length in bytes of a vote's encrypted comment. Each byte is a 32-byte ciphertext in the
+/// vote transaction, so this is kept small enough for the transaction to fit.
+/// Must match `MAX_COMMENT_LENGTH` in encrypted-ixs/src/lib.rs.
+pub const MAX_COMMENT_LENGTH: usize = 8;
+
+/// Length of `get_result_packed`'s result: id (4), num_options (1), winner (1), is_tie (1)
+/// and total_votes (8)
+pub const PACKED_RESULT_LENGTH: usize = 15;
+
+/// Most voters a `VoterList` can hold, so the account stays under the 10KB a program can allocate
+/// in one instruction. Larger electorates should use an off-chain list committed to by a Merkle root.
+pub const MAX_VOTER_LIST_SIZE: usize = 200;
+
+/// Most snapshots a `TallyHistory` keeps before overwriting the oldest, so the account stays
+/// under the 10KB a program can allocate in one instruction
+pub const MAX_TALLY_SNAPSHOTS: usize = 16;
+
+/// Most entries an `AuthorityLog` keeps before overwriting the oldest
+pub const MAX_AUTHORITY_LOG_ENTRIES: usize = 32;
+
+/// Most polls an `AuthorityPollIndex` lists. 64 pubkeys keep the index at about 2KB of rent.
+pub const MAX_INDEXED_POLLS: usize = 64;
+
+/// Seconds between `reveal_result` calls on the same poll, until the admin changes it
+pub const DEFAULT_REVEAL_COOLDOWN: i64 = 30;
+
+/// Highest `computation_priority` a poll can have
+pub const MAX_COMPUTATION_PRIORITY: u8 = 10;
+
+/// Compute unit price, in micro-lamports, each level of `computation_priority` adds to a poll's
+/// computations, so the Arcium cluster's callback transactions land sooner
+pub const CU_PRICE_MICRO_PER_PRIORITY: u64 = 10_000;
+
+/// Slots, about a minute, clients are told to wait before resending an action whose computation
+/// failed while the Arcium cluster was rotating its keys, giving the rotation time to finish
+pub const RETRY_AFTER_ROTATION_SLOTS: u64 = 150;
+
+/// Slots, about an hour, a poll's tally can be held for a computation before the poll authority
+/// can release it with `release_tally`, in case the computation's callback never lands
+pub const TALLY_HOLD_TIMEOUT_SLOTS: u64 = 9_000;
+
+/// Lamports the payer must have left, after paying for any accounts the instruction creates,
+/// to queue an MPC computation. A conservative estimate of the computation account's rent and
+/// the cluster's fee, so an underfunded payer gets a clear error rather than a failed CPI.
+pub const MIN_COMPUTATION_BALANCE: u64 = 10_000_000;
+
+/// Largest a poll account may be, in bytes. Accounts created by another program, as `init`
+/// does, can be at most 10 KiB in one instruction.
+pub const MAX_POLL_SIZE: usize = 10 * 1024;
+
+/// Maximum length of the poll question, in bytes. Polls created while it was lower are too
+/// small for a longer question until `resize_poll` grows them.
+pub const MAX_QUESTION_LENGTH: usize = 200;
+
+/// Maximum length of an option label in a `PollTemplate`, in bytes
+pub const MAX_OPTION_LABEL_LENGTH: usize = 32;
+
+/// Maximum length of an option's metadata URI, in bytes
+pub const MAX_OPTION_URI_LENGTH: usize = 128;
+
+/// The `EncScheme`s the vote circuits can decrypt, as a `supported_schemes` bitmask: only
+/// `X25519Rescue`
+pub const DECRYPTABLE_SCHEMES: u8 = 1;
+
+/// Most a conviction vote's weight can be multiplied by, however early it's cast
+pub const MAX_CONVICTION_MULTIPLIER: u64 = 10;
+
+/// Weight of a single vote in polls that aren't token-weighted, so every vote counts equally
+pub const VOTE_WEIGHT: u64 = 1;
+
+/// Longest a poll's result can be embargoed after it closes, in seconds: a year
+pub const MAX_EMBARGO_SECS: i64 = 365 * 24 * 60 * 60;
+
+/// Most decimal places `weight_scale` can drop from a balance: 10^19 is the largest power of
+/// ten a `u64` holds
+pub const MAX_WEIGHT_SCALE: u8 = 19;
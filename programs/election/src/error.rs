@@ -8,4 +8,189 @@ pub enum ErrorCode {
     AbortedComputation,
     #[msg("Cluster not set")]
     ClusterNotSet,
+    #[msg("The question is too long")]
+    QuestionTooLong,
+    #[msg("Voting has already started on this poll")]
+    VotingAlreadyStarted,
+    #[msg("The computation definition has already been initialized")]
+    CompDefAlreadyInitialized,
+    #[msg("A poll needs at least 2 options")]
+    TooFewOptions,
+    #[msg("The poll has more options than the program supports")]
+    TooManyOptions,
+    #[msg("The poll's close time is in the past")]
+    CloseTimeInPast,
+    #[msg("Voting on this poll has closed")]
+    VotingClosed,
+    #[msg("Too many polls to aggregate in one instruction")]
+    TooManyPolls,
+    #[msg("The account is not a poll owned by this program")]
+    InvalidPollAccount,
+    #[msg("The poll is not a child of the given parent poll")]
+    NotAChildPoll,
+    #[msg("The child poll has already been aggregated into its parent")]
+    AlreadyAggregated,
+    #[msg("Voting on the child poll hasn't closed yet")]
+    VotingNotClosed,
+    #[msg("The polls have different numbers of options")]
+    OptionCountMismatch,
+    #[msg("The voter list is full")]
+    VoterListFull,
+    #[msg("The voter is not on the poll's voter list")]
+    VoterNotEligible,
+    #[msg("The voter's token account isn't for the poll's weight mint, or isn't theirs")]
+    InvalidWeightTokenAccount,
+    #[msg("The voter holds none of the poll's weight token, or less than one unit after weight_scale")]
+    NoVotingWeight,
+    #[msg("The votes still to come in a token-weighted poll can't be bounded")]
+    TokenWeightedPoll,
+    #[msg("The poll has been finalized")]
+    PollFinalized,
+    #[msg("The poll's result hasn't been revealed")]
+    PollNotRevealed,
+    #[msg("The poll's encryption mode doesn't allow this reveal")]
+    WrongEncMode,
+    #[msg("Shared encryption mode polls need a tally recipient")]
+    MissingTallyRecipient,
+    #[msg("The poll was revealed too recently")]
+    RevealTooSoon,
+    #[msg("The poll hasn't been activated")]
+    PollNotActivated,
+    #[msg("The poll has already been activated")]
+    PollAlreadyActivated,
+    #[msg("The computation priority is above MAX_COMPUTATION_PRIORITY")]
+    InvalidComputationPriority,
+    #[msg("The poll is frozen")]
+    PollFrozen,
+    #[msg("The poll must be frozen, or voting closed, before it's revealed")]
+    PollNotFrozen,
+    #[msg("Referendums need 2 options and a pass threshold of at most 100%, and can't be quadratic")]
+    InvalidReferendum,
+    #[msg("The poll isn't a referendum")]
+    // No longer returned, as WrongPollType replaced it. Kept so later error codes don't change.
+    NotAReferendum,
+    #[msg("The poll's encrypted counters haven't been initialized yet")]
+    PollNotInitialized,
+    #[msg("The poll hasn't been finalized")]
+    PollNotFinalized,
+    #[msg("The poll doesn't publish its result cross-chain")]
+    NotCrossChain,
+    #[msg("No Wormhole program has been configured")]
+    WormholeNotConfigured,
+    #[msg("The Wormhole bridge account is invalid")]
+    InvalidWormholeBridge,
+    #[msg("The reveal cooldown can't be negative")]
+    InvalidRevealCooldown,
+    #[msg("The poll has no expected voter count")]
+    ExpectedVotersNotSet,
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("k must be between 1 and the smaller of MAX_TOP_K and the poll's option count")]
+    InvalidTopK,
+    #[msg("The computation's output isn't signed by the Arcium cluster's current key")]
+    ComputationSignatureInvalid,
+    #[msg("The Arcium cluster has no key to check computation outputs with")]
+    ClusterKeyUnset,
+    #[msg("The computation account doesn't hold a queued computation")]
+    InvalidComputationAccount,
+    #[msg("The computation's output doesn't match the circuit's output type")]
+    MalformedComputationOutput,
+    #[msg("The payer doesn't have enough SOL to pay for the MPC computation")]
+    InsufficientFunds,
+    #[msg("The result is embargoed until embargo_secs after the poll closes")]
+    ResultEmbargoed,
+    #[msg("An embargo must be from 0 to MAX_EMBARGO_SECS and needs the poll to have a close_ts")]
+    InvalidEmbargo,
+    #[msg("Quadratic polls can't be token-weighted")]
+    InvalidQuadraticPoll,
+    #[msg("Quadratic polls are voted in with vote_quadratic")]
+    // No longer returned, as WrongPollType replaced it. Kept so later error codes don't change.
+    QuadraticPoll,
+    #[msg("The poll isn't a quadratic poll")]
+    // No longer returned, as WrongPollType replaced it. Kept so later error codes don't change.
+    NotAQuadraticPoll,
+    #[msg("The abstain option must be one of the poll's options, leaving at least two others")]
+    InvalidAbstainOption,
+    #[msg("Too few votes were cast to meet the poll's quorum")]
+    QuorumNotMet,
+    #[msg("A reveal is still pending for this poll")]
+    RevealPending,
+    #[msg("Another update to the poll's tally is still pending, try again once it lands")]
+    TallyBusy,
+    #[msg("The voter has already voted in this poll")]
+    AlreadyVoted,
+    #[msg("The program must be paused first")]
+    ProgramNotPaused,
+    #[msg("Callbacks can only be run by the Arcium program")]
+    NotArciumCallback,
+    #[msg("Option quotas can't be used in token-weighted or quadratic polls")]
+    InvalidOptionQuota,
+    #[msg("This poll's result can only be revealed once")]
+    AlreadyRevealed,
+    #[msg("This poll's reveal can't be cranked, as it wasn't created with auto_reveal")]
+    AutoRevealDisabled,
+    #[msg("The poll's close_ts hasn't passed, so its reveal can't be cranked yet")]
+    RevealNotDue,
+    #[msg("The vote encryption key is all zeroes or another x25519 point of small order")]
+    InvalidEncryptionKey,
+    #[msg("This instruction is for a different type of poll")]
+    WrongPollType,
+    #[msg("Only polls with exactly 2 options can be revealed as a bool")]
+    NotABinaryPoll,
+    #[msg("Polls with a vote fee need the fee vault passed to vote")]
+    MissingFeeVault,
+    #[msg("The fee vault has no fees to withdraw")]
+    NoFeesToWithdraw,
+    #[msg("The authority already has the most active polls allowed")]
+    TooManyActivePolls,
+    #[msg("The Arcium cluster was rotating its keys; retry after the poll's retry_after_slot")]
+    ClusterRotating,
+    #[msg("Conviction polls need a close_ts and can't have quadratic credits or an option quota")]
+    InvalidConvictionSettings,
+    #[msg("The voter has no committed vote waiting to be counted in this poll")]
+    NoCommittedVote,
+    #[msg("Option label exceeds maximum length")]
+    OptionLabelTooLong,
+    #[msg("A template's duration can't be negative")]
+    InvalidTemplateDuration,
+    #[msg("Option URIs must be given for every option or none, each at most 128 bytes")]
+    InvalidOptionUris,
+    #[msg("Too many voter records to check in one instruction")]
+    TooManyVoterRecords,
+    #[msg("The account is not the voter's record owned by this program")]
+    InvalidVoterRecord,
+    #[msg("Slot windows need use_slots, no close_ts, and an end_slot after start_slot and now")]
+    InvalidSlotWindow,
+    #[msg("Voting on this poll hasn't started yet")]
+    VotingNotStarted,
+    #[msg("The poll doesn't accept votes encrypted with this scheme")]
+    UnsupportedScheme,
+    #[msg("The previous instruction must be an Ed25519 signature by the authority over the poll's consent message")]
+    InvalidConsentSignature,
+    #[msg("The consent was signed for a different relay nonce than the authority's next one")]
+    StaleRelayNonce,
+    #[msg("The poll was created with reveal_disabled, so its tally can never be revealed")]
+    RevealDisabled,
+    #[msg("The sample size must be between MIN_SAMPLE_SIZE and MAX_SAMPLE_SIZE")]
+    InvalidSampleSize,
+    #[msg("The poll can't move from its current status to that one")]
+    InvalidStateTransition,
+    #[msg("A batch must count between 1 and MAX_BATCH_BALLOTS committed votes")]
+    InvalidBallotCount,
+    #[msg("The same voter record appears twice in the batch")]
+    DuplicateBallot,
+    #[msg("The voter has no vote in this poll to check a claim against")]
+    NoVoteToClaim,
+    #[msg("This authority already has a poll with this id")]
+    PollIdInUse,
+    #[msg("Votes with a comment need the vote comment account passed to vote")]
+    MissingVoteComment,
+    #[msg("The comment must be encrypted with a different nonce from the choice")]
+    CommentNonceReused,
+    #[msg("weight_scale must be at most MAX_WEIGHT_SCALE, and 0 unless the poll is token-weighted")]
+    InvalidWeightScale,
+    #[msg("A receipt-free poll can't be token-weighted, quadratic or a referendum")]
+    InvalidReceiptFreePoll,
+    #[msg("The poll's tally isn't held, or hasn't been held for TALLY_HOLD_TIMEOUT_SLOTS yet")]
+    TallyHoldNotExpired,
 }
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    election::{ActivatePoll, CreatePollCallback},
+    error::ErrorCode,
+    handlers::create_poll::open_poll,
+    state::{PollSettings, PollStatus},
+};
+
+/// Opens a poll made by `preallocate_poll`, queuing the `create_poll` computation.
+///
+/// Takes the same arguments, and applies the same checks, as `create_poll`. The MPC cost is
+/// only incurred now, rather than when the account was allocated.
+///
+/// # Arguments
+/// * `id` - The preallocated poll's ID
+/// * `question` - The poll question voters will respond to
+/// * `num_options` - How many options voters can choose between (2 to `MAX_OPTIONS`)
+/// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+/// * `settings` - Optional poll behaviour
+pub fn activate_poll(
+    ctx: Context<ActivatePoll>,
+    computation_offset: u64,
+    id: u32,
+    question: String,
+    num_options: u8,
+    nonce: u128,
+    settings: PollSettings,
+) -> Result<()> {
+    require!(
+        ctx.accounts.poll_account.status == PollStatus::Created,
+        ErrorCode::PollAlreadyActivated
+    );
+
+    msg!(
+        "Activating poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    let poll_key = ctx.accounts.poll_account.key();
+    let nonce = open_poll(
+        &mut ctx.accounts.poll_account,
+        poll_key,
+        question,
+        num_options,
+        nonce,
+        settings,
+    )?;
+    ctx.accounts.config.total_polls += 1;
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        ctx.accounts.payer.key(),
+        ctx.accounts.config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.payer.key(),
+        poll_key,
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![CreatePollCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    Ok(())
+}
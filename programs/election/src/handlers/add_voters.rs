@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_VOTER_LIST_SIZE,
+    election::AddVoters,
+    error::ErrorCode,
+    state::AuthorityAction,
+};
+
+/// Adds wallets to a poll's voter list, creating the list on first use.
+///
+/// Only the poll authority can add voters. Wallets already on the list are skipped,
+/// and the whole call fails if the list would exceed `MAX_VOTER_LIST_SIZE`.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `voters` - Wallets to allow to vote
+pub fn add_voters(ctx: Context<AddVoters>, id: u32, voters: Vec<Pubkey>) -> Result<()> {
+    let voter_list = &mut ctx.accounts.voter_list;
+    voter_list.bump = ctx.bumps.voter_list;
+    voter_list.poll = ctx.accounts.poll_account.key();
+
+    for voter in voters {
+        if !voter_list.voters.contains(&voter) {
+            voter_list.voters.push(voter);
+        }
+    }
+    require!(
+        voter_list.voters.len() <= MAX_VOTER_LIST_SIZE,
+        ErrorCode::VoterListFull
+    );
+
+    msg!(
+        "Voter list for poll {} now has {} voters",
+        id,
+        voter_list.voters.len()
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::AddVoters,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::{POLL_TALLY_LENGTH, POLL_TALLY_OFFSET},
+    error::ErrorCode,
+    election::{AggregateChild, AggregateChildCallback, AggregateChildOutput, InitAggregateChildCompDef},
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `aggregate_child` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `aggregate_child` encrypted instruction.
+pub fn init_aggregate_child_comp_def(ctx: Context<InitAggregateChildCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Adds a child poll's encrypted tally into its parent poll's tally.
+///
+/// Only the parent poll's authority can call this. The child must have named the parent
+/// when it was created, and voting on the child must have closed so no votes are missed.
+/// Each child can only be aggregated once; call this once per child to federate several,
+/// or to merge the shards of a poll split across several accounts for throughput.
+///
+/// # Arguments
+/// * `parent_id` - The parent poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn aggregate_child(
+    ctx: Context<AggregateChild>,
+    computation_offset: u64,
+    parent_id: u32,
+) -> Result<()> {
+    let parent_poll = &ctx.accounts.parent_poll;
+    let child_poll = &ctx.accounts.child_poll;
+
+    require!(
+        child_poll.settings.parent == Some(parent_poll.key()),
+        ErrorCode::NotAChildPoll
+    );
+    require!(!child_poll.aggregated, ErrorCode::AlreadyAggregated);
+    require!(
+        child_poll.voting_closed(&Clock::get()?),
+        ErrorCode::VotingNotClosed
+    );
+    require!(
+        child_poll.num_options == parent_poll.num_options,
+        ErrorCode::OptionCountMismatch
+    );
+    require!(!parent_poll.tally_pending, ErrorCode::TallyBusy);
+    // Otherwise the child's votes could be revealed as part of the parent's tally
+    require!(
+        !child_poll.settings.reveal_disabled || parent_poll.settings.reveal_disabled,
+        ErrorCode::RevealDisabled
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(parent_poll.nonce)
+        .account(
+            parent_poll.key(),
+            POLL_TALLY_OFFSET,
+            POLL_TALLY_LENGTH,
+        )
+        .plaintext_u128(child_poll.nonce)
+        .account(
+            child_poll.key(),
+            POLL_TALLY_OFFSET,
+            POLL_TALLY_LENGTH,
+        )
+        .build();
+
+    // Marked now rather than in the callback so the same child can't be queued twice meanwhile
+    ctx.accounts.child_poll.aggregated = true;
+    let computation = ctx.accounts.computation_account.key();
+    ctx.accounts.parent_poll.hold_tally_at(Clock::get()?.slot, computation);
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.parent_poll.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![AggregateChildCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[
+                CallbackAccount {
+                    pubkey: ctx.accounts.parent_poll.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.child_poll.key(),
+                    is_writable: false,
+                },
+            ]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Aggregation of child poll {} into poll {} queued (computation offset {})",
+        ctx.accounts.child_poll.id,
+        ctx.accounts.parent_poll.id,
+        computation_offset
+    );
+
+    Ok(())
+}
+
+pub fn aggregate_child_callback(
+    ctx: Context<AggregateChildCallback>,
+    output: SignedComputationOutputs<AggregateChildOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // As in `vote_callback`, aggregating after release_tally could undo the parent's later votes
+    if !ctx.accounts.parent_poll.holds_tally_for(ctx.accounts.computation_account.key()) {
+        msg!(
+            "Aggregating into poll {} landed after its tally was released",
+            ctx.accounts.parent_poll.id
+        );
+        return Ok(());
+    }
+
+    let parent_tally = match verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    ) {
+        Ok(AggregateChildOutput { field_0 }) => field_0,
+        Err(error) => {
+            // Failing would leave the parent's tally held forever
+            ctx.accounts.parent_poll.tally_pending = false;
+            msg!("Aggregating into poll {} failed: {}", ctx.accounts.parent_poll.id, error);
+            return Ok(());
+        }
+    };
+
+    let child_votes = ctx.accounts.child_poll.total_votes;
+    let parent_poll = &mut ctx.accounts.parent_poll;
+    parent_poll.set_poll_tally(parent_tally.ciphertexts, parent_tally.nonce);
+    parent_poll.tally_pending = false;
+    parent_poll.total_votes = parent_poll.total_votes.saturating_add(child_votes);
+
+    msg!(
+        "Child poll {} aggregated into poll {} (computation {})",
+        ctx.accounts.child_poll.id,
+        parent_poll.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    Ok(())
+}
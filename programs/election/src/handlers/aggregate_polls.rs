@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_AGGREGATE_POLLS,
+    election::AggregatePolls,
+    error::ErrorCode,
+    state::{AggregateEvent, Poll},
+};
+
+/// Sums the public counters of several polls and emits them as one `AggregateEvent`.
+///
+/// The polls are passed as remaining accounts, so a dashboard can summarize up to
+/// `MAX_AGGREGATE_POLLS` polls with a single call instead of one RPC read per poll.
+/// Nothing is written: this only reads the polls' public fields.
+pub fn aggregate_polls(ctx: Context<AggregatePolls>) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_AGGREGATE_POLLS,
+        ErrorCode::TooManyPolls
+    );
+
+    let mut total_votes: u64 = 0;
+    let mut total_revealed: u32 = 0;
+
+    for poll_info in ctx.remaining_accounts {
+        require_keys_eq!(*poll_info.owner, crate::ID, ErrorCode::InvalidPollAccount);
+        let poll = Poll::try_deserialize(&mut &poll_info.data.borrow()[..])
+            .map_err(|_| ErrorCode::InvalidPollAccount)?;
+
+        total_votes += poll.total_votes;
+        if poll.status.is_revealed() {
+            total_revealed += 1;
+        }
+    }
+
+    emit!(AggregateEvent {
+        total_polls: ctx.remaining_accounts.len() as u32,
+        total_votes,
+        total_revealed,
+    });
+
+    Ok(())
+}
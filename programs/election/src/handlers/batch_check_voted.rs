@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_BATCH_VOTER_RECORDS,
+    election::BatchCheckVoted,
+    error::ErrorCode,
+    state::VoterRecord,
+};
+
+/// Returns which of several polls a voter has voted in, as a bitmask: bit `i` is set if the
+/// `i`th remaining account is the voter's `VoterRecord` for a vote they've cast.
+///
+/// A dashboard passes the voter's record address in each poll (`["voter", poll, voter]`), up
+/// to `MAX_BATCH_VOTER_RECORDS` of them, instead of reading each poll's record with its own
+/// RPC call. A record that doesn't exist yet means the voter hasn't voted in that poll.
+/// This is a read-only view: call it via simulation and read the u32 from return data.
+///
+/// # Arguments
+/// * `voter` - The wallet whose votes to check
+pub fn batch_check_voted(ctx: Context<BatchCheckVoted>, voter: Pubkey) -> Result<u32> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_BATCH_VOTER_RECORDS,
+        ErrorCode::TooManyVoterRecords
+    );
+
+    let mut voted: u32 = 0;
+    for (index, voter_record_info) in ctx.remaining_accounts.iter().enumerate() {
+        if voter_record_info.data_is_empty() {
+            continue;
+        }
+        require_keys_eq!(
+            *voter_record_info.owner,
+            crate::ID,
+            ErrorCode::InvalidVoterRecord
+        );
+        let voter_record = VoterRecord::try_deserialize(&mut &voter_record_info.data.borrow()[..])
+            .map_err(|_| ErrorCode::InvalidVoterRecord)?;
+        require_keys_eq!(voter_record.voter, voter, ErrorCode::InvalidVoterRecord);
+
+        // A record whose vote was given back to the voter after a failed computation has no poll
+        if voter_record.poll != Pubkey::default() {
+            voted |= 1 << index;
+        }
+    }
+
+    Ok(voted)
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::ClonePoll, handlers::create_poll::require_new_poll};
+
+/// Copies a poll to a new id, e.g. to recover from an id collision without losing its votes.
+///
+/// The encrypted tally, its nonce, the question and every other field are copied byte for
+/// byte, so the clone can be revealed exactly like the source. No MPC is needed: the
+/// ciphertexts stay valid because they're still read with the same nonce under the MXE's key.
+///
+/// Voter records are tied to the source poll's address, so they don't carry over. If the
+/// source has been voted on, the clone is closed to voting straight away so nobody can vote
+/// twice; otherwise it keeps the source's settings.
+///
+/// # Arguments
+/// * `source_id` - The id of the poll to copy
+/// * `new_id` - The id to create the clone under
+pub fn clone_poll(ctx: Context<ClonePoll>, source_id: u32, new_id: u32) -> Result<()> {
+    require_new_poll(&ctx.accounts.poll_account)?;
+
+    let mut clone = (**ctx.accounts.source_poll).clone();
+    clone.bump = ctx.bumps.poll_account;
+    clone.id = new_id;
+    // A reveal queued on the source calls back to the source, not the clone
+    clone.reveal_pending = false;
+    clone.tally_pending = false;
+    if clone.total_votes > 0 {
+        clone.settings.close_ts = Clock::get()?.unix_timestamp;
+    }
+    // The counters sum over every poll, so the clone counts as well as its source
+    let config = &mut ctx.accounts.config;
+    config.total_polls += 1;
+    config.total_votes_all_polls += clone.total_votes;
+    if clone.status.is_revealed() {
+        config.total_revealed += 1;
+    }
+    // It's also another open poll for the authority
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        ctx.accounts.authority.key(),
+        config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.authority.key(),
+        ctx.accounts.poll_account.key(),
+    );
+    ctx.accounts.poll_account.set_inner(clone);
+
+    msg!("Cloned poll {} to poll {}", source_id, new_id);
+
+    Ok(())
+}
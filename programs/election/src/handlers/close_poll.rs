@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::ClosePoll, error::ErrorCode, state::PollStatus};
+
+/// Closes a poll, returning its rent to the poll authority.
+///
+/// Only polls that have stopped taking votes, by being frozen or reaching their `close_ts`,
+/// or that never took any, because their counters were never stored, can be closed. A poll can't be closed while a `reveal_result` computation is pending,
+/// since its callback would then target a closed account; wait for the reveal to complete.
+/// The poll stops counting towards the authority's `max_active_polls`, and is taken off their
+/// `AuthorityPollIndex`.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn close_poll(ctx: Context<ClosePoll>, id: u32) -> Result<()> {
+    let poll = &mut ctx.accounts.poll_account;
+    require!(
+        !poll.status.is_initialized()
+            || poll.status.is_frozen()
+            || poll.voting_closed(&Clock::get()?),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.reveal_pending, ErrorCode::RevealPending);
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    // A preallocated poll that was never activated was never counted
+    let counted = poll.status != PollStatus::Created;
+    poll.transition(PollStatus::Cancelled)?;
+
+    if counted {
+        ctx.accounts.authority_state.remove_poll(
+            ctx.bumps.authority_state,
+            ctx.accounts.authority.key(),
+        );
+    }
+    ctx.accounts.poll_index.remove_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.authority.key(),
+        ctx.accounts.poll_account.key(),
+    );
+
+    msg!("Poll {} closed", id);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::vote_arguments,
+    error::ErrorCode,
+    state::{EncChoice, EncPubkey, EncScheme, PollType},
+    election::{CommitVote, FinalizeVote, VoteCallback},
+    handlers::{
+        create_poll::{arg_builder, event_cpi_callback_accounts, require_computation_funds},
+        vote::{check_voter, collect_vote_fee, commit_ballot, hold_tally, vote_weight},
+    },
+};
+
+/// Commits an encrypted vote without counting it, the first half of a two-phase vote.
+///
+/// The vote is checked and stored in the voter's `VoterRecord` exactly as `vote` would, fee
+/// and all, but nothing is queued. `finalize_vote` then counts it. If counting fails, the
+/// committed vote is kept and `finalize_vote` can simply be sent again, where a failed `vote`
+/// can leave a voter who has voted but whose vote isn't counted.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `choice` - Encrypted vote choice (an option index below the poll's `num_options`)
+/// * `vote_encryption_pubkey` - Voter's public key for encryption
+/// * `vote_nonce` - Cryptographic nonce for the vote encryption. Ignored in polls with
+///   `derive_nonces`, where the choice must be encrypted with the poll's next derived nonce.
+/// * `scheme` - How the choice is encrypted, one of the poll's `supported_schemes`
+#[allow(unused_variables)]
+pub fn commit_vote(
+    ctx: Context<CommitVote>,
+    poll_id: u32,
+    choice: EncChoice,
+    vote_encryption_pubkey: EncPubkey,
+    vote_nonce: u128,
+    scheme: EncScheme,
+) -> Result<()> {
+    require!(
+        ctx.accounts.poll_account.poll_type != PollType::Quadratic,
+        ErrorCode::WrongPollType
+    );
+    collect_vote_fee(
+        &ctx.accounts.poll_account,
+        ctx.accounts.fee_vault.as_deref_mut(),
+        ctx.bumps.fee_vault,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    check_voter(&ctx.accounts.poll_account, &ctx.accounts.voter_list, &ctx.accounts.payer)?;
+    let weight = vote_weight(
+        &ctx.accounts.poll_account,
+        ctx.accounts.weight_token_account.as_ref(),
+        ctx.accounts.payer.key(),
+    )?;
+
+    ctx.accounts.voter_record.bump = ctx.bumps.voter_record;
+    commit_ballot(
+        &mut ctx.accounts.poll_account,
+        &mut ctx.accounts.voter_record,
+        ctx.accounts.payer.key(),
+        choice,
+        vote_encryption_pubkey,
+        vote_nonce,
+        scheme,
+        weight,
+    )?;
+    ctx.accounts.voter_record.committed = true;
+
+    msg!("Vote committed for poll {}", ctx.accounts.poll_account.id);
+
+    Ok(())
+}
+
+/// Counts the payer's committed vote, the second half of a two-phase vote.
+///
+/// Queues the same `vote` computation as `vote`, from the ballot stored by `commit_vote`, and
+/// `vote_callback` counts it. Until it does, the vote stays committed, so this can be sent
+/// again after a failed computation. Votes in receipt-free polls can only be counted in
+/// `vote_many` batches.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn finalize_vote(
+    ctx: Context<FinalizeVote>,
+    computation_offset: u64,
+    poll_id: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.poll_account.voting_closed(&Clock::get()?),
+        ErrorCode::VotingClosed
+    );
+    // Counted alone, the vote's tally change would line up with its voter
+    require!(
+        ctx.accounts.poll_account.poll_type != PollType::ReceiptFree,
+        ErrorCode::WrongPollType
+    );
+    require_computation_funds(&ctx.accounts.payer)?;
+    hold_tally(
+        &mut ctx.accounts.poll_account,
+        ctx.accounts.computation_account.key(),
+    )?;
+
+    let voter_record = &ctx.accounts.voter_record;
+    let computation_args = arg_builder(&vote_arguments(
+        ctx.accounts.poll_account.key(),
+        &ctx.accounts.poll_account,
+        voter_record.choice,
+        voter_record.vote_encryption_pubkey,
+        voter_record.vote_nonce,
+        voter_record.weight,
+    ))
+    .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+    let [event_authority, program] = event_cpi_callback_accounts();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![VoteCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[
+                CallbackAccount {
+                    pubkey: ctx.accounts.poll_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.voter_record.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.config.key(),
+                    is_writable: true,
+                },
+                event_authority,
+                program,
+            ]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Committed vote queued for poll {} (computation offset {})",
+        ctx.accounts.poll_account.id,
+        computation_offset
+    );
+
+    Ok(())
+}
@@ -1,19 +1,38 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
-use arcium_client::idl::arcium::types::CallbackAccount;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use arcium_client::idl::arcium::{client::args::CallbackComputation, types::CallbackAccount};
 
-use crate::election::{CreatePoll, CreatePollCallback, CreatePollCompDef, CreatePollOutput};
+use crate::{
+    client::Argument,
+    constants::{
+        DECRYPTABLE_SCHEMES, MAX_COMPUTATION_PRIORITY, MAX_EMBARGO_SECS, MAX_OPTIONS,
+        MAX_OPTION_URI_LENGTH, MAX_QUESTION_LENGTH, MAX_WEIGHT_SCALE, MIN_COMPUTATION_BALANCE,
+        MIN_OPTIONS, RETRY_AFTER_ROTATION_SLOTS,
+    },
+    error::ErrorCode,
+    election::{CreatePoll, CreatePollCallback, CreatePollCompDef, CreatePollOutput},
+    state::{
+        EncMode, Poll, PollCreatedEvent, PollSettings, PollStatus, PollType, RetryLaterEvent,
+    },
+};
 
 /// One-off job to create computation definition for `create_poll` in encrypted-ixs/src/lib.rs.
 ///
 /// This initializes the onchain computation definition account that registers the encrypted
 /// instruction. Must be called once before using the `create_poll` encrypted instruction.
 pub fn init_create_poll_comp_def(ctx: Context<CreatePollCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
     init_comp_def(ctx.accounts, None, None)?;
     Ok(())
 }
 
-/// Creates a new confidential poll with the given question.
+/// Creates a new confidential poll with the given question and number of options.
 ///
 /// This initializes a poll account and sets up the encrypted vote counters using MPC.
 /// The vote tallies are stored in encrypted form and can only be revealed by the poll authority.
@@ -22,23 +41,44 @@ pub fn init_create_poll_comp_def(ctx: Context<CreatePollCompDef>) -> Result<()>
 /// # Arguments
 /// * `id` - Unique identifier for this poll
 /// * `question` - The poll question voters will respond to
+/// * `num_options` - How many options voters can choose between (2 to `MAX_OPTIONS`)
 /// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+/// * `settings` - Optional poll behaviour (quorum, closing time, who may reveal)
 pub fn create_poll(
     ctx: Context<CreatePoll>,
     computation_offset: u64,
     id: u32,
     question: String,
+    num_options: u8,
     nonce: u128,
+    settings: PollSettings,
 ) -> Result<()> {
-    msg!("Creating a new poll");
+    msg!(
+        "Creating poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
 
-    // Initialize the poll account with the provided parameters
-    ctx.accounts.poll_account.question = question;
-    ctx.accounts.poll_account.bump = ctx.bumps.poll_account;
-    ctx.accounts.poll_account.id = id;
-    ctx.accounts.poll_account.authority = ctx.accounts.payer.key();
-    ctx.accounts.poll_account.nonce = nonce;
-    ctx.accounts.poll_account.vote_counts = [[0; 32]; 3];
+    require_new_poll(&ctx.accounts.poll_account)?;
+    require_computation_funds(&ctx.accounts.payer)?;
+
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    poll.bump = ctx.bumps.poll_account;
+    poll.id = id;
+    poll.authority = ctx.accounts.payer.key();
+    let nonce = open_poll(poll, poll_key, question, num_options, nonce, settings)?;
+    ctx.accounts.config.total_polls += 1;
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        ctx.accounts.payer.key(),
+        ctx.accounts.config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.payer.key(),
+        poll_key,
+    );
 
     let computation_args = ArgBuilder::new()
         .plaintext_u128(nonce)
@@ -46,7 +86,9 @@ pub fn create_poll(
 
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
-    // Initialize encrypted vote counters (3 options) through MPC
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    // Initialize encrypted vote counters (all MAX_OPTIONS of them) and total weight through MPC
     queue_computation(
         ctx.accounts,
         computation_offset,
@@ -60,23 +102,395 @@ pub fn create_poll(
             }]
         )?],
         1,
-        0,
+        cu_price_micro,
     )?;
 
     Ok(())
 }
 
+/// Validates a poll's parameters and initializes everything but its bump, id and authority,
+/// leaving its encrypted counters to be set by the `create_poll` computation.
+///
+/// Shared by `create_poll` and `activate_poll`, so both open polls the same way.
+///
+/// # Returns
+/// The nonce to initialize the encrypted counters with: `nonce`, or a derived one if the
+/// poll derives its nonces
+pub(crate) fn open_poll(
+    poll: &mut Poll,
+    poll_key: Pubkey,
+    question: String,
+    num_options: u8,
+    nonce: u128,
+    settings: PollSettings,
+) -> Result<u128> {
+    // Checked here so an oversized question fails with a clear error rather than
+    // when Anchor serializes the account at the end of the instruction
+    require!(
+        question.len() <= MAX_QUESTION_LENGTH,
+        ErrorCode::QuestionTooLong
+    );
+    require!(
+        num_options as usize >= MIN_OPTIONS,
+        ErrorCode::TooFewOptions
+    );
+    require!(
+        num_options as usize <= MAX_OPTIONS,
+        ErrorCode::TooManyOptions
+    );
+    require!(
+        settings.close_ts == 0 || settings.close_ts > Clock::get()?.unix_timestamp,
+        ErrorCode::CloseTimeInPast
+    );
+    require!(
+        settings.enc_mode == EncMode::Mxe || settings.tally_recipient != [0; 32],
+        ErrorCode::MissingTallyRecipient
+    );
+    require!(
+        settings.computation_priority <= MAX_COMPUTATION_PRIORITY,
+        ErrorCode::InvalidComputationPriority
+    );
+    require!(
+        settings.embargo_secs == 0
+            || ((1..=MAX_EMBARGO_SECS).contains(&settings.embargo_secs) && settings.close_ts != 0),
+        ErrorCode::InvalidEmbargo
+    );
+    require!(
+        settings.quadratic_credits == 0 || settings.weight_mint.is_none(),
+        ErrorCode::InvalidQuadraticPoll
+    );
+    require!(
+        settings.pass_threshold_pct == 0
+            || (settings.pass_threshold_pct <= 100
+                && num_options == 2
+                && settings.quadratic_credits == 0),
+        ErrorCode::InvalidReferendum
+    );
+    // Abstaining leaves the poll needing at least MIN_OPTIONS sides to choose between
+    require!(
+        settings.abstain_option.is_none_or(|abstain| {
+            abstain < num_options && num_options as usize > MIN_OPTIONS
+        }),
+        ErrorCode::InvalidAbstainOption
+    );
+    // Counters hold vote weight, so a quota only counts votes if each vote counts once
+    require!(
+        settings.option_quota == 0
+            || (settings.weight_mint.is_none() && settings.quadratic_credits == 0),
+        ErrorCode::InvalidOptionQuota
+    );
+    require!(
+        settings.conviction_bucket_secs == 0
+            || (settings.conviction_bucket_secs > 0
+                && settings.close_ts != 0
+                && settings.quadratic_credits == 0
+                && settings.option_quota == 0),
+        ErrorCode::InvalidConvictionSettings
+    );
+    require!(
+        (settings.option_uris.is_empty() || settings.option_uris.len() == num_options as usize)
+            && settings.option_uris.iter().all(|uri| uri.len() <= MAX_OPTION_URI_LENGTH),
+        ErrorCode::InvalidOptionUris
+    );
+    require!(
+        if settings.use_slots {
+            settings.close_ts == 0
+                && (settings.end_slot == 0
+                    || (settings.end_slot > settings.start_slot
+                        && settings.end_slot > Clock::get()?.slot))
+        } else {
+            settings.start_slot == 0 && settings.end_slot == 0
+        },
+        ErrorCode::InvalidSlotWindow
+    );
+    require!(
+        settings.supported_schemes != 0 && settings.supported_schemes & !DECRYPTABLE_SCHEMES == 0,
+        ErrorCode::UnsupportedScheme
+    );
+    require!(
+        settings.weight_scale == 0
+            || (settings.weight_mint.is_some() && settings.weight_scale <= MAX_WEIGHT_SCALE),
+        ErrorCode::InvalidWeightScale
+    );
+    // A receipt-free poll is its own poll type, so it can't also be any other
+    require!(
+        !settings.receipt_free
+            || (settings.weight_mint.is_none()
+                && settings.quadratic_credits == 0
+                && settings.pass_threshold_pct == 0),
+        ErrorCode::InvalidReceiptFreePoll
+    );
+
+    // Initialize the poll account with the provided parameters
+    poll.poll_type = PollType::of(&settings);
+    poll.question = question;
+    poll.num_options = num_options;
+    poll.nonce_counter = 0;
+    let nonce = if settings.derive_nonces {
+        poll.derive_nonce(&poll_key)
+    } else {
+        nonce
+    };
+    poll.nonce = nonce;
+    poll.vote_counts = [[0; 32]; MAX_OPTIONS];
+    poll.total_weight = [0; 32];
+    poll.spoiled = [0; 32];
+    poll.total_votes = 0;
+    poll.turnout = 0;
+    poll.voter_root = [0; 32];
+    poll.reveal_pending = false;
+    poll.tally_pending = false;
+    poll.last_reveal_ts = 0;
+    poll.winner = 0;
+    poll.is_tie = false;
+    poll.live_winner = None;
+    poll.aggregated = false;
+    poll.settings = settings;
+    poll.snapshot_slot = Clock::get()?.slot;
+    poll.tally_epoch = 0;
+    // Open once the encrypted counters are stored by `create_poll_callback`
+    poll.transition(PollStatus::Initialized)?;
+
+    Ok(nonce)
+}
+
+/// Checks the payer can afford to queue an MPC computation, so newcomers with an underfunded
+/// wallet get `InsufficientFunds` instead of an opaque error from inside `queue_computation`.
+pub(crate) fn require_computation_funds(payer: &AccountInfo) -> Result<()> {
+    require!(
+        payer.lamports() >= MIN_COMPUTATION_BALANCE,
+        ErrorCode::InsufficientFunds
+    );
+    Ok(())
+}
+
+/// Checks a poll account being created doesn't already hold a poll, so reusing an id fails
+/// with `PollIdInUse` instead of the system program's generic "already in use" error.
+///
+/// Poll creators take their poll account with `init_if_needed` for this. A new account is
+/// zeroed, so a set authority means the id is taken.
+pub(crate) fn require_new_poll(poll: &Poll) -> Result<()> {
+    require!(poll.authority == Pubkey::default(), ErrorCode::PollIdInUse);
+    Ok(())
+}
+
+/// Checks that a callback is being run by the Arcium program, as part of the transaction the
+/// cluster sends with a computation's result, rather than called directly.
+///
+/// `verify_output` already checks the cluster signed the output, but the callback shouldn't
+/// rely on that alone before touching state. Arcium doesn't reach the callback through a CPI:
+/// its `callback_computation` instruction checks the result, and the callback follows it as
+/// the next top-level instruction in the same transaction. The instructions sysvar holds those
+/// instructions, so the one before the callback must be Arcium's `callback_computation`.
+pub(crate) fn require_arcium_caller(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let previous_index = (current_index as usize)
+        .checked_sub(1)
+        .ok_or(ErrorCode::NotArciumCallback)?;
+    let previous_instruction = load_instruction_at_checked(previous_index, instructions_sysvar)?;
+    require_keys_eq!(
+        previous_instruction.program_id,
+        Arcium::id(),
+        ErrorCode::NotArciumCallback
+    );
+    require!(
+        previous_instruction
+            .data
+            .starts_with(CallbackComputation::DISCRIMINATOR),
+        ErrorCode::NotArciumCallback
+    );
+    Ok(())
+}
+
+/// Checks a callback's computation output and decodes it, like `verify_output`, but with each
+/// way it can fail mapped to its own `ErrorCode`, the same in every callback.
+///
+/// Arcium's own errors share their numbers with this program's, so passed through unchanged
+/// an aborted computation would read as `InvalidAuthority`.
+pub(crate) fn verify_computation<O: HasSize + AnchorDeserialize + AnchorSerialize>(
+    output: SignedComputationOutputs<O>,
+    cluster_account: &Cluster,
+    computation_account: &UncheckedAccount,
+) -> std::result::Result<O, ErrorCode> {
+    let aborted = matches!(output, SignedComputationOutputs::Failure);
+    output
+        .verify_output(cluster_account, computation_account)
+        .map_err(|error| computation_failure(aborted, &error))
+}
+
+/// The `ErrorCode` for a computation output `verify_output` rejected with `error`, `aborted`
+/// being whether the output was `SignedComputationOutputs::Failure`.
+///
+/// An aborted computation is reported as aborted even when the cluster's key is also unset,
+/// which `verify_output` checks first.
+pub(crate) fn computation_failure(aborted: bool, error: &Error) -> ErrorCode {
+    let is = |arcium_error: ArciumError| *error == Error::from(arcium_error);
+    if aborted {
+        ErrorCode::AbortedComputation
+    } else if is(ArciumError::BLSSignatureVerificationFailed) {
+        ErrorCode::ComputationSignatureInvalid
+    } else if is(ArciumError::InvalidClusterBLSPublicKey) {
+        ErrorCode::ClusterKeyUnset
+    } else if is(ArciumError::InvalidComputationAccount) {
+        ErrorCode::InvalidComputationAccount
+    } else {
+        // A signed output that doesn't decode, or the IDL-only marker variant
+        ErrorCode::MalformedComputationOutput
+    }
+}
+
+/// Starts building a computation's arguments from their `client` encoding, so the program
+/// queues exactly what off-chain clients compute.
+pub(crate) fn arg_builder(arguments: &[Argument]) -> ArgBuilder {
+    arguments
+        .iter()
+        .fold(ArgBuilder::new(), |builder, argument| match *argument {
+            Argument::X25519Pubkey(pubkey) => builder.x25519_pubkey(pubkey),
+            Argument::PlaintextBool(value) => builder.plaintext_bool(value),
+            Argument::PlaintextU8(value) => builder.plaintext_u8(value),
+            Argument::PlaintextU64(value) => builder.plaintext_u64(value),
+            Argument::PlaintextU128(value) => builder.plaintext_u128(value),
+            Argument::EncryptedU8(ciphertext) => builder.encrypted_u8(ciphertext),
+            Argument::Account {
+                pubkey,
+                offset,
+                length,
+            } => builder.account(pubkey, offset, length),
+        })
+}
+
+/// The accounts `#[event_cpi]` adds to a callback's accounts, so the callback can `emit_cpi!`.
+/// The macro appends them to the struct, so they go after the callback's other accounts.
+pub(crate) fn event_cpi_callback_accounts() -> [CallbackAccount; 2] {
+    let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &crate::ID);
+    [
+        CallbackAccount {
+            pubkey: event_authority,
+            is_writable: false,
+        },
+        CallbackAccount {
+            pubkey: crate::ID,
+            is_writable: false,
+        },
+    ]
+}
+
+/// Handles a callback whose output failed `verify_computation`, deciding whether it's worth
+/// retrying.
+///
+/// A signed output that doesn't verify (`ComputationSignatureInvalid`) was signed with a key
+/// the cluster account no longer holds: the cluster rotated its keys while the computation ran.
+/// That's no fault of the action, so rather than failing it for good, the poll gets a
+/// `retry_after_slot`, when the rotation should be over, and a `RetryLaterEvent` tells clients
+/// to resend it then. Any other failure isn't deferred.
+pub(crate) fn defer_after_key_rotation(poll: &mut Account<Poll>, failure: ErrorCode) -> Result<()> {
+    if !matches!(failure, ErrorCode::ComputationSignatureInvalid) {
+        return Ok(());
+    }
+    let retry_after_slot = Clock::get()?.slot + RETRY_AFTER_ROTATION_SLOTS;
+    poll.retry_after_slot = retry_after_slot;
+    msg!(
+        "Arcium cluster rotated keys during a computation for poll {}; retry after slot {}",
+        poll.id,
+        retry_after_slot
+    );
+    emit!(RetryLaterEvent {
+        poll: poll.key(),
+        retry_after_slot,
+    });
+    Ok(())
+}
+
 pub fn create_poll_callback(
     ctx: Context<CreatePollCallback>,
     output: SignedComputationOutputs<CreatePollOutput>,
 ) -> Result<()> {
-    let CreatePollOutput { field_0: computation_result } = output.verify_output(
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // A reset holds the tally until its counters land. If it was released and another reset
+    // has held the tally since, that one's counters are the ones to store.
+    let poll = &ctx.accounts.poll_account;
+    if poll.tally_pending && !poll.holds_tally_for(ctx.accounts.computation_account.key()) {
+        msg!("Counters for poll {} landed after their reset was released", poll.id);
+        return Ok(());
+    }
+
+    let CreatePollOutput { field_0: computation_result } = verify_computation(
+        output,
         &ctx.accounts.cluster_account,
         &ctx.accounts.computation_account
     )?;
 
-    ctx.accounts.poll_account.vote_counts = computation_result.ciphertexts;
-    ctx.accounts.poll_account.nonce = computation_result.nonce;
+    ctx.accounts
+        .poll_account
+        .set_poll_tally(computation_result.ciphertexts, computation_result.nonce);
+    ctx.accounts.poll_account.tally_pending = false;
+    ctx.accounts.poll_account.transition(PollStatus::Open)?;
+
+    msg!(
+        "Poll {} initialized (computation {})",
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    let poll = &ctx.accounts.poll_account;
+    emit!(PollCreatedEvent {
+        poll: poll.key(),
+        authority: poll.authority,
+        id: poll.id,
+        question: poll.question.clone(),
+        num_options: poll.num_options,
+        option_uris: poll.settings.option_uris.clone(),
+    });
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The name of the `ErrorCode` a callback fails with when `verify_output` returns `error`
+    fn failure(aborted: bool, error: impl Into<Error>) -> String {
+        computation_failure(aborted, &error.into()).name()
+    }
+
+    #[test]
+    fn aborted_computations_fail_as_aborted() {
+        assert_eq!(failure(true, ArciumError::AbortedComputation), "AbortedComputation");
+        // The cluster's key is checked first, but the computation was still aborted
+        assert_eq!(failure(true, ArciumError::InvalidClusterBLSPublicKey), "AbortedComputation");
+    }
+
+    #[test]
+    fn badly_signed_outputs_fail_as_invalid_signatures() {
+        assert_eq!(
+            failure(false, ArciumError::BLSSignatureVerificationFailed),
+            "ComputationSignatureInvalid"
+        );
+    }
+
+    #[test]
+    fn outputs_checked_without_a_cluster_key_fail_as_unset_keys() {
+        assert_eq!(failure(false, ArciumError::InvalidClusterBLSPublicKey), "ClusterKeyUnset");
+    }
+
+    #[test]
+    fn unreadable_computation_accounts_fail_as_invalid_accounts() {
+        assert_eq!(
+            failure(false, ArciumError::InvalidComputationAccount),
+            "InvalidComputationAccount"
+        );
+    }
+
+    #[test]
+    fn undecodable_outputs_fail_as_malformed() {
+        // What `verify_output` returns when the signed bytes don't decode as the output type
+        let truncated = u64::try_from_slice(&[1, 2, 3]).unwrap_err();
+        assert_eq!(failure(false, truncated), "MalformedComputationOutput");
+        assert_eq!(
+            failure(false, ArciumError::MarkerForIdlBuildUsageNotAllowed),
+            "MalformedComputationOutput"
+        );
+    }
+}
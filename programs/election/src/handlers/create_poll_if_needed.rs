@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    election::{CreatePollCallback, CreatePollIfNeeded},
+    error::ErrorCode,
+    handlers::create_poll::open_poll,
+    state::{PollSettings, PollStatus},
+};
+
+/// Creates a poll like `create_poll`, but does nothing if the poll already exists.
+///
+/// Makes poll creation safe to retry: a script resubmitting after a flaky RPC response gets
+/// a no-op rather than an error. The existing poll isn't changed, even if the retry passes
+/// different arguments, and its `create_poll` computation isn't queued again.
+///
+/// # Arguments
+/// * `id` - Unique identifier for this poll
+/// * `question` - The poll question voters will respond to
+/// * `num_options` - How many options voters can choose between (2 to `MAX_OPTIONS`)
+/// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+/// * `settings` - Optional poll behaviour
+pub fn create_poll_if_needed(
+    ctx: Context<CreatePollIfNeeded>,
+    computation_offset: u64,
+    id: u32,
+    question: String,
+    num_options: u8,
+    nonce: u128,
+    settings: PollSettings,
+) -> Result<()> {
+    let poll = &mut ctx.accounts.poll_account;
+
+    // A new account is zeroed, so a set authority means the poll already exists. The seeds
+    // already tie the poll to the payer; checking the stored authority as well means the
+    // account can never be re-initialized on someone else's behalf.
+    if poll.authority != Pubkey::default() {
+        require!(
+            poll.authority == ctx.accounts.payer.key() && poll.id == id,
+            ErrorCode::InvalidAuthority
+        );
+        // Preallocated polls are opened with `activate_poll`
+        require!(poll.status != PollStatus::Created, ErrorCode::PollNotActivated);
+        msg!("Poll {} already exists", id);
+        return Ok(());
+    }
+
+    msg!(
+        "Creating poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    let poll_key = poll.key();
+    poll.bump = ctx.bumps.poll_account;
+    poll.id = id;
+    poll.authority = ctx.accounts.payer.key();
+    let nonce = open_poll(poll, poll_key, question, num_options, nonce, settings)?;
+    ctx.accounts.config.total_polls += 1;
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        ctx.accounts.payer.key(),
+        ctx.accounts.config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.payer.key(),
+        poll_key,
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![CreatePollCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    Ok(())
+}
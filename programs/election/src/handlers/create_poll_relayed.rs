@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+use solana_sdk_ids::ed25519_program;
+
+use crate::{
+    client::relayed_poll_consent,
+    election::{CreatePollCallback, CreatePollRelayed},
+    error::ErrorCode,
+    handlers::create_poll::{open_poll, require_computation_funds, require_new_poll},
+    state::PollSettings,
+};
+
+/// Creates a poll for an `authority` that never sends a transaction itself: a relayer pays for
+/// and signs the transaction, and the authority's consent is an Ed25519 signature.
+///
+/// The instruction right before this one must be an Ed25519 program instruction checking the
+/// authority's signature over `client::relayed_poll_consent`, which covers the poll's id,
+/// question, options and settings, so the relayer can't change any of them. The poll belongs
+/// to the authority exactly as if they'd called `create_poll`, so only they can reveal it.
+///
+/// The consent also covers `relay_nonce`, which must be the authority's next one in their
+/// `AuthorityState` and is then bumped, so a relayer can't replay a consent, e.g. to recreate
+/// the poll after it's closed.
+///
+/// # Arguments
+/// * `id` - Unique identifier for this poll
+/// * `authority` - The poll's authority, who signed the consent
+/// * `question` - The poll question voters will respond to
+/// * `num_options` - How many options voters can choose between (2 to `MAX_OPTIONS`)
+/// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+/// * `settings` - Optional poll behaviour (quorum, closing time, who may reveal)
+/// * `relay_nonce` - The authority's relay nonce the consent was signed for
+#[allow(clippy::too_many_arguments)]
+pub fn create_poll_relayed(
+    ctx: Context<CreatePollRelayed>,
+    computation_offset: u64,
+    id: u32,
+    authority: Pubkey,
+    question: String,
+    num_options: u8,
+    nonce: u128,
+    settings: PollSettings,
+    relay_nonce: u64,
+) -> Result<()> {
+    msg!(
+        "Creating poll {} for {} (computation offset {})",
+        id,
+        authority,
+        computation_offset
+    );
+
+    require_new_poll(&ctx.accounts.poll_account)?;
+    require_computation_funds(&ctx.accounts.payer)?;
+    require!(
+        relay_nonce == ctx.accounts.authority_state.relay_nonce,
+        ErrorCode::StaleRelayNonce
+    );
+    let consent =
+        relayed_poll_consent(&authority, id, relay_nonce, &question, num_options, &settings);
+    require_ed25519_signature(&ctx.accounts.instructions_sysvar, &authority, &consent)?;
+    ctx.accounts.authority_state.relay_nonce += 1;
+
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    poll.bump = ctx.bumps.poll_account;
+    poll.id = id;
+    poll.authority = authority;
+    let nonce = open_poll(poll, poll_key, question, num_options, nonce, settings)?;
+    ctx.accounts.config.total_polls += 1;
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        authority,
+        ctx.accounts.config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        authority,
+        poll_key,
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![CreatePollCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    Ok(())
+}
+
+/// Checks that the instruction before the current one is an Ed25519 program instruction
+/// verifying one signature by `signer` over `message`.
+///
+/// The Ed25519 program fails the whole transaction if a signature it checks is invalid, so
+/// finding the instruction is enough, as long as the key and message it checked are the ones
+/// expected. Its data is a signature count and a padding byte, then seven little-endian u16s
+/// locating the signature, key and message; each of them must be in the instruction's own
+/// data (instruction index `u16::MAX`), not in some other instruction the check can't see.
+fn require_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current_index > 0, ErrorCode::InvalidConsentSignature);
+    let ed25519_instruction = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    require_keys_eq!(
+        ed25519_instruction.program_id,
+        ed25519_program::ID,
+        ErrorCode::InvalidConsentSignature
+    );
+
+    let data = &ed25519_instruction.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidConsentSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let [signature_index, pubkey_offset, pubkey_index, message_offset, message_size, message_index] =
+        [4, 6, 8, 10, 12, 14].map(read_u16);
+    require!(
+        [signature_index, pubkey_index, message_index]
+            .iter()
+            .all(|&index| index == u16::MAX as usize),
+        ErrorCode::InvalidConsentSignature
+    );
+    require!(
+        data.get(pubkey_offset..pubkey_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_size) == Some(message),
+        ErrorCode::InvalidConsentSignature
+    );
+
+    Ok(())
+}
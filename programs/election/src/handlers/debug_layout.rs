@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::DebugLayout,
+    state::{Poll, PollLayout},
+};
+
+/// Reports where a poll's encrypted fields are in its account, for integrators checking the
+/// offsets passed to `ArgBuilder::account` (see `client::POLL_TALLY_OFFSET`). Only built with
+/// the `debug` feature.
+///
+/// The offsets are worked out by serializing the live poll's fields in order, and checked
+/// against the account's bytes, so they follow any change to the layout.
+/// This is a read-only view: call it via simulation and read the `PollLayout` from return data.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn debug_layout(ctx: Context<DebugLayout>, id: u32) -> Result<PollLayout> {
+    let poll = &ctx.accounts.poll_account;
+
+    // The fields up to and including `nonce`, serialized as Anchor stores them
+    let mut prefix = Poll::DISCRIMINATOR.to_vec();
+    poll.bump.serialize(&mut prefix)?;
+    let vote_counts_offset = prefix.len();
+    poll.vote_counts.serialize(&mut prefix)?;
+    let vote_counts_end = prefix.len();
+    poll.total_weight.serialize(&mut prefix)?;
+    poll.spoiled.serialize(&mut prefix)?;
+    let poll_tally_end = prefix.len();
+    poll.id.serialize(&mut prefix)?;
+    poll.authority.serialize(&mut prefix)?;
+    let nonce_offset = prefix.len();
+    poll.nonce.serialize(&mut prefix)?;
+
+    let account_info = poll.to_account_info();
+    let data = account_info.try_borrow_data()?;
+
+    Ok(PollLayout {
+        vote_counts_offset: vote_counts_offset as u32,
+        vote_counts_length: (vote_counts_end - vote_counts_offset) as u32,
+        poll_tally_offset: vote_counts_offset as u32,
+        poll_tally_length: (poll_tally_end - vote_counts_offset) as u32,
+        nonce_offset: nonce_offset as u32,
+        nonce_length: (prefix.len() - nonce_offset) as u32,
+        account_length: data.len() as u32,
+        matches_account: data.starts_with(&prefix),
+    })
+}
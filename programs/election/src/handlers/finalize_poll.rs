@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::FinalizePoll,
+    state::{AuthorityAction, PollFinalizedEvent, PollStatus},
+};
+
+/// Locks a revealed poll's result, so other programs can read the winner straight from the account.
+///
+/// Only the poll authority can finalize a poll, and only once its result has been revealed.
+/// A finalized poll rejects further votes, reveals and edits.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn finalize_poll(ctx: Context<FinalizePoll>, id: u32) -> Result<()> {
+    let poll = &mut ctx.accounts.poll_account;
+    poll.transition(PollStatus::Finalized)?;
+
+    msg!("Poll {} finalized with winner {}", id, poll.winner);
+
+    emit!(PollFinalizedEvent {
+        poll: poll.key(),
+        winner: poll.winner,
+    });
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::Finalize,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::FreezePoll,
+    state::{AuthorityAction, PollFrozenEvent, PollStatus},
+};
+
+/// Stops a poll taking votes, so `reveal_result` can reveal it.
+///
+/// Revealing an open poll would race with votes still being cast: a vote queued just before
+/// the reveal could change the tally the reveal reads. Once frozen, no new vote can be queued.
+/// A vote queued before the freeze still lands when its computation completes, and
+/// `reveal_result` waits for it. Only the poll authority can freeze a poll, and freezing can't be undone.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn freeze_poll(ctx: Context<FreezePoll>, id: u32) -> Result<()> {
+    let poll = &mut ctx.accounts.poll_account;
+    poll.transition(PollStatus::Frozen)?;
+
+    msg!("Poll {} frozen with {} votes", id, poll.total_votes);
+
+    emit!(PollFrozenEvent {
+        poll: poll.key(),
+        total_votes: poll.total_votes,
+    });
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::Freeze,
+    )?;
+
+    Ok(())
+}
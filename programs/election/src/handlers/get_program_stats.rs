@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::GetProgramStats, state::ProgramStats};
+
+/// Returns how many polls have been opened and revealed, and how many votes counted, across
+/// the whole deployment, so a dashboard can check its health with a single call.
+///
+/// This is a read-only view: call it via simulation and read the result from return data,
+/// three little-endian u64s (`total_polls`, `total_revealed`, `total_votes_all_polls`).
+pub fn get_program_stats(ctx: Context<GetProgramStats>) -> Result<ProgramStats> {
+    let config = &ctx.accounts.config;
+
+    Ok(ProgramStats {
+        total_polls: config.total_polls,
+        total_revealed: config.total_revealed,
+        total_votes_all_polls: config.total_votes_all_polls,
+    })
+}
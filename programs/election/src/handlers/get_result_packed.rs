@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::PACKED_RESULT_LENGTH, election::GetResultPacked, error::ErrorCode};
+
+/// Returns a revealed poll's outcome as `PACKED_RESULT_LENGTH` tightly-packed bytes, so another
+/// program can read it from return data after one CPI, without deserializing the whole poll.
+///
+/// The bytes are `Poll::packed_result`: the poll's id (u32), `num_options` (u8), `winner` (u8),
+/// `is_tie` (u8, 0 or 1) and `total_votes` (u64), integers little-endian, at offsets 0, 4, 5, 6
+/// and 7. A fixed-size array is Borsh-encoded as its bytes alone, so the return data is
+/// exactly these 15 bytes.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn get_result_packed(
+    ctx: Context<GetResultPacked>,
+    id: u32,
+) -> Result<[u8; PACKED_RESULT_LENGTH]> {
+    let poll = &ctx.accounts.poll_account;
+    require!(poll.status.is_revealed(), ErrorCode::PollNotRevealed);
+
+    Ok(poll.packed_result())
+}
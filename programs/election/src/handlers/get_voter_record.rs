@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::GetVoterRecord,
+    state::{VoterRecord, VoterRecordView},
+};
+
+/// Returns whether the signer has voted in a poll, when, their encrypted choice and their receipt.
+///
+/// This is a read-only view: call it via simulation and read the result from return data.
+/// The choice stays encrypted, so only the voter (who holds the shared secret) can decrypt it.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn get_voter_record(ctx: Context<GetVoterRecord>, poll_id: u32) -> Result<VoterRecordView> {
+    let voter_record_info = ctx.accounts.voter_record.to_account_info();
+
+    // The voter record only exists once the voter has voted
+    if voter_record_info.data_is_empty() {
+        return Ok(VoterRecordView {
+            has_voted: false,
+            voted_at: 0,
+            choice: [0; 32],
+            vote_encryption_pubkey: [0; 32],
+            vote_nonce: 0,
+            receipt: [0; 32],
+            counted: false,
+        });
+    }
+
+    let voter_record = VoterRecord::try_deserialize(&mut &voter_record_info.data.borrow()[..])?;
+
+    Ok(VoterRecordView {
+        has_voted: true,
+        voted_at: voter_record.voted_at,
+        choice: voter_record.choice,
+        vote_encryption_pubkey: voter_record.vote_encryption_pubkey,
+        vote_nonce: voter_record.vote_nonce,
+        receipt: voter_record.receipt,
+        counted: voter_record.counted,
+    })
+}
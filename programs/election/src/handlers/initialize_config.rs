@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::DEFAULT_REVEAL_COOLDOWN, election::InitializeConfig};
+
+/// Creates the program config, making the payer its admin.
+///
+/// Can only be called once, so it should be run as part of deploying the program. Only the
+/// program's upgrade authority can call it, so nobody watching the deploy can claim the config
+/// first. The admin can hand the role on later with `update_admin`.
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.bump = ctx.bumps.config;
+    config.admin = ctx.accounts.admin.key();
+    config.paused = false;
+    config.reveal_cooldown = DEFAULT_REVEAL_COOLDOWN;
+    config.wormhole_program = Pubkey::default();
+    config.total_polls = 0;
+    config.total_revealed = 0;
+    config.total_votes_all_polls = 0;
+    config.max_active_polls = 0;
+
+    msg!("Program config initialized with admin {}", config.admin);
+
+    Ok(())
+}
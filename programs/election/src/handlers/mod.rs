@@ -6,3 +6,140 @@ pub use vote::*;
 
 pub mod reveal_result;
 pub use reveal_result::*;
+
+pub mod update_question;
+pub use update_question::*;
+pub mod resize_poll;
+pub use resize_poll::*;
+
+pub mod reveal_weight_quorum;
+pub use reveal_weight_quorum::*;
+
+pub mod get_voter_record;
+pub use get_voter_record::*;
+
+pub mod aggregate_polls;
+pub use aggregate_polls::*;
+
+pub mod aggregate_child;
+pub use aggregate_child::*;
+
+pub mod add_voters;
+pub use add_voters::*;
+
+pub mod reveal_decided;
+pub use reveal_decided::*;
+
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod update_admin;
+pub use update_admin::*;
+
+pub mod reveal_top_k;
+pub use reveal_top_k::*;
+
+pub mod snapshot_tally;
+pub use snapshot_tally::*;
+
+pub mod finalize_poll;
+pub use finalize_poll::*;
+
+pub mod reveal_tally_shared;
+pub use reveal_tally_shared::*;
+
+pub mod set_reveal_cooldown;
+pub use set_reveal_cooldown::*;
+
+pub mod preallocate_poll;
+pub use preallocate_poll::*;
+
+pub mod activate_poll;
+pub use activate_poll::*;
+
+pub mod set_wormhole_program;
+pub use set_wormhole_program::*;
+
+pub mod post_result_cross_chain;
+pub use post_result_cross_chain::*;
+
+pub mod clone_poll;
+pub use clone_poll::*;
+
+pub mod freeze_poll;
+pub use freeze_poll::*;
+
+pub mod reveal_referendum;
+pub use reveal_referendum::*;
+
+pub mod reveal_to;
+pub use reveal_to::*;
+
+pub mod create_poll_if_needed;
+pub use create_poll_if_needed::*;
+
+pub mod reveal_spread;
+pub use reveal_spread::*;
+
+pub mod vote_quadratic;
+pub use vote_quadratic::*;
+
+pub mod get_program_stats;
+pub use get_program_stats::*;
+
+pub mod close_poll;
+pub use close_poll::*;
+
+pub mod reset_tally;
+pub use reset_tally::*;
+
+pub mod reveal_with_interval;
+pub use reveal_with_interval::*;
+
+pub mod reveal_bool;
+pub use reveal_bool::*;
+
+pub mod withdraw_fees;
+pub use withdraw_fees::*;
+
+pub mod set_max_active_polls;
+pub use set_max_active_polls::*;
+
+pub mod reveal_full_ranking;
+pub use reveal_full_ranking::*;
+
+pub mod rotate_nonce;
+pub use rotate_nonce::*;
+pub mod commit_vote;
+pub use commit_vote::*;
+pub mod poll_template;
+pub use poll_template::*;
+pub mod batch_check_voted;
+pub use batch_check_voted::*;
+pub mod prove_turnout;
+pub use prove_turnout::*;
+pub mod create_poll_relayed;
+pub use create_poll_relayed::*;
+pub mod get_result_packed;
+pub use get_result_packed::*;
+pub mod reveal_sample;
+pub use reveal_sample::*;
+pub mod vote_many;
+pub use vote_many::*;
+pub mod verify_vote_claim;
+pub use verify_vote_claim::*;
+pub mod reveal_comment;
+pub use reveal_comment::*;
+pub mod reveal_delta;
+pub use reveal_delta::*;
+
+pub mod release_tally;
+pub use release_tally::*;
+
+#[cfg(feature = "debug")]
+pub mod debug_layout;
+#[cfg(feature = "debug")]
+pub use debug_layout::*;
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    constants::{MAX_OPTIONS, MAX_OPTION_LABEL_LENGTH, MIN_OPTIONS},
+    election::{CreatePollCallback, CreatePollFromTemplate, CreatePollTemplate},
+    error::ErrorCode,
+    handlers::create_poll::{open_poll, require_computation_funds, require_new_poll},
+    state::PollSettings,
+};
+
+/// Stores defaults for a series of similar polls, so each can be opened with
+/// `create_poll_from_template` without passing them all again.
+///
+/// The settings are only checked when a poll is opened from the template, since some checks,
+/// like `close_ts` being in the future, depend on when that is.
+///
+/// # Arguments
+/// * `template_id` - Identifies the template among the authority's templates
+/// * `option_labels` - Each option's label (2 to `MAX_OPTIONS` of them)
+/// * `duration_secs` - How long polls from the template stay open. 0 means they never close.
+/// * `settings` - Settings for polls from the template
+pub fn create_poll_template(
+    ctx: Context<CreatePollTemplate>,
+    template_id: u32,
+    option_labels: Vec<String>,
+    duration_secs: i64,
+    settings: PollSettings,
+) -> Result<()> {
+    require!(
+        option_labels.len() >= MIN_OPTIONS,
+        ErrorCode::TooFewOptions
+    );
+    require!(
+        option_labels.len() <= MAX_OPTIONS,
+        ErrorCode::TooManyOptions
+    );
+    require!(
+        option_labels.iter().all(|label| label.len() <= MAX_OPTION_LABEL_LENGTH),
+        ErrorCode::OptionLabelTooLong
+    );
+    require!(duration_secs >= 0, ErrorCode::InvalidTemplateDuration);
+
+    let template = &mut ctx.accounts.template;
+    template.bump = ctx.bumps.template;
+    template.authority = ctx.accounts.authority.key();
+    template.id = template_id;
+    template.option_labels = option_labels;
+    template.duration_secs = duration_secs;
+    template.settings = settings;
+
+    msg!("Created poll template {}", template_id);
+
+    Ok(())
+}
+
+/// Creates a poll like `create_poll`, with its option count, settings and closing time taken
+/// from one of the authority's templates.
+///
+/// # Arguments
+/// * `id` - Unique identifier for this poll
+/// * `template_id` - The template to take the poll's defaults from
+/// * `question` - The poll question voters will respond to
+/// * `nonce` - Cryptographic nonce for initializing encrypted vote counters
+/// * `settings` - Used instead of the template's settings, if given. The template's duration
+///   still sets `close_ts` if these settings leave it 0.
+#[allow(unused_variables)]
+pub fn create_poll_from_template(
+    ctx: Context<CreatePollFromTemplate>,
+    computation_offset: u64,
+    id: u32,
+    template_id: u32,
+    question: String,
+    nonce: u128,
+    settings: Option<PollSettings>,
+) -> Result<()> {
+    msg!(
+        "Creating poll {} from template {} (computation offset {})",
+        id,
+        template_id,
+        computation_offset
+    );
+
+    require_new_poll(&ctx.accounts.poll_account)?;
+    require_computation_funds(&ctx.accounts.payer)?;
+
+    let template = &ctx.accounts.template;
+    let num_options = template.option_labels.len() as u8;
+    let mut settings = settings.unwrap_or_else(|| template.settings.clone());
+    if template.duration_secs > 0 && settings.close_ts == 0 {
+        settings.close_ts = Clock::get()?
+            .unix_timestamp
+            .saturating_add(template.duration_secs);
+    }
+
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    poll.bump = ctx.bumps.poll_account;
+    poll.id = id;
+    poll.authority = ctx.accounts.payer.key();
+    let nonce = open_poll(poll, poll_key, question, num_options, nonce, settings)?;
+    ctx.accounts.config.total_polls += 1;
+    ctx.accounts.authority_state.add_poll(
+        ctx.bumps.authority_state,
+        ctx.accounts.payer.key(),
+        ctx.accounts.config.max_active_polls,
+    )?;
+    ctx.accounts.poll_index.add_poll(
+        ctx.bumps.poll_index,
+        ctx.accounts.payer.key(),
+        poll_key,
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![CreatePollCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    Ok(())
+}
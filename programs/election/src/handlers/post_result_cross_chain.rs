@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_lang::system_program;
+
+use crate::{election::PostResultCrossChain, error::ErrorCode, state::PollStatus};
+
+/// Wormhole core bridge instruction index for `post_message`
+const WORMHOLE_POST_MESSAGE_INSTRUCTION: u8 = 1;
+
+/// Wormhole consistency level meaning guardians wait for the slot to be finalized
+const WORMHOLE_CONSISTENCY_FINALIZED: u8 = 1;
+
+/// Where the message fee sits in the bridge account: after the guardian set index (u32),
+/// last lamports (u64) and guardian set expiration time (u32)
+const WORMHOLE_BRIDGE_FEE_OFFSET: usize = 16;
+
+/// Publishes a finalized poll's winner as a Wormhole message, so it can be used on other chains.
+///
+/// Only polls created with `cross_chain` can be published, and only once finalized, so the
+/// published result can't change. Anyone can publish, paying the Wormhole fee. Each poll is
+/// published at most once, since its message account is a PDA of the poll.
+///
+/// The message payload is 45 bytes, big-endian as Wormhole consumers on EVM chains expect:
+/// * bytes 0-31: the poll authority
+/// * bytes 32-35: the poll ID (u32)
+/// * byte 36: the index of the winning option (u8)
+/// * bytes 37-44: the unix timestamp the result was published at (i64)
+///
+/// The message's emitter is this program's `["emitter"]` PDA.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn post_result_cross_chain(ctx: Context<PostResultCrossChain>, id: u32) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(poll.settings.cross_chain, ErrorCode::NotCrossChain);
+    require!(poll.status == PollStatus::Finalized, ErrorCode::PollNotFinalized);
+    require!(
+        ctx.accounts.config.wormhole_program != Pubkey::default(),
+        ErrorCode::WormholeNotConfigured
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut payload = Vec::with_capacity(45);
+    payload.extend_from_slice(poll.authority.as_ref());
+    payload.extend_from_slice(&id.to_be_bytes());
+    payload.push(poll.winner);
+    payload.extend_from_slice(&now.to_be_bytes());
+
+    // Wormhole takes its fee by checking the fee collector's balance went up
+    let fee = {
+        let bridge_data = ctx.accounts.wormhole_bridge.try_borrow_data()?;
+        let fee_bytes = bridge_data
+            .get(WORMHOLE_BRIDGE_FEE_OFFSET..WORMHOLE_BRIDGE_FEE_OFFSET + 8)
+            .ok_or(ErrorCode::InvalidWormholeBridge)?;
+        u64::from_le_bytes(fee_bytes.try_into().unwrap())
+    };
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    // PostMessage { nonce: u32, payload: Vec<u8>, consistency_level: u8 }, Borsh encoded
+    let mut data = vec![WORMHOLE_POST_MESSAGE_INSTRUCTION];
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    data.push(WORMHOLE_CONSISTENCY_FINALIZED);
+
+    let post_message_instruction = Instruction {
+        program_id: ctx.accounts.wormhole_program.key(),
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.wormhole_bridge.key(), false),
+            AccountMeta::new(ctx.accounts.wormhole_message.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.wormhole_emitter.key(), true),
+            AccountMeta::new(ctx.accounts.wormhole_sequence.key(), false),
+            AccountMeta::new(ctx.accounts.payer.key(), true),
+            AccountMeta::new(ctx.accounts.wormhole_fee_collector.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    let poll_key = poll.key();
+    invoke_signed(
+        &post_message_instruction,
+        &[
+            ctx.accounts.wormhole_bridge.to_account_info(),
+            ctx.accounts.wormhole_message.to_account_info(),
+            ctx.accounts.wormhole_emitter.to_account_info(),
+            ctx.accounts.wormhole_sequence.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.wormhole_fee_collector.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[
+            &[
+                b"wormhole_message",
+                poll_key.as_ref(),
+                &[ctx.bumps.wormhole_message],
+            ],
+            &[b"emitter", &[ctx.bumps.wormhole_emitter]],
+        ],
+    )?;
+
+    msg!(
+        "Poll {} result (winner {}) posted to Wormhole",
+        id,
+        poll.winner
+    );
+
+    Ok(())
+}
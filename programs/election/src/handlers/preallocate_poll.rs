@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::PreallocatePoll, handlers::create_poll::require_new_poll};
+
+/// Creates an empty poll account, paying its rent up front, without queuing any MPC.
+///
+/// Lets operators of scheduled elections provision polls in advance. The poll can't be voted
+/// on until `activate_poll` sets its question, options and encrypted counters.
+///
+/// # Arguments
+/// * `id` - Unique identifier for the poll
+pub fn preallocate_poll(ctx: Context<PreallocatePoll>, id: u32) -> Result<()> {
+    require_new_poll(&ctx.accounts.poll_account)?;
+
+    let poll = &mut ctx.accounts.poll_account;
+    poll.bump = ctx.bumps.poll_account;
+    poll.id = id;
+    poll.authority = ctx.accounts.payer.key();
+    // Anchor zero-initializes every other field, so the poll's status is `Created` until
+    // `activate_poll` opens it
+
+    msg!("Preallocated poll {}", id);
+
+    Ok(())
+}
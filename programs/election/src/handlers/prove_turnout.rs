@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::ProveTurnout, state::TurnoutProof};
+
+/// Returns a poll's turnout and the commitment to the voters behind it, for observers to check
+/// participation without relying on a central tally.
+///
+/// `vote_callback` and `vote_quadratic_callback` add each counted voter to the commitment (see
+/// `Poll::record_turnout`). To verify it, read the poll's `VoterRecord`s that are `counted` in
+/// the returned `epoch`, order them by `turnout_index` and chain their voters from 32 zero
+/// bytes, as `client::turnout_root` does: the result must be `voter_root`, from exactly
+/// `turnout` distinct voters. This is a read-only view: call it via simulation and read the
+/// `TurnoutProof` from return data.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn prove_turnout(ctx: Context<ProveTurnout>, id: u32) -> Result<TurnoutProof> {
+    let poll = &ctx.accounts.poll_account;
+
+    Ok(TurnoutProof {
+        poll: poll.key(),
+        epoch: poll.tally_epoch,
+        turnout: poll.turnout,
+        voter_root: poll.voter_root,
+    })
+}
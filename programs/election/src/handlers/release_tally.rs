@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::ReleaseTally, error::ErrorCode, state::AuthorityAction};
+
+/// Releases a poll's tally held by a computation whose callback never landed, e.g. because its
+/// callback transaction was dropped or the computation expired.
+///
+/// Only the poll authority can call this, and only once the tally has been held for
+/// `TALLY_HOLD_TIMEOUT_SLOTS`: until then the computation may still land. Without it, the poll
+/// would be locked out of votes, `reset_tally` and closing for good. A vote the computation was
+/// counting isn't counted. If its callback lands after all, it's dropped rather than stored,
+/// even if another computation has held the tally by then: callbacks only store the tally for
+/// the computation recorded in `tally_holder`.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn release_tally(ctx: Context<ReleaseTally>, id: u32) -> Result<()> {
+    let poll = &mut ctx.accounts.poll_account;
+    require!(
+        poll.tally_hold_expired(Clock::get()?.slot),
+        ErrorCode::TallyHoldNotExpired
+    );
+    poll.tally_pending = false;
+
+    msg!(
+        "Tally for poll {} released, held since slot {}",
+        id,
+        poll.tally_held_slot
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::ReleaseTally,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    election::{CreatePollCallback, ResetTally},
+    error::ErrorCode,
+    state::{PollStatus, TallyResetEvent},
+};
+
+/// Break-glass recovery for a corrupted tally: discards the poll's tally and queues the
+/// `create_poll` computation to replace it with fresh zeroed counters.
+///
+/// This is destructive: every vote counted so far is lost, and nothing can restore it.
+/// It's only allowed while the program is paused, so the admin has to stop the whole
+/// program first, and each reset is recorded with a `TallyResetEvent` and the poll's
+/// `tally_epoch`. The poll can't be voted on until `create_poll_callback` stores the new
+/// counters. Known-good votes are then replayed by voting again: voters whose `VoterRecord`
+/// predates the reset may vote once more.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `nonce` - Cryptographic nonce for the new encrypted counters. Ignored in polls with
+///   `derive_nonces`.
+pub fn reset_tally(
+    ctx: Context<ResetTally>,
+    computation_offset: u64,
+    id: u32,
+    nonce: u128,
+) -> Result<()> {
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    require!(!poll.reveal_pending, ErrorCode::RevealPending);
+
+    let discarded_votes = poll.total_votes;
+    let nonce = if poll.settings.derive_nonces {
+        poll.derive_nonce(&poll_key)
+    } else {
+        nonce
+    };
+    poll.tally_epoch += 1;
+    poll.total_votes = 0;
+    poll.turnout = 0;
+    poll.voter_root = [0; 32];
+    poll.winner = 0;
+    poll.is_tie = false;
+    poll.live_winner = None;
+    // Open again once `create_poll_callback` stores the new counters
+    poll.transition(PollStatus::Initialized)?;
+    poll.hold_tally_at(Clock::get()?.slot, ctx.accounts.computation_account.key());
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![CreatePollCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Tally of poll {} reset, discarding {} votes (computation offset {})",
+        id,
+        discarded_votes,
+        computation_offset
+    );
+
+    emit!(TallyResetEvent {
+        poll: poll_key,
+        tally_epoch: ctx.accounts.poll_account.tally_epoch,
+        discarded_votes,
+    });
+
+    Ok(())
+}
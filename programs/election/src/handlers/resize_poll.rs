@@ -0,0 +1,51 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{election::ResizePoll, state::Poll};
+
+/// Grows a poll created before the `Poll` layout got larger, such as when
+/// `MAX_QUESTION_LENGTH` was raised, to the current size.
+///
+/// Older polls keep the allocation they were created with, which still holds everything they
+/// store, but not a question longer than the old limit: `update_question` would fail to write
+/// it back. The payer funds the rent for the extra space. Polls already at the current size
+/// are left as they are, so this can be sent for every poll after an upgrade.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn resize_poll(ctx: Context<ResizePoll>, id: u32) -> Result<()> {
+    let poll_info = ctx.accounts.poll_account.to_account_info();
+    let old_size = poll_info.data_len();
+    let new_size = 8 + Poll::INIT_SPACE;
+    if old_size >= new_size {
+        msg!("Poll {} is already {} bytes", ctx.accounts.poll_account.id, old_size);
+        return Ok(());
+    }
+
+    let rent_delta = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(poll_info.lamports());
+    if rent_delta > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: poll_info.clone(),
+                },
+            ),
+            rent_delta,
+        )?;
+    }
+    // The new bytes are zeroed, and the poll is written back over them when the instruction ends
+    poll_info.resize(new_size)?;
+
+    msg!(
+        "Poll {} resized from {} to {} bytes",
+        ctx.accounts.poll_account.id,
+        old_size,
+        new_size
+    );
+
+    Ok(())
+}
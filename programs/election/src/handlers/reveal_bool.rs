@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, BinaryResultEvent, EncMode},
+    election::{
+        InitRevealBoolCompDef, RevealBool, RevealBoolCallback, RevealBoolOutput,
+        RevealBoolOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_bool` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_bool` encrypted instruction.
+pub fn init_reveal_bool_comp_def(ctx: Context<InitRevealBoolCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals a two-option poll's result as a bool: whether option 1 (yes) beat option 0 (no).
+///
+/// Only the poll authority can call this function. Like `reveal_result`, the poll must be
+/// frozen or closed first. Unlike `reveal_referendum`, there's no threshold: yes wins with
+/// more votes than no.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal the result of
+pub fn reveal_bool(ctx: Context<RevealBool>, computation_offset: u64, id: u32) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.num_options == 2, ErrorCode::NotABinaryPoll);
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealBoolCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Binary result reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_bool_callback(
+    ctx: Context<RevealBoolCallback>,
+    output: SignedComputationOutputs<RevealBoolOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealBoolOutput {
+        field_0:
+            RevealBoolOutputStruct0 {
+                field_0: yes_wins,
+                field_1: is_tie,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Binary result reveal completed with yes wins: {} (tie: {}) (computation {})",
+        yes_wins,
+        is_tie,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(BinaryResultEvent { yes_wins, is_tie });
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::VOTE_COMMENT_OFFSET,
+    constants::MAX_COMMENT_LENGTH,
+    error::ErrorCode,
+    state::{AuthorityAction, CommentRevealEvent, EncPubkey},
+    election::{
+        InitRevealCommentCompDef, RevealComment, RevealCommentCallback, RevealCommentOutput,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_comment` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_comment` encrypted instruction.
+pub fn init_reveal_comment_comp_def(ctx: Context<InitRevealCommentCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Hands one voter's comment on a poll to the poll authority, re-encrypted for `recipient`.
+///
+/// Only the poll authority can call this function. The comment is emitted in a
+/// `CommentRevealEvent`, still encrypted, for the holder of `recipient` to decrypt offchain.
+/// Nothing is revealed publicly, and comments never touch the tally or the result.
+///
+/// # Arguments
+/// * `id` - The poll ID the comment was sent in
+/// * `voter` - The voter whose comment to read
+/// * `recipient` - The x25519 key to re-encrypt the comment for
+/// * `recipient_nonce` - A fresh nonce for the recipient's encryption
+#[allow(unused_variables)]
+pub fn reveal_comment(
+    ctx: Context<RevealComment>,
+    computation_offset: u64,
+    id: u32,
+    voter: Pubkey,
+    recipient: EncPubkey,
+    recipient_nonce: u128,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(recipient.is_valid(), ErrorCode::InvalidEncryptionKey);
+
+    let vote_comment = &ctx.accounts.vote_comment;
+    let computation_args = ArgBuilder::new()
+        .x25519_pubkey(vote_comment.encryption_pubkey)
+        .plaintext_u128(vote_comment.nonce)
+        .account(
+            vote_comment.key(),
+            VOTE_COMMENT_OFFSET,
+            (32 * MAX_COMMENT_LENGTH) as u32, // One encrypted byte of the comment per 32 bytes
+        )
+        .x25519_pubkey(recipient.0)
+        .plaintext_u128(recipient_nonce)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealCommentCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: ctx.accounts.vote_comment.key(),
+                is_writable: false,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Comment reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_comment_callback(
+    ctx: Context<RevealCommentCallback>,
+    output: SignedComputationOutputs<RevealCommentOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealCommentOutput { field_0: shared_comment } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Comment reveal completed for voter {} (computation {})",
+        ctx.accounts.vote_comment.voter,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(CommentRevealEvent {
+        poll: ctx.accounts.vote_comment.poll,
+        voter: ctx.accounts.vote_comment.voter,
+        encryption_key: shared_comment.encryption_key,
+        nonce: shared_comment.nonce,
+        ciphertexts: shared_comment.ciphertexts,
+    });
+
+    Ok(())
+}
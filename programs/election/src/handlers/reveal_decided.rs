@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, DecidedEvent, EncMode},
+    election::{
+        InitRevealDecidedCompDef, RevealDecided, RevealDecidedCallback, RevealDecidedOutput,
+        RevealDecidedOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_decided` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_decided` encrypted instruction.
+pub fn init_reveal_decided_comp_def(ctx: Context<InitRevealDecidedCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals whether the poll's outcome can still change, so the election can be called early.
+///
+/// Only the poll authority can call this function, and only for polls created with
+/// `expected_voters` that aren't token-weighted. The votes still to come are the expected voters minus the votes cast
+/// so far. The MPC computation reveals the current leader and whether it's locked in.
+///
+/// # Arguments
+/// * `id` - The poll ID to check
+pub fn reveal_decided(ctx: Context<RevealDecided>, computation_offset: u64, id: u32) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.expected_voters > 0, ErrorCode::ExpectedVotersNotSet);
+    // Each remaining voter could hold any balance, so the weight still to come is unknown
+    require!(poll.settings.weight_mint.is_none(), ErrorCode::TokenWeightedPoll);
+
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    require!(
+        !poll.embargoed(Clock::get()?.unix_timestamp),
+        ErrorCode::ResultEmbargoed
+    );
+    let remaining_votes = poll.settings.expected_voters.saturating_sub(poll.total_votes);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u64(remaining_votes)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealDecidedCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Decided check queued for poll {} with {} votes remaining (computation offset {})",
+        id,
+        remaining_votes,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_decided_callback(
+    ctx: Context<RevealDecidedCallback>,
+    output: SignedComputationOutputs<RevealDecidedOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealDecidedOutput {
+        field_0:
+            RevealDecidedOutputStruct0 {
+                field_0: winner,
+                field_1: decided,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Decided check completed with leader {} (decided: {}) (computation {})",
+        winner,
+        decided,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(DecidedEvent { winner, decided });
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    handlers::{
+        create_poll::{require_arcium_caller, require_computation_funds, verify_computation},
+        reveal_result::start_reveal_cooldown,
+        vote::hold_tally,
+    },
+    state::{AuthorityAction, EncMode, RevealDeltaEvent},
+    election::{InitRevealDeltaCompDef, RevealDelta, RevealDeltaCallback, RevealDeltaOutput},
+};
+
+/// One-off job to create computation definition for `reveal_delta` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_delta` encrypted instruction.
+pub fn init_reveal_delta_comp_def(ctx: Context<InitRevealDeltaCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals which option is in the lead right now, and whether that's changed since the
+/// previous `reveal_delta`, for live leaderboards that should only redraw when the lead flips.
+///
+/// Only the poll authority can call this function. Unlike `reveal_result`, it works while the
+/// poll is still open: each call publishes the current leader, so frequent calls between
+/// few votes can hint at how individual voters voted, so each call counts against the config's
+/// `reveal_cooldown` just as `reveal_result` does. Like a vote, it holds the poll's tally
+/// until `reveal_delta_callback` lands, so votes sent meanwhile fail with `TallyBusy`.
+/// The callback emits a `RevealDeltaEvent` and stores the leader on the poll as its
+/// `live_winner`, for the next call to compare with.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn reveal_delta(ctx: Context<RevealDelta>, computation_offset: u64, id: u32) -> Result<()> {
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    require_computation_funds(&ctx.accounts.payer)?;
+    let now = Clock::get()?.unix_timestamp;
+    start_reveal_cooldown(poll, ctx.accounts.config.reveal_cooldown, now)?;
+    // A vote landing mid-computation would change the nonce the counters are read with
+    hold_tally(poll, ctx.accounts.computation_account.key())?;
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u8(poll.tie_break_offset())
+        .plaintext_u8(poll.abstain_index())
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll_key,
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealDeltaCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Live reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        poll_key,
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_delta_callback(
+    ctx: Context<RevealDeltaCallback>,
+    output: SignedComputationOutputs<RevealDeltaOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // Released with release_tally, and perhaps held by another computation since: the leader
+    // it read may be out of date
+    if !ctx.accounts.poll_account.holds_tally_for(ctx.accounts.computation_account.key()) {
+        msg!(
+            "Live reveal for poll {} landed after its tally was released",
+            ctx.accounts.poll_account.id
+        );
+        return Ok(());
+    }
+
+    let winner = match verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    ) {
+        Ok(RevealDeltaOutput { field_0 }) => field_0,
+        Err(error) => {
+            // Failing would leave the tally held forever. The live winner stays as it was.
+            ctx.accounts.poll_account.tally_pending = false;
+            msg!("Live reveal for poll {} failed: {}", ctx.accounts.poll_account.id, error);
+            return Ok(());
+        }
+    };
+
+    let poll = &mut ctx.accounts.poll_account;
+    poll.tally_pending = false;
+    let previous_winner = poll.live_winner;
+    let changed = previous_winner != Some(winner);
+    poll.live_winner = Some(winner);
+
+    msg!(
+        "Live reveal completed for poll {}: option {} leads, changed: {} (computation {})",
+        poll.id,
+        winner,
+        changed,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(RevealDeltaEvent {
+        poll: poll.key(),
+        previous_winner,
+        winner,
+        changed,
+    });
+
+    Ok(())
+}
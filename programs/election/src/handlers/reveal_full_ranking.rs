@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, FullRankingEvent, RankedOption},
+    election::{
+        InitRevealFullRankingCompDef, RevealFullRanking, RevealFullRankingCallback,
+        RevealFullRankingOutput, RevealFullRankingOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_full_ranking` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_full_ranking` encrypted instruction.
+pub fn init_reveal_full_ranking_comp_def(ctx: Context<InitRevealFullRankingCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals every option ranked by votes, with its count, in one computation.
+///
+/// Only the poll authority can call this function. It reveals more than `reveal_top_k`, every
+/// count, so like `reveal_bool` the poll must be frozen or closed first.
+///
+/// # Arguments
+/// * `id` - The poll ID to rank the options of
+pub fn reveal_full_ranking(
+    ctx: Context<RevealFullRanking>,
+    computation_offset: u64,
+    id: u32,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealFullRankingCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Full ranking reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_full_ranking_callback(
+    ctx: Context<RevealFullRankingCallback>,
+    output: SignedComputationOutputs<RevealFullRankingOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealFullRankingOutput {
+        field_0:
+            RevealFullRankingOutputStruct0 {
+                field_0: options,
+                field_1: counts,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    // Places past the poll's options are padding
+    let ranking: Vec<RankedOption> = options
+        .into_iter()
+        .zip(counts)
+        .take_while(|&(option, _)| option != u8::MAX)
+        .map(|(option, votes)| RankedOption { option, votes })
+        .collect();
+
+    msg!(
+        "Full ranking reveal completed with {} options ranked (computation {})",
+        ranking.len(),
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(FullRankingEvent { ranking });
+
+    Ok(())
+}
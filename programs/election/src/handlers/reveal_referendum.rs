@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, PollType, ReferendumEvent},
+    election::{
+        InitRevealReferendumCompDef, RevealReferendum, RevealReferendumCallback,
+        RevealReferendumOutput, RevealReferendumOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_referendum` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_referendum` encrypted instruction.
+pub fn init_reveal_referendum_comp_def(ctx: Context<InitRevealReferendumCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals whether a yes/no referendum passed, and the share of yes votes.
+///
+/// Only the poll authority can call this function, and only for polls created with a
+/// `pass_threshold_pct`. Like `reveal_result`, the poll must be frozen or closed first.
+/// The MPC computation reveals nothing about the counts beyond the pass/fail result and
+/// the rounded yes percentage.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal the referendum result for
+pub fn reveal_referendum(
+    ctx: Context<RevealReferendum>,
+    computation_offset: u64,
+    id: u32,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.poll_type == PollType::Referendum, ErrorCode::WrongPollType);
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.settings.pass_threshold_pct)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealReferendumCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Referendum reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_referendum_callback(
+    ctx: Context<RevealReferendumCallback>,
+    output: SignedComputationOutputs<RevealReferendumOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealReferendumOutput {
+        field_0:
+            RevealReferendumOutputStruct0 {
+                field_0: passed,
+                field_1: yes_pct,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Referendum reveal completed: passed {} with {}% yes (computation {})",
+        passed,
+        yes_pct,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(ReferendumEvent { passed, yes_pct });
+
+    Ok(())
+}
@@ -1,10 +1,19 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
 
 use crate::{
+    client::reveal_result_arguments,
     error::ErrorCode,
-    state::{Poll, RevealResultEvent},
-    election::{InitRevealResultCompDef, RevealResult, RevealResultCallback, RevealResultOutput},
+    handlers::create_poll::{
+        arg_builder, defer_after_key_rotation, event_cpi_callback_accounts, require_arcium_caller,
+        require_computation_funds, verify_computation,
+    },
+    state::{AuthorityAction, EncMode, Poll, PollStatus, ResultAttestation, RevealResultEvent},
+    election::{
+        InitRevealResultCompDef, RevealResult, RevealResultCallback, RevealResultOutput,
+        RevealResultOutputStruct0,
+    },
 };
 
 /// One-off job to create computation definition for `reveal_result` in encrypted-ixs/src/lib.rs.
@@ -12,38 +21,129 @@ use crate::{
 /// This initializes the onchain computation definition account that registers the encrypted
 /// instruction. Must be called once before using the `reveal_result` encrypted instruction.
 pub fn init_reveal_result_comp_def(ctx: Context<InitRevealResultCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
     init_comp_def(ctx.accounts, None, None)?;
     Ok(())
 }
 
 /// Reveals the final result of the poll.
 ///
-/// Only the poll authority can call this function to decrypt and reveal the vote tallies.
-/// The MPC computation compares all three vote counts and returns the winning option.
+/// Only the poll authority can call this function to decrypt and reveal the vote tallies,
+/// unless the poll opted into `public_reveal_after_close` and voting has closed, in which
+/// case anyone can. The authority can be the payer, or sign as the `authority` account, so
+/// a PDA authority can sign through CPI while another account pays for the computation.
+/// Each poll can only be revealed once per the config's `reveal_cooldown`,
+/// and only once it's been frozen with `freeze_poll` or voting has closed, any embargo
+/// has passed, and enough votes have been cast to meet the poll's `vote_quorum`.
+/// Polls with `single_reveal` can only be revealed once, and polls with `reveal_disabled` never.
+/// The MPC computation compares the vote counts of the poll's options and returns the winning option,
+/// resolving ties using the poll's tie-break seed, along with how many votes were spoiled.
 ///
 /// # Arguments
 /// * `id` - The poll ID to reveal results for
 pub fn reveal_result(ctx: Context<RevealResult>, computation_offset: u64, id: u32) -> Result<()> {
-    // Only the poll authority can reveal the result
+    let clock = Clock::get()?;
+    let poll = &ctx.accounts.poll_account;
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    // The authority account's address is already checked against the poll
+    let is_authority =
+        ctx.accounts.payer.key() == poll.authority || ctx.accounts.authority.is_signer;
+    let is_public_reveal =
+        poll.settings.public_reveal_after_close && poll.voting_closed(&clock);
+    require!(is_authority || is_public_reveal, ErrorCode::InvalidAuthority);
+
+    queue_reveal(ctx, computation_offset, id, &clock)
+}
+
+/// Reveals the result of an `auto_reveal` poll once its `close_ts` has passed.
+///
+/// Permissionless, so an unattended election's result doesn't depend on the authority being
+/// online: anyone (a crank) can call it and pay for the computation. Otherwise it's
+/// `reveal_result`, with the same checks and the same `RevealResultEvent`.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal results for
+pub fn crank_reveal(ctx: Context<RevealResult>, computation_offset: u64, id: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let poll = &ctx.accounts.poll_account;
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(poll.settings.auto_reveal, ErrorCode::AutoRevealDisabled);
+    require!(poll.voting_closed(&clock), ErrorCode::RevealNotDue);
+
+    queue_reveal(ctx, computation_offset, id, &clock)
+}
+
+/// Checks the poll can be revealed now and queues the `reveal_result` computation, once
+/// `reveal_result` or `crank_reveal` has checked who's revealing it.
+fn queue_reveal(
+    ctx: Context<RevealResult>,
+    computation_offset: u64,
+    id: u32,
+    clock: &Clock,
+) -> Result<()> {
+    let now = clock.unix_timestamp;
+    let now_slot = clock.slot;
+    let poll = &mut ctx.accounts.poll_account;
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    // Votes are rejected once the poll is frozen or closed, so the tally can't change under the reveal
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+    // A vote queued before the freeze could still change the tally
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    require!(now_slot >= poll.retry_after_slot, ErrorCode::ClusterRotating);
+    // total_votes is public and counts abstentions, so the quorum is checked here
     require!(
-        ctx.accounts.payer.key() == ctx.accounts.poll_account.authority,
-        ErrorCode::InvalidAuthority
+        poll.total_votes >= poll.settings.vote_quorum,
+        ErrorCode::QuorumNotMet
     );
+    // A reveal that's still queued counts, or a second could be queued behind it
+    require!(
+        !poll.settings.single_reveal || !(poll.status.is_revealed() || poll.reveal_pending),
+        ErrorCode::AlreadyRevealed
+    );
+    require_computation_funds(&ctx.accounts.payer)?;
+
+    start_reveal_cooldown(poll, ctx.accounts.config.reveal_cooldown, now)?;
+    poll.reveal_pending = true;
 
-    msg!("Revealing voting result for poll with id {}", id);
+    let result_attestation = &mut ctx.accounts.result_attestation;
+    result_attestation.bump = ctx.bumps.result_attestation;
+    result_attestation.poll = ctx.accounts.poll_account.key();
+    // Anchor reads an optional account passed as the program id as left out, so the callback
+    // only gets the results account, writable, when the reveal asked for one
+    let results_callback_account =
+        match (ctx.accounts.results_account.as_deref_mut(), ctx.bumps.results_account) {
+            (Some(results_account), Some(bump)) => {
+                results_account.bump = bump;
+                results_account.poll = ctx.accounts.poll_account.key();
+                CallbackAccount {
+                    pubkey: results_account.key(),
+                    is_writable: true,
+                }
+            }
+            _ => CallbackAccount {
+                pubkey: crate::ID,
+                is_writable: false,
+            },
+        };
 
-    let computation_args = ArgBuilder::new()
-        .plaintext_u128(ctx.accounts.poll_account.nonce)
-        .account(
-            ctx.accounts.poll_account.key(),
-            // Offset calculation: discriminator + 1 byte (bump)
-            (Poll::DISCRIMINATOR.len() + 1) as u32,
-            32 * 3, // 3 encrypted vote counters (Neo robot, Humane AI PIN, friend.com), 32 bytes each
-        )
-        .build();
+    let computation_args = arg_builder(&reveal_result_arguments(
+        ctx.accounts.poll_account.key(),
+        &ctx.accounts.poll_account,
+    ))
+    .build();
 
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+    let [event_authority, program] = event_cpi_callback_accounts();
+
     queue_computation(
         ctx.accounts,
         computation_offset,
@@ -51,11 +151,47 @@ pub fn reveal_result(ctx: Context<RevealResult>, computation_offset: u64, id: u3
         vec![RevealResultCallback::callback_ix(
             computation_offset,
             &ctx.accounts.mxe_account,
-            &[]
+            &[
+                CallbackAccount {
+                    pubkey: ctx.accounts.poll_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.config.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.result_attestation.key(),
+                    is_writable: true,
+                },
+                results_callback_account,
+                event_authority,
+                program,
+            ]
         )?],
         1,
-        0,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    // Logs the authority rather than the payer when it signed for the reveal itself
+    let revealer = if ctx.accounts.authority.is_signer {
+        ctx.accounts.authority.key()
+    } else {
+        ctx.accounts.payer.key()
+    };
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        revealer,
+        AuthorityAction::Reveal,
     )?;
+
     Ok(())
 }
 
@@ -63,12 +199,134 @@ pub fn reveal_result_callback(
     ctx: Context<RevealResultCallback>,
     output: SignedComputationOutputs<RevealResultOutput>,
 ) -> Result<()> {
-    let RevealResultOutput { field_0: winner } = output.verify_output(
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealResultOutput {
+        field_0:
+            RevealResultOutputStruct0 {
+                field_0: winner,
+                field_1: is_tie,
+                field_2: spoiled,
+                field_3: winner_count,
+                field_4: full_options,
+            },
+    } = match verify_computation(
+        output,
         &ctx.accounts.cluster_account,
         &ctx.accounts.computation_account
-    )?;
+    ) {
+        Ok(output) => output,
+        Err(error) => return end_failed_reveal(&mut ctx.accounts.poll_account, error),
+    };
+
+    ctx.accounts.poll_account.reveal_pending = false;
+
+    // A reveal queued before the poll was finalized mustn't change the locked result
+    if ctx.accounts.poll_account.status != PollStatus::Finalized {
+        // Polls can be revealed again, but only count towards the stats once
+        if !ctx.accounts.poll_account.status.is_revealed() {
+            ctx.accounts.config.total_revealed += 1;
+        }
+        ctx.accounts.poll_account.transition(PollStatus::Revealed)?;
+        ctx.accounts.poll_account.winner = winner;
+        ctx.accounts.poll_account.is_tie = is_tie;
+
+        let poll = &ctx.accounts.poll_account;
+        let slot = Clock::get()?.slot;
+        let result_attestation = &mut ctx.accounts.result_attestation;
+        result_attestation.id = poll.id;
+        result_attestation.winner = winner;
+        result_attestation.nonce = poll.nonce;
+        result_attestation.slot = slot;
+        result_attestation.digest =
+            ResultAttestation::digest(&poll.key(), poll.id, winner, poll.nonce, slot);
+
+        if let Some(results_account) = ctx.accounts.results_account.as_mut() {
+            results_account.id = poll.id;
+            results_account.winner = winner;
+            results_account.is_tie = is_tie;
+            results_account.spoiled = spoiled;
+            results_account.total_votes = poll.total_votes;
+            results_account.slot = slot;
+        }
+    }
+
+    msg!(
+        "Reveal completed for poll {} with winner {} (tie: {}, spoiled: {}) (computation {})",
+        ctx.accounts.poll_account.id,
+        winner,
+        is_tie,
+        spoiled,
+        ctx.accounts.computation_account.key()
+    );
+
+    let event = RevealResultEvent {
+        output: winner,
+        is_tie,
+        spoiled,
+        winner_count: ctx
+            .accounts
+            .poll_account
+            .settings
+            .reveal_winner_count
+            .then_some(winner_count),
+        full_options,
+    };
+    // As in `vote_callback`, also emitted as a self-CPI for indexers
+    emit!(event.clone());
+    emit_cpi!(event);
+
+    Ok(())
+}
 
-    emit!(RevealResultEvent { output: winner });
+/// Checks the poll's last reveal was at least `reveal_cooldown` seconds before `now`, and
+/// starts a new cooldown from `now`. Checked when the reveal is queued, not when it completes,
+/// so a burst of reveals can't all be queued before the first finishes.
+pub(crate) fn start_reveal_cooldown(poll: &mut Poll, reveal_cooldown: i64, now: i64) -> Result<()> {
+    require!(
+        poll.last_reveal_ts == 0 || now >= poll.last_reveal_ts + reveal_cooldown,
+        ErrorCode::RevealTooSoon
+    );
+    poll.last_reveal_ts = now;
+    Ok(())
+}
 
+/// Ends a reveal whose computation failed, however it failed. Failing the callback instead
+/// would leave `reveal_pending` set, so the reveal could never be sent again. One cut short by
+/// a key rotation is resent after `retry_after_slot`, any other straight away.
+fn end_failed_reveal(poll: &mut Account<Poll>, failure: ErrorCode) -> Result<()> {
+    poll.reveal_pending = false;
+    defer_after_key_rotation(poll, failure)?;
+    msg!("Reveal for poll {} failed: {}", poll.id, failure);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_aborted_reveal_can_be_sent_again() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [Poll::DISCRIMINATOR, &[0; Poll::INIT_SPACE][..]].concat();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let mut poll = Account::<Poll>::try_from(&info).unwrap();
+        poll.reveal_pending = true;
+
+        end_failed_reveal(&mut poll, ErrorCode::AbortedComputation).unwrap();
+
+        assert!(!poll.reveal_pending);
+        // Only a key rotation makes the reveal wait
+        assert_eq!(poll.retry_after_slot, 0);
+    }
+}
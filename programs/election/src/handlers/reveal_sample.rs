@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::{sample_seed, POLL_TALLY_OFFSET},
+    constants::{MAX_OPTIONS, MAX_SAMPLE_SIZE, MIN_SAMPLE_SIZE},
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, SampleRevealEvent},
+    election::{
+        InitRevealSampleCompDef, RevealSample, RevealSampleCallback, RevealSampleOutput,
+        RevealSampleOutputStruct0,
+    },
+    handlers::{
+        create_poll::{require_arcium_caller, verify_computation},
+        reveal_result::start_reveal_cooldown,
+    },
+};
+
+/// One-off job to create computation definition for `reveal_sample` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_sample` encrypted instruction.
+pub fn init_reveal_sample_comp_def(ctx: Context<InitRevealSampleCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals the winner of a pseudo-random sample of the poll's votes, as an estimate of the
+/// real winner.
+///
+/// Only the poll authority can call this function. Like `reveal_result`, the poll must be
+/// frozen or closed first, and reveals of either kind share the config's `reveal_cooldown`.
+/// The sample is drawn with replacement, so with `n` votes drawn it can pick the wrong winner
+/// when the real margin is within about `1/sqrt(n)`.
+///
+/// The draws are seeded on-chain with `client::sample_seed`, from the latest slot hash and the
+/// poll's address, so the authority can't pick a seed that draws the sample they want. With
+/// the cooldown, they can't cheaply redraw until one suits them either.
+///
+/// # Arguments
+/// * `id` - The poll ID to estimate the winner of
+/// * `sample_size` - How many votes to draw, from `MIN_SAMPLE_SIZE` to `MAX_SAMPLE_SIZE`
+pub fn reveal_sample(
+    ctx: Context<RevealSample>,
+    computation_offset: u64,
+    id: u32,
+    sample_size: u8,
+) -> Result<()> {
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    require!(
+        (MIN_SAMPLE_SIZE..=MAX_SAMPLE_SIZE).contains(&(sample_size as usize)),
+        ErrorCode::InvalidSampleSize
+    );
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+    start_reveal_cooldown(poll, ctx.accounts.config.reveal_cooldown, now)?;
+
+    // The first entry of the SlotHashes sysvar, after its u64 length, is the latest slot and
+    // its hash
+    let slot_hashes = ctx.accounts.recent_slothashes.try_borrow_data()?;
+    let latest_slot_hash: [u8; 32] = slot_hashes
+        .get(16..48)
+        .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let seed = sample_seed(&latest_slot_hash, &poll_key);
+    drop(slot_hashes);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u8(poll.tie_break_offset())
+        .plaintext_u8(poll.abstain_index())
+        .plaintext_u8(sample_size)
+        .plaintext_u64(seed)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll_key,
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealSampleCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Sample reveal of {} votes queued for poll {} with seed {} (computation offset {})",
+        sample_size,
+        id,
+        seed,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_sample_callback(
+    ctx: Context<RevealSampleCallback>,
+    output: SignedComputationOutputs<RevealSampleOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealSampleOutput {
+        field_0:
+            RevealSampleOutputStruct0 {
+                field_0: winner,
+                field_1: sample_size,
+                field_2: seed,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Sample reveal completed with winner {} from {} votes (computation {})",
+        winner,
+        sample_size,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(SampleRevealEvent {
+        winner,
+        sample_size,
+        seed,
+    });
+
+    Ok(())
+}
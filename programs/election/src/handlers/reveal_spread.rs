@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, SpreadEvent},
+    election::{
+        InitRevealSpreadCompDef, RevealSpread, RevealSpreadCallback, RevealSpreadOutput,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_spread` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_spread` encrypted instruction.
+pub fn init_reveal_spread_comp_def(ctx: Context<InitRevealSpreadCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals how polarized a poll's vote was, as the gap between its most and least voted options.
+///
+/// Only the poll authority can call this function. Like `reveal_result`, the poll must be
+/// frozen or closed first, so the spread describes the final tally. Weighted polls' spread is
+/// in vote weight rather than votes.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal the spread of
+pub fn reveal_spread(
+    ctx: Context<RevealSpread>,
+    computation_offset: u64,
+    id: u32,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealSpreadCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Spread reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_spread_callback(
+    ctx: Context<RevealSpreadCallback>,
+    output: SignedComputationOutputs<RevealSpreadOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealSpreadOutput { field_0: spread } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Spread reveal completed with spread {} (computation {})",
+        spread,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(SpreadEvent { spread });
+
+    Ok(())
+}
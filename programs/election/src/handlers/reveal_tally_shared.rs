@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::{POLL_TALLY_LENGTH, POLL_TALLY_OFFSET},
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, SharedTallyEvent},
+    election::{
+        InitRevealTallySharedCompDef, RevealTallyShared, RevealTallySharedCallback,
+        RevealTallySharedOutput,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_tally_shared` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_tally_shared` encrypted instruction.
+pub fn init_reveal_tally_shared_comp_def(
+    ctx: Context<InitRevealTallySharedCompDef>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Re-encrypts an `EncMode::Shared` poll's whole tally for its `tally_recipient`.
+///
+/// Only the poll authority can call this function. The tally is emitted in a `SharedTallyEvent`,
+/// still encrypted, for the recipient to decrypt offchain.
+///
+/// # Arguments
+/// * `id` - The poll ID to hand the tally of
+/// * `recipient_nonce` - A fresh nonce for the recipient's encryption
+pub fn reveal_tally_shared(
+    ctx: Context<RevealTallyShared>,
+    computation_offset: u64,
+    id: u32,
+    recipient_nonce: u128,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Shared, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+
+    let computation_args = ArgBuilder::new()
+        .x25519_pubkey(poll.settings.tally_recipient)
+        .plaintext_u128(recipient_nonce)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            // The whole PollTally, each field stored as a 32-byte ciphertext
+            POLL_TALLY_LENGTH,
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealTallySharedCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll.key(),
+                is_writable: false,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Shared tally reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_tally_shared_callback(
+    ctx: Context<RevealTallySharedCallback>,
+    output: SignedComputationOutputs<RevealTallySharedOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealTallySharedOutput { field_0: shared_tally } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Shared tally reveal completed for poll {} (computation {})",
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(SharedTallyEvent {
+        poll: ctx.accounts.poll_account.key(),
+        encryption_key: shared_tally.encryption_key,
+        nonce: shared_tally.nonce,
+        ciphertexts: shared_tally.ciphertexts,
+    });
+
+    Ok(())
+}
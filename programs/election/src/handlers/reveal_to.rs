@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, EncPubkey},
+    election::{InitRevealToCompDef, RevealTo, RevealToCallback, RevealToOutput},
+    handlers::{
+        create_poll::{require_arcium_caller, verify_computation},
+        reveal_result::start_reveal_cooldown,
+    },
+};
+
+/// One-off job to create computation definition for `reveal_to` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_to` encrypted instruction.
+pub fn init_reveal_to_comp_def(ctx: Context<InitRevealToCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Encrypts the poll's winner for a recipient of the authority's choosing, e.g. an auditor.
+///
+/// Only the poll authority can call this function, and like `reveal_result` only once the poll
+/// is frozen or voting has closed, no vote is still being counted, and the config's
+/// `reveal_cooldown` has passed since the poll's last reveal. The authority can be the payer,
+/// or sign as the `authority` account. Nothing is revealed publicly: the winner is stored in the
+/// recipient's `SealedResult` account, which only the recipient can decrypt. Running it again
+/// for the same recipient replaces their sealed result.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal the winner of
+/// * `recipient` - The recipient's x25519 public key
+/// * `recipient_nonce` - A fresh nonce for the recipient's encryption
+pub fn reveal_to(
+    ctx: Context<RevealTo>,
+    computation_offset: u64,
+    id: u32,
+    recipient: EncPubkey,
+    recipient_nonce: u128,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let poll_key = ctx.accounts.poll_account.key();
+    // The authority account's address is already checked against the poll
+    require!(
+        ctx.accounts.payer.key() == ctx.accounts.authority.key()
+            || ctx.accounts.authority.is_signer,
+        ErrorCode::InvalidAuthority
+    );
+    let poll = &mut ctx.accounts.poll_account;
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    // A vote queued before the freeze could still change the tally
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    start_reveal_cooldown(poll, ctx.accounts.config.reveal_cooldown, clock.unix_timestamp)?;
+
+    let computation_args = ArgBuilder::new()
+        .x25519_pubkey(recipient.0)
+        .plaintext_u128(recipient_nonce)
+        .plaintext_u8(poll.num_options)
+        .plaintext_u8(poll.tie_break_offset())
+        .plaintext_u8(poll.abstain_index())
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll_key,
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    let sealed_result = &mut ctx.accounts.sealed_result;
+    sealed_result.bump = ctx.bumps.sealed_result;
+    sealed_result.poll = ctx.accounts.poll_account.key();
+    sealed_result.recipient = recipient.0;
+    // Until the computation completes, the stored winner (if any) is from an earlier run
+    sealed_result.sealed = false;
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealToCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: ctx.accounts.sealed_result.key(),
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Reveal to recipient queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    // As in `reveal_result`, logs the authority rather than the payer when it signed
+    let revealer = if ctx.accounts.authority.is_signer {
+        ctx.accounts.authority.key()
+    } else {
+        ctx.accounts.payer.key()
+    };
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        revealer,
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_to_callback(
+    ctx: Context<RevealToCallback>,
+    output: SignedComputationOutputs<RevealToOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealToOutput { field_0: sealed_winner } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    let sealed_result = &mut ctx.accounts.sealed_result;
+    sealed_result.nonce = sealed_winner.nonce;
+    sealed_result.winner = sealed_winner.ciphertexts[0];
+    sealed_result.sealed = true;
+
+    msg!(
+        "Reveal to recipient completed for poll {} (computation {})",
+        sealed_result.poll,
+        ctx.accounts.computation_account.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::{MAX_OPTIONS, MAX_TOP_K},
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, TopKEvent},
+    election::{InitRevealTopKCompDef, RevealTopK, RevealTopKCallback, RevealTopKOutput},
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_top_k` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_top_k` encrypted instruction.
+pub fn init_reveal_top_k_comp_def(ctx: Context<InitRevealTopKCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals the `k` options with the most votes, in order, for multi-winner elections.
+///
+/// Only the poll authority can call this function. The MPC computation ranks the encrypted
+/// vote counts and reveals only the ranking.
+///
+/// # Arguments
+/// * `id` - The poll ID to rank the options of
+/// * `k` - How many options to rank, from 1 to the smaller of `MAX_TOP_K` and the poll's option count
+pub fn reveal_top_k(ctx: Context<RevealTopK>, computation_offset: u64, id: u32, k: u8) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(
+        k >= 1 && k as usize <= MAX_TOP_K && k <= poll.num_options,
+        ErrorCode::InvalidTopK
+    );
+
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    require!(
+        !poll.embargoed(Clock::get()?.unix_timestamp),
+        ErrorCode::ResultEmbargoed
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u8(k)
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealTopKCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Top {} reveal queued for poll {} (computation offset {})",
+        k,
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_top_k_callback(
+    ctx: Context<RevealTopKCallback>,
+    output: SignedComputationOutputs<RevealTopKOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealTopKOutput { field_0: ranking } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Top k reveal completed with ranking {:?} (computation {})",
+        ranking,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(TopKEvent { ranking });
+
+    Ok(())
+}
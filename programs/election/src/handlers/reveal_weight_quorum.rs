@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::{POLL_TALLY_LENGTH, POLL_TALLY_OFFSET},
+    error::ErrorCode,
+    state::{AuthorityAction, WeightQuorumEvent},
+    election::{
+        InitRevealWeightQuorumCompDef, RevealWeightQuorum, RevealWeightQuorumCallback,
+        RevealWeightQuorumOutput,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_weight_quorum` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_weight_quorum` encrypted instruction.
+pub fn init_reveal_weight_quorum_comp_def(ctx: Context<InitRevealWeightQuorumCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals whether the poll's total vote weight reached its quorum.
+///
+/// Only the poll authority can call this function. The MPC computation compares the encrypted
+/// total weight against the poll's `weight_quorum` and reveals only whether it was met.
+///
+/// # Arguments
+/// * `id` - The poll ID to check the quorum for
+pub fn reveal_weight_quorum(
+    ctx: Context<RevealWeightQuorum>,
+    computation_offset: u64,
+    id: u32,
+) -> Result<()> {
+    // Only the poll authority can reveal whether the quorum was met
+    require!(
+        ctx.accounts.payer.key() == ctx.accounts.poll_account.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(
+        !ctx.accounts.poll_account.settings.reveal_disabled,
+        ErrorCode::RevealDisabled
+    );
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u64(ctx.accounts.poll_account.settings.weight_quorum)
+        .plaintext_u128(ctx.accounts.poll_account.nonce)
+        .account(
+            ctx.accounts.poll_account.key(),
+            POLL_TALLY_OFFSET,
+            // The whole PollTally, since the total weight can't be decrypted without the counters before it
+            POLL_TALLY_LENGTH,
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealWeightQuorumCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Weight quorum reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_weight_quorum_callback(
+    ctx: Context<RevealWeightQuorumCallback>,
+    output: SignedComputationOutputs<RevealWeightQuorumOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealWeightQuorumOutput { field_0: quorum_met } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Weight quorum reveal completed with quorum met {} (computation {})",
+        quorum_met,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(WeightQuorumEvent { quorum_met });
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::{
+    client::POLL_TALLY_OFFSET,
+    constants::MAX_OPTIONS,
+    error::ErrorCode,
+    state::{AuthorityAction, EncMode, SampledResultEvent},
+    election::{
+        InitRevealWithIntervalCompDef, RevealWithInterval, RevealWithIntervalCallback,
+        RevealWithIntervalOutput, RevealWithIntervalOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+};
+
+/// One-off job to create computation definition for `reveal_with_interval` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `reveal_with_interval` encrypted instruction.
+pub fn init_reveal_with_interval_comp_def(ctx: Context<InitRevealWithIntervalCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Reveals a sampled poll's winner with a margin of error that shrinks as the sample grows.
+///
+/// Only the poll authority can call this function. Like `reveal_result`, the poll must be
+/// frozen or closed first. The margin is worked out from the votes counted in the tally, or
+/// from vote weight in weighted polls, which the estimate isn't meant for.
+///
+/// # Arguments
+/// * `id` - The poll ID to reveal the winner of
+pub fn reveal_with_interval(
+    ctx: Context<RevealWithInterval>,
+    computation_offset: u64,
+    id: u32,
+) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.settings.reveal_disabled, ErrorCode::RevealDisabled);
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    require!(
+        poll.status.is_frozen() || poll.voting_closed(&clock),
+        ErrorCode::PollNotFrozen
+    );
+    require!(!poll.embargoed(now), ErrorCode::ResultEmbargoed);
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u8(poll.num_options)
+        .plaintext_u8(poll.tie_break_offset())
+        .plaintext_u8(poll.abstain_index())
+        .plaintext_u128(poll.nonce)
+        .account(
+            poll.key(),
+            POLL_TALLY_OFFSET,
+            (32 * MAX_OPTIONS) as u32, // MAX_OPTIONS encrypted vote counters, 32 bytes each
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RevealWithIntervalCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Sampled result reveal queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.payer.key(),
+        AuthorityAction::Reveal,
+    )?;
+
+    Ok(())
+}
+
+pub fn reveal_with_interval_callback(
+    ctx: Context<RevealWithIntervalCallback>,
+    output: SignedComputationOutputs<RevealWithIntervalOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let RevealWithIntervalOutput {
+        field_0:
+            RevealWithIntervalOutputStruct0 {
+                field_0: winner,
+                field_1: moe_pct,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Sampled result reveal completed with winner {} +/- {}% (computation {})",
+        winner,
+        moe_pct,
+        ctx.accounts.computation_account.key()
+    );
+
+    emit!(SampledResultEvent { winner, moe_pct });
+
+    Ok(())
+}
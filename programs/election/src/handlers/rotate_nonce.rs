@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::{POLL_TALLY_LENGTH, POLL_TALLY_OFFSET},
+    error::ErrorCode,
+    handlers::create_poll::{require_arcium_caller, verify_computation},
+    state::{AuthorityAction, EncMode},
+    election::{InitRotateNonceCompDef, RotateNonce, RotateNonceCallback, RotateNonceOutput},
+};
+
+/// One-off job to create computation definition for `rotate_nonce` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `rotate_nonce` encrypted instruction.
+pub fn init_rotate_nonce_comp_def(ctx: Context<InitRotateNonceCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Re-encrypts the poll's tally under a fresh nonce, without changing any count.
+///
+/// Only the poll authority can call this function, e.g. before a sensitive reveal or after
+/// the poll's nonce may have leaked. Like a vote, it holds the poll's tally until
+/// `rotate_nonce_callback` stores the re-encrypted one, so votes sent meanwhile fail with
+/// `TallyBusy`.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `nonce` - The new nonce for the tally. Ignored in polls with `derive_nonces`, which use
+///   the poll's next derived nonce.
+pub fn rotate_nonce(
+    ctx: Context<RotateNonce>,
+    computation_offset: u64,
+    id: u32,
+    nonce: u128,
+) -> Result<()> {
+    let poll_key = ctx.accounts.poll_account.key();
+    let poll = &mut ctx.accounts.poll_account;
+    require!(
+        ctx.accounts.payer.key() == poll.authority,
+        ErrorCode::InvalidAuthority
+    );
+    require!(poll.settings.enc_mode == EncMode::Mxe, ErrorCode::WrongEncMode);
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    // A queued reveal reads the tally when it runs, so it mustn't find it half rotated
+    require!(!poll.reveal_pending, ErrorCode::RevealPending);
+
+    let nonce = if poll.settings.derive_nonces {
+        poll.derive_nonce(&poll_key)
+    } else {
+        nonce
+    };
+    poll.hold_tally_at(Clock::get()?.slot, ctx.accounts.computation_account.key());
+
+    let computation_args = ArgBuilder::new()
+        .plaintext_u128(nonce)
+        .plaintext_u128(poll.nonce)
+        .account(poll_key, POLL_TALLY_OFFSET, POLL_TALLY_LENGTH)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![RotateNonceCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: poll_key,
+                is_writable: true,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Nonce rotation queued for poll {} (computation offset {})",
+        id,
+        computation_offset
+    );
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        poll_key,
+        ctx.accounts.payer.key(),
+        AuthorityAction::RotateNonce,
+    )?;
+
+    Ok(())
+}
+
+pub fn rotate_nonce_callback(
+    ctx: Context<RotateNonceCallback>,
+    output: SignedComputationOutputs<RotateNonceOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // As in `vote_callback`, a rotation landing after release_tally could undo later votes
+    if !ctx.accounts.poll_account.holds_tally_for(ctx.accounts.computation_account.key()) {
+        msg!(
+            "Nonce rotation for poll {} landed after its tally was released",
+            ctx.accounts.poll_account.id
+        );
+        return Ok(());
+    }
+
+    let rotated_tally = match verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    ) {
+        Ok(RotateNonceOutput { field_0 }) => field_0,
+        Err(error) => {
+            // Failing would leave the tally held forever. The old nonce stays in use.
+            ctx.accounts.poll_account.tally_pending = false;
+            msg!("Nonce rotation for poll {} failed: {}", ctx.accounts.poll_account.id, error);
+            return Ok(());
+        }
+    };
+
+    ctx.accounts
+        .poll_account
+        .set_poll_tally(rotated_tally.ciphertexts, rotated_tally.nonce);
+    ctx.accounts.poll_account.tally_pending = false;
+
+    msg!(
+        "Nonce rotated for poll {} (computation {})",
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::election::SetMaxActivePolls;
+
+/// Sets how many polls one authority can have open at once. Only the config admin can call this.
+///
+/// Authorities already over a lowered limit keep their polls, but can't open more until enough
+/// are closed with `close_poll`.
+///
+/// # Arguments
+/// * `max_active_polls` - Most open polls per authority. 0 removes the limit.
+pub fn set_max_active_polls(ctx: Context<SetMaxActivePolls>, max_active_polls: u32) -> Result<()> {
+    ctx.accounts.config.max_active_polls = max_active_polls;
+
+    msg!("Max active polls per authority set to {}", max_active_polls);
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::election::SetPaused;
+
+/// Pauses or unpauses the program. Only the config admin can call this.
+///
+/// While paused, `create_poll`, `preallocate_poll`, `activate_poll`, `vote` and `reveal_result`
+/// are rejected. Read-only instructions keep working.
+///
+/// # Arguments
+/// * `paused` - Whether the program should be paused
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+
+    msg!("Program paused: {}", paused);
+
+    Ok(())
+}
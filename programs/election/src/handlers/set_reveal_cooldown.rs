@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::SetRevealCooldown, error::ErrorCode};
+
+/// Sets how long a poll must wait between reveals. Only the config admin can call this.
+///
+/// # Arguments
+/// * `reveal_cooldown` - Seconds between `reveal_result` calls on the same poll. 0 disables the cooldown.
+pub fn set_reveal_cooldown(ctx: Context<SetRevealCooldown>, reveal_cooldown: i64) -> Result<()> {
+    require!(reveal_cooldown >= 0, ErrorCode::InvalidRevealCooldown);
+
+    ctx.accounts.config.reveal_cooldown = reveal_cooldown;
+
+    msg!("Reveal cooldown set to {} seconds", reveal_cooldown);
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::election::SetWormholeProgram;
+
+/// Sets the Wormhole core bridge program that cross-chain results are posted to.
+/// Only the config admin can call this.
+///
+/// # Arguments
+/// * `wormhole_program` - The Wormhole core bridge program ID for this cluster
+pub fn set_wormhole_program(
+    ctx: Context<SetWormholeProgram>,
+    wormhole_program: Pubkey,
+) -> Result<()> {
+    ctx.accounts.config.wormhole_program = wormhole_program;
+
+    msg!("Wormhole program set to {}", wormhole_program);
+
+    Ok(())
+}
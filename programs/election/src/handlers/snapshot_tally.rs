@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::SnapshotTally,
+    state::TallySnapshot,
+};
+
+/// Copies the poll's current encrypted vote counters into its tally history.
+///
+/// Only the poll authority can take snapshots. The history is created on the first snapshot.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn snapshot_tally(ctx: Context<SnapshotTally>, id: u32) -> Result<()> {
+    let poll = &ctx.accounts.poll_account;
+    let tally_history = &mut ctx.accounts.tally_history;
+    tally_history.bump = ctx.bumps.tally_history;
+    tally_history.poll = poll.key();
+
+    let slot = Clock::get()?.slot;
+    tally_history.push(TallySnapshot {
+        slot,
+        nonce: poll.nonce,
+        vote_counts: poll.vote_counts,
+    });
+
+    msg!(
+        "Snapshot {} of poll {} taken at slot {}",
+        tally_history.total_snapshots,
+        id,
+        slot
+    );
+
+    Ok(())
+}
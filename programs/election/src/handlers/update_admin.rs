@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{election::UpdateAdmin, error::ErrorCode};
+
+/// Hands the program config's admin role to `new_admin`. Only the current admin can call this.
+///
+/// The new admin takes over pausing, the reveal cooldown, the active poll limit and the
+/// Wormhole program at once, and the old admin loses them, so double-check the key.
+///
+/// # Arguments
+/// * `new_admin` - Who administers the config from now on
+pub fn update_admin(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
+    // Nobody holds the default key's secret, so the config could never be changed again
+    require_keys_neq!(new_admin, Pubkey::default(), ErrorCode::InvalidAuthority);
+
+    ctx.accounts.config.admin = new_admin;
+
+    msg!("Program config admin changed to {}", new_admin);
+
+    Ok(())
+}
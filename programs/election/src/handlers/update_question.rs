@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_QUESTION_LENGTH,
+    election::UpdateQuestion,
+    error::ErrorCode,
+    state::AuthorityAction,
+};
+
+/// Replaces the question of a poll that nobody has voted on yet.
+///
+/// Lets the poll authority fix typos right after creating a poll. Once the first vote
+/// has been sent the question is locked, since voters chose based on its wording.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `question` - The new poll question
+pub fn update_question(ctx: Context<UpdateQuestion>, id: u32, question: String) -> Result<()> {
+    require!(
+        question.len() <= MAX_QUESTION_LENGTH,
+        ErrorCode::QuestionTooLong
+    );
+    // total_votes only goes up in the callback, so a queued vote holds the tally instead
+    let poll = &ctx.accounts.poll_account;
+    require!(
+        poll.total_votes == 0 && !poll.tally_pending,
+        ErrorCode::VotingAlreadyStarted
+    );
+
+    ctx.accounts.poll_account.question = question;
+
+    msg!("Question updated for poll {}", id);
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::UpdateQuestion,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    error::ErrorCode,
+    state::{PollType, VoteClaimEvent},
+    election::{
+        InitVerifyVoteClaimCompDef, VerifyVoteClaim, VerifyVoteClaimCallback,
+        VerifyVoteClaimOutput, VerifyVoteClaimOutputStruct0,
+    },
+    handlers::create_poll::{require_arcium_caller, require_computation_funds, verify_computation},
+};
+
+/// One-off job to create computation definition for `verify_vote_claim` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `verify_vote_claim` encrypted instruction.
+pub fn init_verify_vote_claim_comp_def(ctx: Context<InitVerifyVoteClaimCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Checks the payer's claim about how they voted against the encrypted choice in their
+/// `VoterRecord`, the one their receipt commits to, and emits whether it matches.
+///
+/// Only the voter can make a claim about their own vote, so proving how they voted stays their
+/// choice. Nothing but the result is revealed: a wrong claim doesn't give the real choice away.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `claimed_choice` - The option the voter claims to have chosen
+#[allow(unused_variables)]
+pub fn verify_vote_claim(
+    ctx: Context<VerifyVoteClaim>,
+    computation_offset: u64,
+    poll_id: u32,
+    claimed_choice: u8,
+) -> Result<()> {
+    // Proving how they voted is exactly what a receipt-free poll stops voters doing
+    require!(
+        ctx.accounts.poll_account.poll_type != PollType::ReceiptFree,
+        ErrorCode::WrongPollType
+    );
+    let voter_record = &ctx.accounts.voter_record;
+    // A vote given back to the voter after a failed computation has no poll
+    require_keys_eq!(
+        voter_record.poll,
+        ctx.accounts.poll_account.key(),
+        ErrorCode::NoVoteToClaim
+    );
+    require_computation_funds(&ctx.accounts.payer)?;
+
+    let computation_args = ArgBuilder::new()
+        .x25519_pubkey(voter_record.vote_encryption_pubkey)
+        .plaintext_u128(voter_record.vote_nonce)
+        .encrypted_u8(voter_record.choice)
+        .plaintext_u8(claimed_choice)
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![VerifyVoteClaimCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[CallbackAccount {
+                pubkey: ctx.accounts.voter_record.key(),
+                is_writable: false,
+            }]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Vote claim queued for poll {} (computation offset {})",
+        ctx.accounts.poll_account.id,
+        computation_offset
+    );
+
+    Ok(())
+}
+
+pub fn verify_vote_claim_callback(
+    ctx: Context<VerifyVoteClaimCallback>,
+    output: SignedComputationOutputs<VerifyVoteClaimOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let VerifyVoteClaimOutput {
+        field_0:
+            VerifyVoteClaimOutputStruct0 {
+                field_0: matches,
+                field_1: claimed_choice,
+            },
+    } = verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    )?;
+
+    msg!(
+        "Vote claim of option {} checked: {} (computation {})",
+        claimed_choice,
+        if matches { "matches" } else { "doesn't match" },
+        ctx.accounts.computation_account.key()
+    );
+
+    let voter_record = &ctx.accounts.voter_record;
+    emit!(VoteClaimEvent {
+        poll: voter_record.poll,
+        voter: voter_record.voter,
+        receipt: voter_record.receipt,
+        claimed_choice,
+        matches,
+    });
+
+    Ok(())
+}
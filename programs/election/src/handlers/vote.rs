@@ -1,10 +1,22 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::TokenAccount;
 use arcium_anchor::prelude::*;
 use arcium_client::idl::arcium::types::CallbackAccount;
 
 use crate::{
-    state::{Poll, VoteEvent},
-    election::{InitVoteCompDef, Vote, VoteCallback, VoteOutput},
+    client::vote_arguments,
+    constants::VOTE_WEIGHT,
+    error::ErrorCode,
+    state::{
+        EncChoice, EncComment, EncPubkey, EncScheme, FeeVault, Poll, PollType, ProgramConfig,
+        VoteAbortedEvent, VoteComment, VoteEvent, VoterList, VoterRecord,
+    },
+    election::{InitVoteCompDef, Vote, VoteCallback, VoteOutput, VoteOutputStruct0},
+    handlers::create_poll::{
+        arg_builder, defer_after_key_rotation, event_cpi_callback_accounts, require_arcium_caller,
+        require_computation_funds, verify_computation,
+    },
 };
 
 /// One-off job to create computation definition for `vote` in encrypted-ixs/src/lib.rs.
@@ -12,50 +24,113 @@ use crate::{
 /// This initializes the onchain computation definition account that registers the encrypted
 /// instruction. Must be called once before using the `vote` encrypted instruction.
 pub fn init_vote_comp_def(ctx: Context<InitVoteCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
     init_comp_def(ctx.accounts, None, None)?;
     Ok(())
 }
 
 /// Submits an encrypted vote to the poll.
 ///
-/// This function allows a voter to cast their vote (0 = Neo robot, 1 = Humane AI PIN, 2 = friend.com) in encrypted form.
+/// This function allows a voter to cast their vote (the index of their chosen option) in encrypted form.
 /// The vote is added to the running tally through MPC computation, ensuring
 /// that individual votes remain confidential while updating the overall count.
+/// A `VoterRecord` is created for the voter, so each voter can only vote once per poll (until
+/// `reset_tally` resets it), holding a receipt that's marked counted once the vote is in the tally.
+/// In token-weighted polls the vote is weighted by the voter's balance of the poll's `weight_mint`,
+/// up to the poll's `max_weight`. In conviction polls the weight is then multiplied by the poll's
+/// `conviction_multiplier`, so votes cast earlier weigh more.
 ///
 /// # Arguments
 /// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
-/// * `choice` - Encrypted vote choice (0, 1, or 2 for the three options)
+/// * `choice` - Encrypted vote choice (an option index below the poll's `num_options`)
 /// * `vote_encryption_pubkey` - Voter's public key for encryption
-/// * `vote_nonce` - Cryptographic nonce for the vote encryption
+/// * `vote_nonce` - Cryptographic nonce for the vote encryption. Ignored in polls with
+///   `derive_nonces`, where the choice must be encrypted with the poll's next derived nonce.
+/// * `scheme` - How the choice is encrypted, one of the poll's `supported_schemes`
+/// * `comment` - An optional encrypted comment, stored in the voter's `VoteComment` (which
+///   must then be passed) for the poll authority to read with `reveal_comment`. Comments are
+///   never counted and play no part in the result.
 ///
 /// Note: The `unused_variables` warning for `poll_id` is spurious. The parameter is actually used
 /// in the `Vote` struct's `#[account]` constraint via `poll_id.to_le_bytes()` for PDA
 /// derivation. However, Rust's compiler cannot detect this usage because Anchor's macros expand
 /// after the static analysis phase, so it appears unused in the function body.
 #[allow(unused_variables)]
+#[allow(clippy::too_many_arguments)]
 pub fn vote(
     ctx: Context<Vote>,
     computation_offset: u64,
     poll_id: u32,
-    choice: [u8; 32],
-    vote_encryption_pubkey: [u8; 32],
+    choice: EncChoice,
+    vote_encryption_pubkey: EncPubkey,
     vote_nonce: u128,
+    scheme: EncScheme,
+    comment: Option<EncComment>,
 ) -> Result<()> {
-    let computation_args = ArgBuilder::new()
-        .x25519_pubkey(vote_encryption_pubkey)
-        .plaintext_u128(vote_nonce)
-        .encrypted_u8(choice)
-        .plaintext_u128(ctx.accounts.poll_account.nonce)
-        .account(
-            ctx.accounts.poll_account.key(),
-            // Offset calculation: discriminator + 1 byte (bump)
-            (Poll::DISCRIMINATOR.len() + 1) as u32,
-            32 * 3, // 3 vote counters (Neo robot, Humane AI PIN, friend.com), each stored as 32-byte ciphertext
-        )
-        .build();
+    // Quadratic votes have to spend credits, through vote_quadratic, and receipt-free votes
+    // are only counted in batches, through commit_vote and vote_many
+    require!(
+        !matches!(
+            ctx.accounts.poll_account.poll_type,
+            PollType::Quadratic | PollType::ReceiptFree
+        ),
+        ErrorCode::WrongPollType
+    );
+    // Collected first, so check_voter's balance check covers what the payer has left
+    collect_vote_fee(
+        &ctx.accounts.poll_account,
+        ctx.accounts.fee_vault.as_deref_mut(),
+        ctx.bumps.fee_vault,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    check_voter(&ctx.accounts.poll_account, &ctx.accounts.voter_list, &ctx.accounts.payer)?;
+    let weight = vote_weight(
+        &ctx.accounts.poll_account,
+        ctx.accounts.weight_token_account.as_ref(),
+        ctx.accounts.payer.key(),
+    )?;
+
+    ctx.accounts.voter_record.bump = ctx.bumps.voter_record;
+    let vote_nonce = record_vote(
+        &mut ctx.accounts.poll_account,
+        &mut ctx.accounts.voter_record,
+        ctx.accounts.payer.key(),
+        choice,
+        vote_encryption_pubkey,
+        vote_nonce,
+        scheme,
+        weight,
+        ctx.accounts.computation_account.key(),
+    )?;
+    if let Some(comment) = comment {
+        store_comment(
+            ctx.accounts.vote_comment.as_deref_mut(),
+            ctx.bumps.vote_comment,
+            &ctx.accounts.voter_record,
+            comment,
+            vote_nonce,
+        )?;
+    }
+
+    let computation_args = arg_builder(&vote_arguments(
+        ctx.accounts.poll_account.key(),
+        &ctx.accounts.poll_account,
+        choice.0,
+        vote_encryption_pubkey.0,
+        vote_nonce,
+        weight,
+    ))
+    .build();
 
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+    let [event_authority, program] = event_cpi_callback_accounts();
+
     queue_computation(
         ctx.accounts,
         computation_offset,
@@ -63,14 +138,33 @@ pub fn vote(
         vec![VoteCallback::callback_ix(
             computation_offset,
             &ctx.accounts.mxe_account,
-            &[CallbackAccount {
-                pubkey: ctx.accounts.poll_account.key(),
-                is_writable: true,
-            }]
+            &[
+                CallbackAccount {
+                    pubkey: ctx.accounts.poll_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.voter_record.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.config.key(),
+                    is_writable: true,
+                },
+                event_authority,
+                program,
+            ]
         )?],
         1,
-        0,
+        cu_price_micro,
     )?;
+
+    msg!(
+        "Vote queued for poll {} (computation offset {})",
+        ctx.accounts.poll_account.id,
+        computation_offset
+    );
+
     Ok(())
 }
 
@@ -78,20 +172,333 @@ pub fn vote_callback(
     ctx: Context<VoteCallback>,
     output: SignedComputationOutputs<VoteOutput>,
 ) -> Result<()> {
-    let VoteOutput { field_0: vote_result } = output.verify_output(
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // Released with release_tally after the timeout, and perhaps held by another computation
+    // since: the tally may have moved on since this vote's computation read it, so storing its
+    // result could undo other votes
+    if !ctx.accounts.poll_account.holds_tally_for(ctx.accounts.computation_account.key()) {
+        if !ctx.accounts.voter_record.committed {
+            ctx.accounts.voter_record.poll = Pubkey::default();
+        }
+        msg!(
+            "Vote for poll {} landed after its tally was released",
+            ctx.accounts.poll_account.id
+        );
+        return Ok(());
+    }
+
+    let (vote_result, invariants_hold) = match verify_computation(
+        output,
         &ctx.accounts.cluster_account,
         &ctx.accounts.computation_account
-    )?;
+    ) {
+        Ok(VoteOutput {
+            field_0:
+                VoteOutputStruct0 {
+                    field_0: vote_result,
+                    field_1: invariants_hold,
+                },
+        }) => (vote_result, invariants_hold),
+        Err(error) => {
+            // Failing would leave the tally held forever. The vote just isn't counted.
+            ctx.accounts.poll_account.tally_pending = false;
+            defer_after_key_rotation(&mut ctx.accounts.poll_account, error)?;
+            // A committed vote stays committed for finalize_vote to retry. Any other vote
+            // is freed for the voter to send again, whatever the failure.
+            if !ctx.accounts.voter_record.committed {
+                ctx.accounts.voter_record.poll = Pubkey::default();
+            }
+            msg!("Vote for poll {} failed: {}", ctx.accounts.poll_account.id, error);
+            return Ok(());
+        }
+    };
+
+    if !invariants_hold {
+        // The circuit handed back the tally unchanged, so the stored one is still current.
+        // As after a failed computation, a committed vote stays committed and any other is freed.
+        ctx.accounts.poll_account.tally_pending = false;
+        if !ctx.accounts.voter_record.committed {
+            ctx.accounts.voter_record.poll = Pubkey::default();
+        }
+        msg!(
+            "Vote for poll {} aborted: the updated tally broke the vote circuit's invariants",
+            ctx.accounts.poll_account.id
+        );
+        emit!(VoteAbortedEvent {
+            poll: ctx.accounts.poll_account.key(),
+            voter: ctx.accounts.voter_record.voter,
+        });
+        return Ok(());
+    }
 
-    ctx.accounts.poll_account.vote_counts = vote_result.ciphertexts;
-    ctx.accounts.poll_account.nonce = vote_result.nonce;
+    count_vote(
+        &mut ctx.accounts.poll_account,
+        &mut ctx.accounts.voter_record,
+        &mut ctx.accounts.config,
+        vote_result.ciphertexts,
+        vote_result.nonce,
+    );
+
+    msg!(
+        "Vote counted for poll {} (computation {})",
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
 
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
 
-    emit!(VoteEvent {
+    let event = VoteEvent {
         timestamp: current_timestamp,
-    });
+    };
+    // Logged for existing clients, and also written as self-CPI instruction data, which
+    // indexers can read even when an RPC provider truncates the logs
+    emit!(event.clone());
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Checks the poll is open and the payer may vote in it. Shared by `vote` and `vote_quadratic`.
+pub(crate) fn check_voter(
+    poll: &Poll,
+    voter_list: &AccountInfo,
+    payer: &AccountInfo,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(!poll.voting_closed(&clock), ErrorCode::VotingClosed);
+    require!(!poll.voting_not_started(&clock), ErrorCode::VotingNotStarted);
+    require_computation_funds(payer)?;
+
+    if poll.settings.use_voter_list {
+        require!(!voter_list.data_is_empty(), ErrorCode::VoterNotEligible);
+        let voter_list = VoterList::try_deserialize(&mut &voter_list.data.borrow()[..])?;
+        require!(
+            voter_list.voters.contains(payer.key),
+            ErrorCode::VoterNotEligible
+        );
+    }
+
+    Ok(())
+}
+
+/// The weight a vote counts with: 1, or in token-weighted polls the voter's balance of the
+/// poll's `weight_mint` up to its `max_weight`, then times the poll's `conviction_multiplier`.
+/// Shared by `vote` and `commit_vote`.
+pub(crate) fn vote_weight(
+    poll: &Poll,
+    weight_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    voter: Pubkey,
+) -> Result<u64> {
+    let weight = match poll.settings.weight_mint {
+        Some(weight_mint) => {
+            let token_account =
+                weight_token_account.ok_or(ErrorCode::InvalidWeightTokenAccount)?;
+            require!(
+                token_account.mint == weight_mint && token_account.owner == voter,
+                ErrorCode::InvalidWeightTokenAccount
+            );
+            // Dropping decimals keeps large balances from saturating the counters
+            let weight = token_account.amount / 10u64.pow(poll.settings.weight_scale as u32);
+            require!(weight > 0, ErrorCode::NoVotingWeight);
+            weight
+        }
+        None => VOTE_WEIGHT,
+    };
+    // Capped here rather than in the circuit: the weight is a public balance either way
+    let weight = match poll.settings.max_weight {
+        0 => weight,
+        max_weight => weight.min(max_weight),
+    };
+    // Applied after the cap, so early whales still outweigh late ones
+    Ok(weight.saturating_mul(poll.conviction_multiplier(Clock::get()?.unix_timestamp)))
+}
+
+/// Moves the poll's `vote_fee`, if it has one, from the voter into the poll's fee vault.
+/// Shared by `vote` and `vote_quadratic`.
+pub(crate) fn collect_vote_fee<'info>(
+    poll: &Account<'info, Poll>,
+    fee_vault: Option<&mut Account<'info, FeeVault>>,
+    fee_vault_bump: Option<u8>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let fee = poll.settings.vote_fee;
+    if fee == 0 {
+        return Ok(());
+    }
+    let fee_vault = fee_vault.ok_or(ErrorCode::MissingFeeVault)?;
+    fee_vault.bump = fee_vault_bump.ok_or(ErrorCode::MissingFeeVault)?;
+    fee_vault.poll = poll.key();
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: fee_vault.to_account_info(),
+            },
+        ),
+        fee,
+    )?;
+    fee_vault.total_collected += fee;
 
     Ok(())
 }
+
+/// Checks the vote's encryption scheme and key, then fills in its `VoterRecord` and holds the poll's tally
+/// for the vote's `computation` until the vote is counted.
+/// Shared by `vote` and `vote_quadratic`.
+///
+/// # Returns
+/// The nonce the choice is encrypted with: `vote_nonce`, or a derived one if the poll
+/// derives its nonces
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_vote(
+    poll: &mut Account<Poll>,
+    voter_record: &mut VoterRecord,
+    voter: Pubkey,
+    choice: EncChoice,
+    vote_encryption_pubkey: EncPubkey,
+    vote_nonce: u128,
+    scheme: EncScheme,
+    weight: u64,
+    computation: Pubkey,
+) -> Result<u128> {
+    let vote_nonce = commit_ballot(
+        poll,
+        voter_record,
+        voter,
+        choice,
+        vote_encryption_pubkey,
+        vote_nonce,
+        scheme,
+        weight,
+    )?;
+    hold_tally(poll, computation)?;
+
+    Ok(vote_nonce)
+}
+
+/// Stores a vote's encrypted comment in the voter's `VoteComment`, apart from the tally.
+fn store_comment(
+    vote_comment: Option<&mut Account<VoteComment>>,
+    vote_comment_bump: Option<u8>,
+    voter_record: &VoterRecord,
+    comment: EncComment,
+    vote_nonce: u128,
+) -> Result<()> {
+    // Under the same key, the same nonce would encrypt both with the same keystream
+    require!(comment.nonce != vote_nonce, ErrorCode::CommentNonceReused);
+    let vote_comment = vote_comment.ok_or(ErrorCode::MissingVoteComment)?;
+    vote_comment.bump = vote_comment_bump.ok_or(ErrorCode::MissingVoteComment)?;
+    vote_comment.poll = voter_record.poll;
+    vote_comment.voter = voter_record.voter;
+    vote_comment.encryption_pubkey = voter_record.vote_encryption_pubkey;
+    vote_comment.nonce = comment.nonce;
+    vote_comment.ciphertexts = comment.ciphertexts;
+
+    Ok(())
+}
+
+/// Holds the poll's tally for a vote computation, until its callback lands.
+/// Shared by `record_vote` and `finalize_vote`.
+pub(crate) fn hold_tally(poll: &mut Poll, computation: Pubkey) -> Result<()> {
+    // The computation reads the stored tally, so it must not start before the last one lands
+    require!(!poll.tally_pending, ErrorCode::TallyBusy);
+    require!(
+        Clock::get()?.slot >= poll.retry_after_slot,
+        ErrorCode::ClusterRotating
+    );
+    poll.hold_tally_at(Clock::get()?.slot, computation);
+
+    Ok(())
+}
+
+/// Checks the vote's encryption scheme and key, then fills in its `VoterRecord`, without touching the
+/// poll's tally. Shared by `record_vote` and `commit_vote`.
+///
+/// # Returns
+/// The nonce the choice is encrypted with: `vote_nonce`, or a derived one if the poll
+/// derives its nonces
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn commit_ballot(
+    poll: &mut Account<Poll>,
+    voter_record: &mut VoterRecord,
+    voter: Pubkey,
+    choice: EncChoice,
+    vote_encryption_pubkey: EncPubkey,
+    vote_nonce: u128,
+    scheme: EncScheme,
+    weight: u64,
+) -> Result<u128> {
+    // Checked before anything is queued, as the MPC computation would be wasted on them
+    require!(
+        poll.settings.supported_schemes & scheme.bit() != 0,
+        ErrorCode::UnsupportedScheme
+    );
+    require!(vote_encryption_pubkey.is_valid(), ErrorCode::InvalidEncryptionKey);
+    // A new record is zeroed. An existing one can only be reused after a reset.
+    require!(
+        voter_record.poll == Pubkey::default() || voter_record.epoch < poll.tally_epoch,
+        ErrorCode::AlreadyVoted
+    );
+
+    let vote_nonce = if poll.settings.derive_nonces {
+        let poll_key = poll.key();
+        poll.derive_nonce(&poll_key)
+    } else {
+        vote_nonce
+    };
+
+    voter_record.poll = poll.key();
+    voter_record.voter = voter;
+    let clock = Clock::get()?;
+    voter_record.voted_at = clock.unix_timestamp;
+    voter_record.voted_slot = clock.slot;
+    voter_record.choice = choice.0;
+    voter_record.vote_encryption_pubkey = vote_encryption_pubkey.0;
+    voter_record.vote_nonce = vote_nonce;
+    voter_record.weight = weight;
+    // A receipt would let a voter prove their vote to a coercer
+    voter_record.receipt = if poll.poll_type == PollType::ReceiptFree {
+        [0; 32]
+    } else {
+        VoterRecord::receipt(&choice.0, poll.nonce)
+    };
+    voter_record.counted = false;
+    voter_record.committed = false;
+    voter_record.epoch = poll.tally_epoch;
+
+    Ok(vote_nonce)
+}
+
+/// Stores a vote computation's updated tally, releasing it for the next vote, and marks the
+/// vote counted. Shared by `vote_callback` and `vote_quadratic_callback`.
+pub(crate) fn count_vote(
+    poll: &mut Poll,
+    voter_record: &mut VoterRecord,
+    config: &mut ProgramConfig,
+    ciphertexts: [[u8; 32]; Poll::POLL_TALLY_CIPHERTEXTS],
+    nonce: u128,
+) {
+    poll.set_poll_tally(ciphertexts, nonce);
+    poll.tally_pending = false;
+    mark_counted(poll, voter_record, config);
+}
+
+/// Marks a vote whose computation has landed as counted, in the poll's turnout and the
+/// program's stats. Shared by `count_vote` and `vote_many_callback`.
+pub(crate) fn mark_counted(
+    poll: &mut Poll,
+    voter_record: &mut VoterRecord,
+    config: &mut ProgramConfig,
+) {
+    poll.total_votes += 1;
+    config.total_votes_all_polls += 1;
+    voter_record.turnout_index = poll.record_turnout(&voter_record.voter);
+    // The receipt now vouches for a vote that's in the tally
+    voter_record.counted = true;
+    voter_record.committed = false;
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::{vote_many_arguments, Ballot},
+    constants::MAX_BATCH_BALLOTS,
+    error::ErrorCode,
+    state::{VoteEvent, VoterRecord},
+    election::{InitVoteManyCompDef, VoteMany, VoteManyCallback, VoteManyOutput},
+    handlers::{
+        create_poll::{
+            arg_builder, defer_after_key_rotation, event_cpi_callback_accounts,
+            require_arcium_caller, require_computation_funds, verify_computation,
+        },
+        vote::{hold_tally, mark_counted},
+    },
+};
+
+/// One-off job to create computation definition for `vote_many` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `vote_many` encrypted instruction.
+pub fn init_vote_many_comp_def(ctx: Context<InitVoteManyCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Counts a batch of committed votes in one computation, for high-volume polls where the
+/// fixed cost of each vote's computation dominates.
+///
+/// The votes are committed by their voters with `commit_vote` as usual, which checks them,
+/// collects any fee and stores each ballot in its `VoterRecord`. Anyone can then pass 1 to
+/// `MAX_BATCH_BALLOTS` of those records as remaining accounts, and `vote_many_callback` counts
+/// them all when the computation lands. Each voter's ballot stays encrypted under their own
+/// key, so whoever sends the batch learns nothing about the choices. If the computation
+/// fails, the votes stay committed, to be batched again or finalized one by one. In
+/// receipt-free polls this is the only way votes are counted.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+#[allow(unused_variables)]
+pub fn vote_many<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VoteMany<'info>>,
+    computation_offset: u64,
+    poll_id: u32,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.poll_account.voting_closed(&Clock::get()?),
+        ErrorCode::VotingClosed
+    );
+    require!(
+        (1..=MAX_BATCH_BALLOTS).contains(&ctx.remaining_accounts.len()),
+        ErrorCode::InvalidBallotCount
+    );
+    require_computation_funds(&ctx.accounts.payer)?;
+
+    let poll_key = ctx.accounts.poll_account.key();
+    let mut ballots = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut callback_accounts = vec![
+        CallbackAccount {
+            pubkey: poll_key,
+            is_writable: true,
+        },
+        CallbackAccount {
+            pubkey: ctx.accounts.config.key(),
+            is_writable: true,
+        },
+    ];
+    callback_accounts.extend(event_cpi_callback_accounts());
+    for (index, record_info) in ctx.remaining_accounts.iter().enumerate() {
+        require_keys_eq!(*record_info.owner, crate::ID, ErrorCode::InvalidVoterRecord);
+        // Counting the same ballot twice would count its voter twice
+        require!(
+            ctx.remaining_accounts[..index]
+                .iter()
+                .all(|earlier| earlier.key != record_info.key),
+            ErrorCode::DuplicateBallot
+        );
+        let voter_record = VoterRecord::try_deserialize(&mut &record_info.data.borrow()[..])
+            .map_err(|_| ErrorCode::InvalidVoterRecord)?;
+        require_keys_eq!(voter_record.poll, poll_key, ErrorCode::InvalidVoterRecord);
+        require!(
+            voter_record.committed && voter_record.epoch == ctx.accounts.poll_account.tally_epoch,
+            ErrorCode::NoCommittedVote
+        );
+
+        ballots.push(Ballot {
+            choice: voter_record.choice,
+            vote_encryption_pubkey: voter_record.vote_encryption_pubkey,
+            vote_nonce: voter_record.vote_nonce,
+            weight: voter_record.weight,
+        });
+        callback_accounts.push(CallbackAccount {
+            pubkey: record_info.key(),
+            is_writable: true,
+        });
+    }
+
+    hold_tally(
+        &mut ctx.accounts.poll_account,
+        ctx.accounts.computation_account.key(),
+    )?;
+
+    let computation_args =
+        arg_builder(&vote_many_arguments(poll_key, &ctx.accounts.poll_account, &ballots)).build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![VoteManyCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &callback_accounts
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Batch of {} committed votes queued for poll {} (computation offset {})",
+        ballots.len(),
+        ctx.accounts.poll_account.id,
+        computation_offset
+    );
+
+    Ok(())
+}
+
+/// Stores the batch's updated tally and marks every voter record in it counted. The records
+/// arrive as remaining accounts, in the order they were batched.
+pub fn vote_many_callback(
+    ctx: Context<VoteManyCallback>,
+    output: SignedComputationOutputs<VoteManyOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // As in `vote_callback`, a batch landing after release_tally isn't counted. The votes
+    // stay committed.
+    if !ctx.accounts.poll_account.holds_tally_for(ctx.accounts.computation_account.key()) {
+        msg!(
+            "Vote batch for poll {} landed after its tally was released",
+            ctx.accounts.poll_account.id
+        );
+        return Ok(());
+    }
+
+    let vote_result = match verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    ) {
+        Ok(VoteManyOutput { field_0 }) => field_0,
+        Err(error) => {
+            // Failing would leave the tally held forever. Whatever the failure, the votes
+            // stay committed, to be batched again or finalized one by one.
+            ctx.accounts.poll_account.tally_pending = false;
+            defer_after_key_rotation(&mut ctx.accounts.poll_account, error)?;
+            msg!("Vote batch for poll {} failed: {}", ctx.accounts.poll_account.id, error);
+            return Ok(());
+        }
+    };
+
+    let poll = &mut ctx.accounts.poll_account;
+    poll.set_poll_tally(vote_result.ciphertexts, vote_result.nonce);
+    poll.tally_pending = false;
+    for record_info in ctx.remaining_accounts {
+        let mut voter_record = VoterRecord::try_deserialize(&mut &record_info.data.borrow()[..])?;
+        mark_counted(poll, &mut voter_record, &mut ctx.accounts.config);
+        voter_record.try_serialize(&mut &mut record_info.data.borrow_mut()[..])?;
+    }
+
+    msg!(
+        "Batch of {} votes counted for poll {} (computation {})",
+        ctx.remaining_accounts.len(),
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    // One event per vote, as if each had been counted on its own
+    let timestamp = Clock::get()?.unix_timestamp;
+    for _ in ctx.remaining_accounts {
+        let event = VoteEvent { timestamp };
+        emit!(event.clone());
+        emit_cpi!(event);
+    }
+
+    Ok(())
+}
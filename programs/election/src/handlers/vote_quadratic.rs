@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+use crate::{
+    client::{POLL_TALLY_LENGTH, POLL_TALLY_OFFSET},
+    error::ErrorCode,
+    handlers::{
+        create_poll::{
+            defer_after_key_rotation, event_cpi_callback_accounts, require_arcium_caller,
+            verify_computation,
+        },
+        vote::{check_voter, collect_vote_fee, count_vote, record_vote},
+    },
+    state::{Ciphertext, EncChoice, EncPubkey, EncScheme, PollType, VoteEvent},
+    election::{
+        InitVoteQuadraticCompDef, VoteQuadratic, VoteQuadraticCallback, VoteQuadraticOutput,
+    },
+};
+
+/// One-off job to create computation definition for `vote_quadratic` in encrypted-ixs/src/lib.rs.
+///
+/// This initializes the onchain computation definition account that registers the encrypted
+/// instruction. Must be called once before using the `vote_quadratic` encrypted instruction.
+pub fn init_vote_quadratic_comp_def(ctx: Context<InitVoteQuadraticCompDef>) -> Result<()> {
+    require!(
+        ctx.accounts.comp_def_account.data_is_empty(),
+        ErrorCode::CompDefAlreadyInitialized
+    );
+    init_comp_def(ctx.accounts, None, None)?;
+    Ok(())
+}
+
+/// Submits an encrypted quadratic vote to a poll created with `quadratic_credits`.
+///
+/// The voter spends some of their credits on one option, which gains the square root of the
+/// credits spent. Both the choice and the credits are encrypted, under the same key and nonce,
+/// so nobody learns how strongly anyone voted. Spending more than the poll's
+/// `quadratic_credits` spoils the vote. Otherwise works like `vote`.
+///
+/// # Arguments
+/// * `poll_id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+/// * `choice` - Encrypted vote choice (an option index below the poll's `num_options`)
+/// * `credits` - Encrypted number of credits spent on the choice
+/// * `vote_encryption_pubkey` - Voter's public key for encryption
+/// * `vote_nonce` - Cryptographic nonce for the vote encryption. Ignored in polls with
+///   `derive_nonces`, where the vote must be encrypted with the poll's next derived nonce.
+/// * `scheme` - How the vote is encrypted, one of the poll's `supported_schemes`
+#[allow(unused_variables)]
+#[allow(clippy::too_many_arguments)]
+pub fn vote_quadratic(
+    ctx: Context<VoteQuadratic>,
+    computation_offset: u64,
+    poll_id: u32,
+    choice: EncChoice,
+    credits: Ciphertext,
+    vote_encryption_pubkey: EncPubkey,
+    vote_nonce: u128,
+    scheme: EncScheme,
+) -> Result<()> {
+    require!(
+        ctx.accounts.poll_account.poll_type == PollType::Quadratic,
+        ErrorCode::WrongPollType
+    );
+    collect_vote_fee(
+        &ctx.accounts.poll_account,
+        ctx.accounts.fee_vault.as_deref_mut(),
+        ctx.bumps.fee_vault,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    check_voter(&ctx.accounts.poll_account, &ctx.accounts.voter_list, &ctx.accounts.payer)?;
+
+    ctx.accounts.voter_record.bump = ctx.bumps.voter_record;
+    // The votes the credits buy are only known inside the computation
+    let vote_nonce = record_vote(
+        &mut ctx.accounts.poll_account,
+        &mut ctx.accounts.voter_record,
+        ctx.accounts.payer.key(),
+        choice,
+        vote_encryption_pubkey,
+        vote_nonce,
+        scheme,
+        0,
+        ctx.accounts.computation_account.key(),
+    )?;
+
+    let computation_args = ArgBuilder::new()
+        .x25519_pubkey(vote_encryption_pubkey.0)
+        .plaintext_u128(vote_nonce)
+        .encrypted_u8(choice.0)
+        .encrypted_u64(credits.0)
+        .plaintext_u8(ctx.accounts.poll_account.num_options)
+        .plaintext_u64(ctx.accounts.poll_account.settings.quadratic_credits)
+        .plaintext_u128(ctx.accounts.poll_account.nonce)
+        .account(
+            ctx.accounts.poll_account.key(),
+            POLL_TALLY_OFFSET,
+            // The whole PollTally, each field stored as a 32-byte ciphertext
+            POLL_TALLY_LENGTH,
+        )
+        .build();
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let cu_price_micro = ctx.accounts.poll_account.computation_cu_price_micro();
+    let [event_authority, program] = event_cpi_callback_accounts();
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        computation_args,
+        vec![VoteQuadraticCallback::callback_ix(
+            computation_offset,
+            &ctx.accounts.mxe_account,
+            &[
+                CallbackAccount {
+                    pubkey: ctx.accounts.poll_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.voter_record.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.config.key(),
+                    is_writable: true,
+                },
+                event_authority,
+                program,
+            ]
+        )?],
+        1,
+        cu_price_micro,
+    )?;
+
+    msg!(
+        "Quadratic vote queued for poll {} (computation offset {})",
+        ctx.accounts.poll_account.id,
+        computation_offset
+    );
+
+    Ok(())
+}
+
+pub fn vote_quadratic_callback(
+    ctx: Context<VoteQuadraticCallback>,
+    output: SignedComputationOutputs<VoteQuadraticOutput>,
+) -> Result<()> {
+    require_arcium_caller(&ctx.accounts.instructions_sysvar)?;
+
+    // As in `vote_callback`, a vote landing after release_tally isn't counted
+    if !ctx.accounts.poll_account.holds_tally_for(ctx.accounts.computation_account.key()) {
+        ctx.accounts.voter_record.poll = Pubkey::default();
+        msg!(
+            "Vote for poll {} landed after its tally was released",
+            ctx.accounts.poll_account.id
+        );
+        return Ok(());
+    }
+
+    let vote_result = match verify_computation(
+        output,
+        &ctx.accounts.cluster_account,
+        &ctx.accounts.computation_account
+    ) {
+        Ok(VoteQuadraticOutput { field_0 }) => field_0,
+        Err(error) => {
+            // Failing would leave the tally held forever. The vote just isn't counted.
+            ctx.accounts.poll_account.tally_pending = false;
+            defer_after_key_rotation(&mut ctx.accounts.poll_account, error)?;
+            // As in `vote_callback`, frees the voter to send the vote again
+            ctx.accounts.voter_record.poll = Pubkey::default();
+            msg!("Vote for poll {} failed: {}", ctx.accounts.poll_account.id, error);
+            return Ok(());
+        }
+    };
+
+    count_vote(
+        &mut ctx.accounts.poll_account,
+        &mut ctx.accounts.voter_record,
+        &mut ctx.accounts.config,
+        vote_result.ciphertexts,
+        vote_result.nonce,
+    );
+
+    msg!(
+        "Quadratic vote counted for poll {} (computation {})",
+        ctx.accounts.poll_account.id,
+        ctx.accounts.computation_account.key()
+    );
+
+    let event = VoteEvent {
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+    // As in `vote_callback`, also emitted as a self-CPI for indexers
+    emit!(event.clone());
+    emit_cpi!(event);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    election::WithdrawFees,
+    error::ErrorCode,
+    state::{AuthorityAction, FeesWithdrawnEvent},
+};
+
+/// Sends the vote fees a poll's `FeeVault` has collected to the poll authority.
+///
+/// Only the poll authority can withdraw, and it's logged in the poll's `AuthorityLog`. The
+/// vault keeps its rent-exempt minimum, so it can go on collecting fees.
+///
+/// # Arguments
+/// * `id` - The poll ID (used for account derivation via Anchor's #[instruction] attribute)
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, id: u32) -> Result<()> {
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    let rent_exempt_minimum =
+        Rent::get()?.minimum_balance(fee_vault.to_account_info().data_len());
+    let amount = fee_vault.get_lamports().saturating_sub(rent_exempt_minimum);
+    require!(amount > 0, ErrorCode::NoFeesToWithdraw);
+
+    fee_vault.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
+    fee_vault.total_withdrawn += amount;
+
+    ctx.accounts.authority_log.record(
+        ctx.bumps.authority_log,
+        ctx.accounts.poll_account.key(),
+        ctx.accounts.authority.key(),
+        AuthorityAction::WithdrawFees,
+    )?;
+
+    msg!("Withdrew {} lamports of fees from poll {}", amount, id);
+
+    emit!(FeesWithdrawnEvent { amount });
+
+    Ok(())
+}
@@ -6,8 +6,10 @@
 #![allow(deprecated)]
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 use arcium_anchor::prelude::*;
 
+pub mod client;
 pub mod constants;
 pub mod error;
 pub mod handlers;
@@ -15,7 +17,14 @@ pub mod state;
 
 use constants::*;
 pub use error::ErrorCode;
-pub use state::Poll;
+pub use state::{
+    AuthorityLog, AuthorityPollIndex, AuthorityState, Ciphertext, EncChoice, EncComment, EncMode,
+    EncPubkey, EncScheme, FeeVault, Poll, PollSettings, PollStatus, PollTemplate, PollType,
+    ProgramConfig, ProgramStats, ResultAttestation, ResultsAccount, SealedResult, TallyHistory,
+    TallySnapshot, TurnoutProof, VoteComment, VoterList, VoterRecord, VoterRecordView,
+};
+#[cfg(feature = "debug")]
+pub use state::PollLayout;
 
 declare_id!("EmzBbFz57z4S47eca95vPxSLFeotx4tTXmNK8cDd1sBJ");
 
@@ -32,9 +41,19 @@ pub mod election {
         computation_offset: u64,
         id: u32,
         question: String,
+        num_options: u8,
         nonce: u128,
+        settings: PollSettings,
     ) -> Result<()> {
-        handlers::create_poll::create_poll(ctx, computation_offset, id, question, nonce)
+        handlers::create_poll::create_poll(
+            ctx,
+            computation_offset,
+            id,
+            question,
+            num_options,
+            nonce,
+            settings,
+        )
     }
 
     #[arcium_callback(encrypted_ix = "create_poll")]
@@ -45,18 +64,29 @@ pub mod election {
         handlers::create_poll::create_poll_callback(ctx, output)
     }
 
+    pub fn update_question(ctx: Context<UpdateQuestion>, id: u32, question: String) -> Result<()> {
+        handlers::update_question::update_question(ctx, id, question)
+    }
+
+    pub fn resize_poll(ctx: Context<ResizePoll>, id: u32) -> Result<()> {
+        handlers::resize_poll::resize_poll(ctx, id)
+    }
+
     pub fn init_vote_comp_def(ctx: Context<InitVoteCompDef>) -> Result<()> {
         handlers::vote::init_vote_comp_def(ctx)
     }
 
     #[allow(unused_variables)]
+    #[allow(clippy::too_many_arguments)]
     pub fn vote(
         ctx: Context<Vote>,
         computation_offset: u64,
         poll_id: u32,
-        choice: [u8; 32],
-        vote_encryption_pubkey: [u8; 32],
+        choice: EncChoice,
+        vote_encryption_pubkey: EncPubkey,
         vote_nonce: u128,
+        scheme: EncScheme,
+        comment: Option<EncComment>,
     ) -> Result<()> {
         handlers::vote::vote(
             ctx,
@@ -65,6 +95,8 @@ pub mod election {
             choice,
             vote_encryption_pubkey,
             vote_nonce,
+            scheme,
+            comment,
         )
     }
 
@@ -88,6 +120,14 @@ pub mod election {
         handlers::reveal_result::reveal_result(ctx, computation_offset, id)
     }
 
+    pub fn crank_reveal(
+        ctx: Context<RevealResult>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_result::crank_reveal(ctx, computation_offset, id)
+    }
+
     #[arcium_callback(encrypted_ix = "reveal_result")]
     pub fn reveal_result_callback(
         ctx: Context<RevealResultCallback>,
@@ -96,45 +136,4875 @@ pub mod election {
         handlers::reveal_result::reveal_result_callback(ctx, output)
     }
 
-    // Account struct definitions - these need to be inside the arcium_program module
-    // so they can access the generated SignerAccount type
+    pub fn init_reveal_weight_quorum_comp_def(
+        ctx: Context<InitRevealWeightQuorumCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_weight_quorum::init_reveal_weight_quorum_comp_def(ctx)
+    }
 
-    #[init_computation_definition_accounts("create_poll", payer)]
-    #[derive(Accounts)]
-    pub struct CreatePollCompDef<'info> {
-        #[account(mut)]
-        pub payer: Signer<'info>,
+    pub fn reveal_weight_quorum(
+        ctx: Context<RevealWeightQuorum>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_weight_quorum::reveal_weight_quorum(ctx, computation_offset, id)
+    }
 
-        #[account(
-            mut,
-            address = derive_mxe_pda!()
-        )]
-        pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[arcium_callback(encrypted_ix = "reveal_weight_quorum")]
+    pub fn reveal_weight_quorum_callback(
+        ctx: Context<RevealWeightQuorumCallback>,
+        output: SignedComputationOutputs<RevealWeightQuorumOutput>,
+    ) -> Result<()> {
+        handlers::reveal_weight_quorum::reveal_weight_quorum_callback(ctx, output)
+    }
 
-        #[account(mut)]
-        /// CHECK: comp_def_account, checked by arcium program.
-        /// Can't check it here as it's not initialized yet.
-        pub comp_def_account: UncheckedAccount<'info>,
+    #[allow(unused_variables)]
+    pub fn get_voter_record(
+        ctx: Context<GetVoterRecord>,
+        poll_id: u32,
+    ) -> Result<VoterRecordView> {
+        handlers::get_voter_record::get_voter_record(ctx, poll_id)
+    }
 
-        #[account(mut)]
-        /// CHECK: address_lookup_table, checked by arcium program
-        pub address_lookup_table: UncheckedAccount<'info>,
+    pub fn aggregate_polls(ctx: Context<AggregatePolls>) -> Result<()> {
+        handlers::aggregate_polls::aggregate_polls(ctx)
+    }
 
-        /// CHECK: lut_program, checked by arcium program
-        pub lut_program: UncheckedAccount<'info>,
+    pub fn batch_check_voted(ctx: Context<BatchCheckVoted>, voter: Pubkey) -> Result<u32> {
+        handlers::batch_check_voted::batch_check_voted(ctx, voter)
+    }
+
+    pub fn prove_turnout(ctx: Context<ProveTurnout>, id: u32) -> Result<TurnoutProof> {
+        handlers::prove_turnout::prove_turnout(ctx, id)
+    }
+
+    pub fn get_result_packed(
+        ctx: Context<GetResultPacked>,
+        id: u32,
+    ) -> Result<[u8; PACKED_RESULT_LENGTH]> {
+        handlers::get_result_packed::get_result_packed(ctx, id)
+    }
+
+    pub fn init_aggregate_child_comp_def(ctx: Context<InitAggregateChildCompDef>) -> Result<()> {
+        handlers::aggregate_child::init_aggregate_child_comp_def(ctx)
+    }
+
+    #[allow(unused_variables)]
+    pub fn aggregate_child(
+        ctx: Context<AggregateChild>,
+        computation_offset: u64,
+        parent_id: u32,
+    ) -> Result<()> {
+        handlers::aggregate_child::aggregate_child(ctx, computation_offset, parent_id)
+    }
+
+    #[arcium_callback(encrypted_ix = "aggregate_child")]
+    pub fn aggregate_child_callback(
+        ctx: Context<AggregateChildCallback>,
+        output: SignedComputationOutputs<AggregateChildOutput>,
+    ) -> Result<()> {
+        handlers::aggregate_child::aggregate_child_callback(ctx, output)
+    }
+
+    pub fn add_voters(ctx: Context<AddVoters>, id: u32, voters: Vec<Pubkey>) -> Result<()> {
+        handlers::add_voters::add_voters(ctx, id, voters)
+    }
+
+    pub fn init_reveal_decided_comp_def(ctx: Context<InitRevealDecidedCompDef>) -> Result<()> {
+        handlers::reveal_decided::init_reveal_decided_comp_def(ctx)
+    }
+
+    pub fn reveal_decided(
+        ctx: Context<RevealDecided>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_decided::reveal_decided(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_decided")]
+    pub fn reveal_decided_callback(
+        ctx: Context<RevealDecidedCallback>,
+        output: SignedComputationOutputs<RevealDecidedOutput>,
+    ) -> Result<()> {
+        handlers::reveal_decided::reveal_decided_callback(ctx, output)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        handlers::initialize_config::initialize_config(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        handlers::set_paused::set_paused(ctx, paused)
+    }
+
+    pub fn update_admin(ctx: Context<UpdateAdmin>, new_admin: Pubkey) -> Result<()> {
+        handlers::update_admin::update_admin(ctx, new_admin)
+    }
+
+    pub fn init_reveal_top_k_comp_def(ctx: Context<InitRevealTopKCompDef>) -> Result<()> {
+        handlers::reveal_top_k::init_reveal_top_k_comp_def(ctx)
+    }
+
+    pub fn reveal_top_k(
+        ctx: Context<RevealTopK>,
+        computation_offset: u64,
+        id: u32,
+        k: u8,
+    ) -> Result<()> {
+        handlers::reveal_top_k::reveal_top_k(ctx, computation_offset, id, k)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_top_k")]
+    pub fn reveal_top_k_callback(
+        ctx: Context<RevealTopKCallback>,
+        output: SignedComputationOutputs<RevealTopKOutput>,
+    ) -> Result<()> {
+        handlers::reveal_top_k::reveal_top_k_callback(ctx, output)
+    }
+
+    pub fn snapshot_tally(ctx: Context<SnapshotTally>, id: u32) -> Result<()> {
+        handlers::snapshot_tally::snapshot_tally(ctx, id)
+    }
+
+    pub fn finalize_poll(ctx: Context<FinalizePoll>, id: u32) -> Result<()> {
+        handlers::finalize_poll::finalize_poll(ctx, id)
+    }
+
+    pub fn init_reveal_tally_shared_comp_def(
+        ctx: Context<InitRevealTallySharedCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_tally_shared::init_reveal_tally_shared_comp_def(ctx)
+    }
+
+    pub fn reveal_tally_shared(
+        ctx: Context<RevealTallyShared>,
+        computation_offset: u64,
+        id: u32,
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        handlers::reveal_tally_shared::reveal_tally_shared(ctx, computation_offset, id, recipient_nonce)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_tally_shared")]
+    pub fn reveal_tally_shared_callback(
+        ctx: Context<RevealTallySharedCallback>,
+        output: SignedComputationOutputs<RevealTallySharedOutput>,
+    ) -> Result<()> {
+        handlers::reveal_tally_shared::reveal_tally_shared_callback(ctx, output)
+    }
+
+    pub fn set_reveal_cooldown(ctx: Context<SetRevealCooldown>, reveal_cooldown: i64) -> Result<()> {
+        handlers::set_reveal_cooldown::set_reveal_cooldown(ctx, reveal_cooldown)
+    }
+
+    pub fn set_max_active_polls(ctx: Context<SetMaxActivePolls>, max_active_polls: u32) -> Result<()> {
+        handlers::set_max_active_polls::set_max_active_polls(ctx, max_active_polls)
+    }
+
+    pub fn preallocate_poll(ctx: Context<PreallocatePoll>, id: u32) -> Result<()> {
+        handlers::preallocate_poll::preallocate_poll(ctx, id)
+    }
+
+    pub fn activate_poll(
+        ctx: Context<ActivatePoll>,
+        computation_offset: u64,
+        id: u32,
+        question: String,
+        num_options: u8,
+        nonce: u128,
+        settings: PollSettings,
+    ) -> Result<()> {
+        handlers::activate_poll::activate_poll(
+            ctx,
+            computation_offset,
+            id,
+            question,
+            num_options,
+            nonce,
+            settings,
+        )
+    }
+
+    pub fn set_wormhole_program(
+        ctx: Context<SetWormholeProgram>,
+        wormhole_program: Pubkey,
+    ) -> Result<()> {
+        handlers::set_wormhole_program::set_wormhole_program(ctx, wormhole_program)
+    }
+
+    pub fn post_result_cross_chain(ctx: Context<PostResultCrossChain>, id: u32) -> Result<()> {
+        handlers::post_result_cross_chain::post_result_cross_chain(ctx, id)
+    }
+
+    pub fn clone_poll(ctx: Context<ClonePoll>, source_id: u32, new_id: u32) -> Result<()> {
+        handlers::clone_poll::clone_poll(ctx, source_id, new_id)
+    }
+
+    pub fn freeze_poll(ctx: Context<FreezePoll>, id: u32) -> Result<()> {
+        handlers::freeze_poll::freeze_poll(ctx, id)
+    }
+
+    pub fn init_reveal_referendum_comp_def(
+        ctx: Context<InitRevealReferendumCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_referendum::init_reveal_referendum_comp_def(ctx)
+    }
+
+    pub fn reveal_referendum(
+        ctx: Context<RevealReferendum>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_referendum::reveal_referendum(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_referendum")]
+    pub fn reveal_referendum_callback(
+        ctx: Context<RevealReferendumCallback>,
+        output: SignedComputationOutputs<RevealReferendumOutput>,
+    ) -> Result<()> {
+        handlers::reveal_referendum::reveal_referendum_callback(ctx, output)
+    }
+
+    pub fn init_reveal_to_comp_def(ctx: Context<InitRevealToCompDef>) -> Result<()> {
+        handlers::reveal_to::init_reveal_to_comp_def(ctx)
+    }
+
+    pub fn reveal_to(
+        ctx: Context<RevealTo>,
+        computation_offset: u64,
+        id: u32,
+        recipient: EncPubkey,
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        handlers::reveal_to::reveal_to(ctx, computation_offset, id, recipient, recipient_nonce)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_to")]
+    pub fn reveal_to_callback(
+        ctx: Context<RevealToCallback>,
+        output: SignedComputationOutputs<RevealToOutput>,
+    ) -> Result<()> {
+        handlers::reveal_to::reveal_to_callback(ctx, output)
+    }
+
+    pub fn create_poll_if_needed(
+        ctx: Context<CreatePollIfNeeded>,
+        computation_offset: u64,
+        id: u32,
+        question: String,
+        num_options: u8,
+        nonce: u128,
+        settings: PollSettings,
+    ) -> Result<()> {
+        handlers::create_poll_if_needed::create_poll_if_needed(
+            ctx,
+            computation_offset,
+            id,
+            question,
+            num_options,
+            nonce,
+            settings,
+        )
+    }
+
+    pub fn init_reveal_spread_comp_def(ctx: Context<InitRevealSpreadCompDef>) -> Result<()> {
+        handlers::reveal_spread::init_reveal_spread_comp_def(ctx)
+    }
+
+    pub fn reveal_spread(
+        ctx: Context<RevealSpread>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_spread::reveal_spread(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_spread")]
+    pub fn reveal_spread_callback(
+        ctx: Context<RevealSpreadCallback>,
+        output: SignedComputationOutputs<RevealSpreadOutput>,
+    ) -> Result<()> {
+        handlers::reveal_spread::reveal_spread_callback(ctx, output)
+    }
+
+    pub fn init_vote_quadratic_comp_def(ctx: Context<InitVoteQuadraticCompDef>) -> Result<()> {
+        handlers::vote_quadratic::init_vote_quadratic_comp_def(ctx)
+    }
+
+    #[allow(unused_variables)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote_quadratic(
+        ctx: Context<VoteQuadratic>,
+        computation_offset: u64,
+        poll_id: u32,
+        choice: EncChoice,
+        credits: Ciphertext,
+        vote_encryption_pubkey: EncPubkey,
+        vote_nonce: u128,
+        scheme: EncScheme,
+    ) -> Result<()> {
+        handlers::vote_quadratic::vote_quadratic(
+            ctx,
+            computation_offset,
+            poll_id,
+            choice,
+            credits,
+            vote_encryption_pubkey,
+            vote_nonce,
+            scheme,
+        )
+    }
+
+    #[arcium_callback(encrypted_ix = "vote_quadratic")]
+    pub fn vote_quadratic_callback(
+        ctx: Context<VoteQuadraticCallback>,
+        output: SignedComputationOutputs<VoteQuadraticOutput>,
+    ) -> Result<()> {
+        handlers::vote_quadratic::vote_quadratic_callback(ctx, output)
+    }
+
+    pub fn get_program_stats(ctx: Context<GetProgramStats>) -> Result<ProgramStats> {
+        handlers::get_program_stats::get_program_stats(ctx)
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn debug_layout(ctx: Context<DebugLayout>, id: u32) -> Result<PollLayout> {
+        handlers::debug_layout::debug_layout(ctx, id)
+    }
+
+    pub fn close_poll(ctx: Context<ClosePoll>, id: u32) -> Result<()> {
+        handlers::close_poll::close_poll(ctx, id)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, id: u32) -> Result<()> {
+        handlers::withdraw_fees::withdraw_fees(ctx, id)
+    }
+
+    pub fn reset_tally(
+        ctx: Context<ResetTally>,
+        computation_offset: u64,
+        id: u32,
+        nonce: u128,
+    ) -> Result<()> {
+        handlers::reset_tally::reset_tally(ctx, computation_offset, id, nonce)
+    }
+
+    pub fn init_reveal_with_interval_comp_def(
+        ctx: Context<InitRevealWithIntervalCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_with_interval::init_reveal_with_interval_comp_def(ctx)
+    }
+
+    pub fn reveal_with_interval(
+        ctx: Context<RevealWithInterval>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_with_interval::reveal_with_interval(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_with_interval")]
+    pub fn reveal_with_interval_callback(
+        ctx: Context<RevealWithIntervalCallback>,
+        output: SignedComputationOutputs<RevealWithIntervalOutput>,
+    ) -> Result<()> {
+        handlers::reveal_with_interval::reveal_with_interval_callback(ctx, output)
+    }
+
+    pub fn init_reveal_bool_comp_def(ctx: Context<InitRevealBoolCompDef>) -> Result<()> {
+        handlers::reveal_bool::init_reveal_bool_comp_def(ctx)
+    }
+
+    pub fn reveal_bool(
+        ctx: Context<RevealBool>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_bool::reveal_bool(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_bool")]
+    pub fn reveal_bool_callback(
+        ctx: Context<RevealBoolCallback>,
+        output: SignedComputationOutputs<RevealBoolOutput>,
+    ) -> Result<()> {
+        handlers::reveal_bool::reveal_bool_callback(ctx, output)
+    }
+
+    pub fn init_reveal_full_ranking_comp_def(
+        ctx: Context<InitRevealFullRankingCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_full_ranking::init_reveal_full_ranking_comp_def(ctx)
+    }
+
+    pub fn reveal_full_ranking(
+        ctx: Context<RevealFullRanking>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_full_ranking::reveal_full_ranking(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_full_ranking")]
+    pub fn reveal_full_ranking_callback(
+        ctx: Context<RevealFullRankingCallback>,
+        output: SignedComputationOutputs<RevealFullRankingOutput>,
+    ) -> Result<()> {
+        handlers::reveal_full_ranking::reveal_full_ranking_callback(ctx, output)
+    }
+
+    pub fn init_rotate_nonce_comp_def(
+        ctx: Context<InitRotateNonceCompDef>,
+    ) -> Result<()> {
+        handlers::rotate_nonce::init_rotate_nonce_comp_def(ctx)
+    }
+
+    pub fn rotate_nonce(
+        ctx: Context<RotateNonce>,
+        computation_offset: u64,
+        id: u32,
+        nonce: u128,
+    ) -> Result<()> {
+        handlers::rotate_nonce::rotate_nonce(ctx, computation_offset, id, nonce)
+    }
+
+    #[arcium_callback(encrypted_ix = "rotate_nonce")]
+    pub fn rotate_nonce_callback(
+        ctx: Context<RotateNonceCallback>,
+        output: SignedComputationOutputs<RotateNonceOutput>,
+    ) -> Result<()> {
+        handlers::rotate_nonce::rotate_nonce_callback(ctx, output)
+    }
+
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        poll_id: u32,
+        choice: EncChoice,
+        vote_encryption_pubkey: EncPubkey,
+        vote_nonce: u128,
+        scheme: EncScheme,
+    ) -> Result<()> {
+        handlers::commit_vote::commit_vote(
+            ctx,
+            poll_id,
+            choice,
+            vote_encryption_pubkey,
+            vote_nonce,
+            scheme,
+        )
+    }
+
+    pub fn finalize_vote(
+        ctx: Context<FinalizeVote>,
+        computation_offset: u64,
+        poll_id: u32,
+    ) -> Result<()> {
+        handlers::commit_vote::finalize_vote(ctx, computation_offset, poll_id)
+    }
+
+    pub fn create_poll_template(
+        ctx: Context<CreatePollTemplate>,
+        template_id: u32,
+        option_labels: Vec<String>,
+        duration_secs: i64,
+        settings: PollSettings,
+    ) -> Result<()> {
+        handlers::poll_template::create_poll_template(
+            ctx,
+            template_id,
+            option_labels,
+            duration_secs,
+            settings,
+        )
+    }
+
+    pub fn create_poll_from_template(
+        ctx: Context<CreatePollFromTemplate>,
+        computation_offset: u64,
+        id: u32,
+        template_id: u32,
+        question: String,
+        nonce: u128,
+        settings: Option<PollSettings>,
+    ) -> Result<()> {
+        handlers::poll_template::create_poll_from_template(
+            ctx,
+            computation_offset,
+            id,
+            template_id,
+            question,
+            nonce,
+            settings,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_poll_relayed(
+        ctx: Context<CreatePollRelayed>,
+        computation_offset: u64,
+        id: u32,
+        authority: Pubkey,
+        question: String,
+        num_options: u8,
+        nonce: u128,
+        settings: PollSettings,
+        relay_nonce: u64,
+    ) -> Result<()> {
+        handlers::create_poll_relayed::create_poll_relayed(
+            ctx,
+            computation_offset,
+            id,
+            authority,
+            question,
+            num_options,
+            nonce,
+            settings,
+            relay_nonce,
+        )
+    }
+
+    pub fn init_reveal_sample_comp_def(ctx: Context<InitRevealSampleCompDef>) -> Result<()> {
+        handlers::reveal_sample::init_reveal_sample_comp_def(ctx)
+    }
+
+    pub fn reveal_sample(
+        ctx: Context<RevealSample>,
+        computation_offset: u64,
+        id: u32,
+        sample_size: u8,
+    ) -> Result<()> {
+        handlers::reveal_sample::reveal_sample(ctx, computation_offset, id, sample_size)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_sample")]
+    pub fn reveal_sample_callback(
+        ctx: Context<RevealSampleCallback>,
+        output: SignedComputationOutputs<RevealSampleOutput>,
+    ) -> Result<()> {
+        handlers::reveal_sample::reveal_sample_callback(ctx, output)
+    }
+
+    pub fn init_vote_many_comp_def(ctx: Context<InitVoteManyCompDef>) -> Result<()> {
+        handlers::vote_many::init_vote_many_comp_def(ctx)
+    }
+
+    pub fn vote_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VoteMany<'info>>,
+        computation_offset: u64,
+        poll_id: u32,
+    ) -> Result<()> {
+        handlers::vote_many::vote_many(ctx, computation_offset, poll_id)
+    }
+
+    #[arcium_callback(encrypted_ix = "vote_many")]
+    pub fn vote_many_callback(
+        ctx: Context<VoteManyCallback>,
+        output: SignedComputationOutputs<VoteManyOutput>,
+    ) -> Result<()> {
+        handlers::vote_many::vote_many_callback(ctx, output)
+    }
+
+    pub fn init_verify_vote_claim_comp_def(ctx: Context<InitVerifyVoteClaimCompDef>) -> Result<()> {
+        handlers::verify_vote_claim::init_verify_vote_claim_comp_def(ctx)
+    }
+
+    pub fn verify_vote_claim(
+        ctx: Context<VerifyVoteClaim>,
+        computation_offset: u64,
+        poll_id: u32,
+        claimed_choice: u8,
+    ) -> Result<()> {
+        handlers::verify_vote_claim::verify_vote_claim(
+            ctx,
+            computation_offset,
+            poll_id,
+            claimed_choice,
+        )
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_vote_claim")]
+    pub fn verify_vote_claim_callback(
+        ctx: Context<VerifyVoteClaimCallback>,
+        output: SignedComputationOutputs<VerifyVoteClaimOutput>,
+    ) -> Result<()> {
+        handlers::verify_vote_claim::verify_vote_claim_callback(ctx, output)
+    }
+
+    pub fn init_reveal_comment_comp_def(ctx: Context<InitRevealCommentCompDef>) -> Result<()> {
+        handlers::reveal_comment::init_reveal_comment_comp_def(ctx)
+    }
+
+    pub fn reveal_comment(
+        ctx: Context<RevealComment>,
+        computation_offset: u64,
+        id: u32,
+        voter: Pubkey,
+        recipient: EncPubkey,
+        recipient_nonce: u128,
+    ) -> Result<()> {
+        handlers::reveal_comment::reveal_comment(
+            ctx,
+            computation_offset,
+            id,
+            voter,
+            recipient,
+            recipient_nonce,
+        )
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_comment")]
+    pub fn reveal_comment_callback(
+        ctx: Context<RevealCommentCallback>,
+        output: SignedComputationOutputs<RevealCommentOutput>,
+    ) -> Result<()> {
+        handlers::reveal_comment::reveal_comment_callback(ctx, output)
+    }
+
+    pub fn init_reveal_delta_comp_def(
+        ctx: Context<InitRevealDeltaCompDef>,
+    ) -> Result<()> {
+        handlers::reveal_delta::init_reveal_delta_comp_def(ctx)
+    }
+
+    pub fn reveal_delta(
+        ctx: Context<RevealDelta>,
+        computation_offset: u64,
+        id: u32,
+    ) -> Result<()> {
+        handlers::reveal_delta::reveal_delta(ctx, computation_offset, id)
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_delta")]
+    pub fn reveal_delta_callback(
+        ctx: Context<RevealDeltaCallback>,
+        output: SignedComputationOutputs<RevealDeltaOutput>,
+    ) -> Result<()> {
+        handlers::reveal_delta::reveal_delta_callback(ctx, output)
+    }
+
+    pub fn release_tally(ctx: Context<ReleaseTally>, id: u32) -> Result<()> {
+        handlers::release_tally::release_tally(ctx, id)
+    }
+
+    // Account struct definitions - these need to be inside the arcium_program module
+    // so they can access the generated SignerAccount type
+
+    #[init_computation_definition_accounts("create_poll", payer)]
+    #[derive(Accounts)]
+    pub struct CreatePollCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[callback_accounts("create_poll")]
+    #[derive(Accounts)]
+    pub struct CreatePollCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        /// CHECK: poll_account, checked by the callback account key passed in queue_computation
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    /// Accounts for `create_poll`. CPI callers pass them in this order.
+    ///
+    /// `payer` becomes the poll's authority and is part of the poll's seeds. A program creating
+    /// polls signs as one of its own PDAs with `invoke_signed`. That PDA must be a system account
+    /// (no data) holding enough lamports for the poll's rent and the computation fee.
+    #[queue_computation_accounts("create_poll", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct CreatePoll<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", payer.key().as_ref()],
+            bump,
+        )]
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", payer.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct UpdateQuestion<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct ResizePoll<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[init_computation_definition_accounts("vote", payer)]
+    #[derive(Accounts)]
+    pub struct InitVoteCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("vote", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, poll_id: u32)]
+    pub struct Vote<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE)
+        )]
+        pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Box<Account<'info, Cluster>>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey
+
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        /// Already exists if the voter voted before the poll's tally was reset
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + VoterRecord::INIT_SPACE,
+            seeds = [b"voter", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump,
+        )]
+        pub voter_record: Account<'info, VoterRecord>,
+
+        /// CHECK: voter_list, only read when the poll has `use_voter_list` set, so it may not exist.
+        /// Deserialized in the handler when it's needed.
+        #[account(
+            seeds = [b"voter_list", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub voter_list: UncheckedAccount<'info>,
+
+        /// The voter's account for the poll's `weight_mint`, only needed in token-weighted polls
+        pub weight_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        /// The poll's fee vault, only needed in polls with a `vote_fee`. Created by the first vote.
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + FeeVault::INIT_SPACE,
+            seeds = [b"fee_vault", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub fee_vault: Option<Box<Account<'info, FeeVault>>>,
+
+        /// The voter's comment account, only needed for votes with a comment
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + VoteComment::INIT_SPACE,
+            seeds = [b"comment", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump,
+        )]
+        pub vote_comment: Option<Box<Account<'info, VoteComment>>>,
+    }
+
+    #[callback_accounts("vote")]
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct VoteCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(mut)]
+        pub voter_record: Account<'info, VoterRecord>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[init_computation_definition_accounts("reveal_result", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealResultCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_result", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealResult<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey. May sign, for the authority to reveal without paying,
+        /// e.g. a PDA signing through CPI.
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        /// Written by the callback with a digest of the revealed result
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + ResultAttestation::INIT_SPACE,
+            seeds = [b"result_attestation", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub result_attestation: Box<Account<'info, ResultAttestation>>,
+
+        /// Written by the callback with the revealed result, if passed. Left out, the reveal
+        /// only updates the poll and emits its event.
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + ResultsAccount::INIT_SPACE,
+            seeds = [b"results", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub results_account: Option<Box<Account<'info, ResultsAccount>>>,
+    }
+
+    #[callback_accounts("reveal_result")]
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct RevealResultCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        /// CHECK: poll_account, checked by the callback account key passed in queue_computation
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            mut,
+            seeds = [b"result_attestation", poll_account.key().as_ref()],
+            bump = result_attestation.bump,
+        )]
+        pub result_attestation: Account<'info, ResultAttestation>,
+
+        #[account(
+            mut,
+            seeds = [b"results", poll_account.key().as_ref()],
+            bump = results_account.bump,
+        )]
+        pub results_account: Option<Account<'info, ResultsAccount>>,
+    }
+    #[init_computation_definition_accounts("reveal_weight_quorum", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealWeightQuorumCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_weight_quorum", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealWeightQuorum<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WEIGHT_QUORUM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_weight_quorum")]
+    #[derive(Accounts)]
+    pub struct RevealWeightQuorumCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WEIGHT_QUORUM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(poll_id: u32)]
+    pub struct GetVoterRecord<'info> {
+        pub voter: Signer<'info>,
+
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        /// CHECK: voter_record, doesn't exist until the voter votes. Deserialized in the handler when it does.
+        #[account(
+            seeds = [b"voter", poll_account.key().as_ref(), voter.key().as_ref()],
+            bump,
+        )]
+        pub voter_record: UncheckedAccount<'info>,
+    }
+
+    /// The polls to aggregate are passed as remaining accounts
+    #[derive(Accounts)]
+    pub struct AggregatePolls {}
+
+    /// The voter records to check are passed as remaining accounts
+    #[derive(Accounts)]
+    pub struct BatchCheckVoted {}
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct ProveTurnout<'info> {
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct GetResultPacked<'info> {
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    #[init_computation_definition_accounts("aggregate_child", payer)]
+    #[derive(Accounts)]
+    pub struct InitAggregateChildCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("aggregate_child", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, parent_id: u32)]
+    pub struct AggregateChild<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_AGGREGATE_CHILD)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), parent_id.to_le_bytes().as_ref()],
+            bump = parent_poll.bump,
+            constraint = parent_poll.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = !parent_poll.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub parent_poll: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(mut)]
+        pub child_poll: Box<Account<'info, Poll>>,
+    }
+
+    #[callback_accounts("aggregate_child")]
+    #[derive(Accounts)]
+    pub struct AggregateChildCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_AGGREGATE_CHILD)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        /// CHECK: parent_poll, checked by the callback account key passed in queue_computation
+        #[account(mut)]
+        pub parent_poll: Box<Account<'info, Poll>>,
+
+        /// CHECK: child_poll, checked by the callback account key passed in queue_computation
+        pub child_poll: Box<Account<'info, Poll>>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct AddVoters<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + VoterList::INIT_SPACE,
+            seeds = [b"voter_list", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub voter_list: Account<'info, VoterList>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[init_computation_definition_accounts("reveal_decided", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealDecidedCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_decided", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealDecided<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DECIDED)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_decided")]
+    #[derive(Accounts)]
+    pub struct RevealDecidedCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DECIDED)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[derive(Accounts)]
+    pub struct InitializeConfig<'info> {
+        #[account(mut)]
+        pub admin: Signer<'info>,
+
+        #[account(
+            init,
+            payer = admin,
+            space = 8 + ProgramConfig::INIT_SPACE,
+            seeds = [b"config"],
+            bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+        pub program: Program<'info, crate::program::Election>,
+
+        /// The program's upgrade state, whose upgrade authority must be the admin, so only
+        /// whoever deployed the program can claim the config
+        #[account(
+            constraint = program_data.upgrade_authority_address == Some(admin.key())
+                @ ErrorCode::InvalidAuthority
+        )]
+        pub program_data: Account<'info, ProgramData>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    pub struct SetPaused<'info> {
+        pub admin: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            has_one = admin @ ErrorCode::InvalidAuthority
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[derive(Accounts)]
+    pub struct UpdateAdmin<'info> {
+        pub admin: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            has_one = admin @ ErrorCode::InvalidAuthority
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[init_computation_definition_accounts("reveal_top_k", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealTopKCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_top_k", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealTopK<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOP_K)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_top_k")]
+    #[derive(Accounts)]
+    pub struct RevealTopKCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TOP_K)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct SnapshotTally<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + TallyHistory::INIT_SPACE,
+            seeds = [b"tally_history", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub tally_history: Account<'info, TallyHistory>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct FinalizePoll<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[init_computation_definition_accounts("reveal_tally_shared", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealTallySharedCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_tally_shared", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealTallyShared<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TALLY_SHARED)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_tally_shared")]
+    #[derive(Accounts)]
+    pub struct RevealTallySharedCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TALLY_SHARED)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    #[derive(Accounts)]
+    pub struct SetRevealCooldown<'info> {
+        pub admin: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            has_one = admin @ ErrorCode::InvalidAuthority
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[derive(Accounts)]
+    pub struct SetMaxActivePolls<'info> {
+        pub admin: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            has_one = admin @ ErrorCode::InvalidAuthority
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct PreallocatePoll<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("create_poll", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct ActivatePoll<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", payer.key().as_ref()],
+            bump,
+        )]
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", payer.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
+    }
+
+    #[derive(Accounts)]
+    pub struct SetWormholeProgram<'info> {
+        pub admin: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            has_one = admin @ ErrorCode::InvalidAuthority
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct PostResultCrossChain<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        /// CHECK: wormhole_program, checked against the program config
+        #[account(address = config.wormhole_program)]
+        pub wormhole_program: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: wormhole_bridge, checked by the Wormhole program
+        pub wormhole_bridge: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"wormhole_message", poll_account.key().as_ref()],
+            bump,
+        )]
+        /// CHECK: wormhole_message, created by the Wormhole program
+        pub wormhole_message: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"emitter"],
+            bump,
+        )]
+        /// CHECK: wormhole_emitter, this program's emitter PDA, only used as a signer
+        pub wormhole_emitter: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: wormhole_sequence, checked by the Wormhole program
+        pub wormhole_sequence: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: wormhole_fee_collector, checked by the Wormhole program
+        pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+        pub clock: Sysvar<'info, Clock>,
+
+        pub rent: Sysvar<'info, Rent>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(source_id: u32, new_id: u32)]
+    pub struct ClonePoll<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), source_id.to_le_bytes().as_ref()],
+            bump = source_poll.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority
+        )]
+        pub source_poll: Box<Account<'info, Poll>>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", authority.key().as_ref(), new_id.to_le_bytes().as_ref()],
+            bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", authority.key().as_ref()],
+            bump,
+        )]
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", authority.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct FreezePoll<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[init_computation_definition_accounts("reveal_referendum", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealReferendumCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_referendum", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealReferendum<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_REFERENDUM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_referendum")]
+    #[derive(Accounts)]
+    pub struct RevealReferendumCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_REFERENDUM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("reveal_to", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealToCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_to", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32, recipient: EncPubkey)]
+    pub struct RevealTo<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TO)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey. May sign, for the authority to reveal without paying,
+        /// e.g. a PDA signing through CPI.
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + SealedResult::INIT_SPACE,
+            seeds = [b"sealed_result", poll_account.key().as_ref(), recipient.as_ref()],
+            bump,
+        )]
+        pub sealed_result: Account<'info, SealedResult>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_to")]
+    #[derive(Accounts)]
+    pub struct RevealToCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_TO)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(mut)]
+        pub sealed_result: Account<'info, SealedResult>,
+    }
+
+    #[queue_computation_accounts("create_poll", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct CreatePollIfNeeded<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", payer.key().as_ref()],
+            bump,
+        )]
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", payer.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
+    }
+
+    #[init_computation_definition_accounts("reveal_spread", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealSpreadCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_spread", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealSpread<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SPREAD)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_spread")]
+    #[derive(Accounts)]
+    pub struct RevealSpreadCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SPREAD)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("vote_quadratic", payer)]
+    #[derive(Accounts)]
+    pub struct InitVoteQuadraticCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("vote_quadratic", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, poll_id: u32)]
+    pub struct VoteQuadratic<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_QUADRATIC)
+        )]
+        pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Box<Account<'info, Cluster>>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey
+
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        /// Already exists if the voter voted before the poll's tally was reset
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + VoterRecord::INIT_SPACE,
+            seeds = [b"voter", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump,
+        )]
+        pub voter_record: Account<'info, VoterRecord>,
+
+        /// CHECK: voter_list, only read when the poll has `use_voter_list` set, so it may not exist.
+        /// Deserialized in the handler when it's needed.
+        #[account(
+            seeds = [b"voter_list", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub voter_list: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        /// The poll's fee vault, only needed in polls with a `vote_fee`. Created by the first vote.
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + FeeVault::INIT_SPACE,
+            seeds = [b"fee_vault", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub fee_vault: Option<Box<Account<'info, FeeVault>>>,
+    }
+
+    #[callback_accounts("vote_quadratic")]
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct VoteQuadraticCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_QUADRATIC)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(mut)]
+        pub voter_record: Account<'info, VoterRecord>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[derive(Accounts)]
+    pub struct GetProgramStats<'info> {
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[cfg(feature = "debug")]
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct DebugLayout<'info> {
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct ClosePoll<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            close = authority,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", authority.key().as_ref()],
+            bump,
+        )]
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", authority.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u32)]
+    pub struct WithdrawFees<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            mut,
+            seeds = [b"fee_vault", poll_account.key().as_ref()],
+            bump = fee_vault.bump,
+        )]
+        pub fee_vault: Account<'info, FeeVault>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("create_poll", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct ResetTally<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        /// Resets are only allowed while the whole program is paused
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = config.paused @ ErrorCode::ProgramNotPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[init_computation_definition_accounts("reveal_with_interval", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealWithIntervalCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_with_interval", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealWithInterval<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WITH_INTERVAL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_with_interval")]
+    #[derive(Accounts)]
+    pub struct RevealWithIntervalCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_WITH_INTERVAL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("reveal_bool", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealBoolCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_bool", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealBool<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_BOOL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_bool")]
+    #[derive(Accounts)]
+    pub struct RevealBoolCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_BOOL)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("reveal_full_ranking", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealFullRankingCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_full_ranking", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealFullRanking<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_FULL_RANKING)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_full_ranking")]
+    #[derive(Accounts)]
+    pub struct RevealFullRankingCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_FULL_RANKING)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("rotate_nonce", payer)]
+    #[derive(Accounts)]
+    pub struct InitRotateNonceCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("rotate_nonce", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RotateNonce<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_NONCE)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("rotate_nonce")]
+    #[derive(Accounts)]
+    pub struct RotateNonceCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_NONCE)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+    }
+
+    #[init_computation_definition_accounts("reveal_sample", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealSampleCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_sample", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealSample<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SAMPLE)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+        /// CHECK: recent_slothashes, checked by the account constraint
+        pub recent_slothashes: AccountInfo<'info>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_sample")]
+    #[derive(Accounts)]
+    pub struct RevealSampleCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SAMPLE)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+    }
+
+    #[init_computation_definition_accounts("vote_many", payer)]
+    #[derive(Accounts)]
+    pub struct InitVoteManyCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("vote_many", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, poll_id: u32)]
+    pub struct VoteMany<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_MANY)
+        )]
+        pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Box<Account<'info, Cluster>>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey
+
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[callback_accounts("vote_many")]
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct VoteManyCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE_MANY)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(mut)]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
+
+    #[init_computation_definition_accounts("verify_vote_claim", payer)]
+    #[derive(Accounts)]
+    pub struct InitVerifyVoteClaimCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("verify_vote_claim", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, poll_id: u32)]
+    pub struct VerifyVoteClaim<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_CLAIM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        /// CHECK: Poll authority pubkey
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            seeds = [b"voter", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump = voter_record.bump,
+        )]
+        pub voter_record: Account<'info, VoterRecord>,
+    }
+
+    #[callback_accounts("verify_vote_claim")]
+    #[derive(Accounts)]
+    pub struct VerifyVoteClaimCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_VOTE_CLAIM)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        pub voter_record: Account<'info, VoterRecord>,
+    }
+
+    #[init_computation_definition_accounts("reveal_comment", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealCommentCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_comment", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32, voter: Pubkey)]
+    pub struct RevealComment<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMENT)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            seeds = [b"comment", poll_account.key().as_ref(), voter.as_ref()],
+            bump = vote_comment.bump,
+        )]
+        pub vote_comment: Account<'info, VoteComment>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+    }
+
+    #[callback_accounts("reveal_comment")]
+    #[derive(Accounts)]
+    pub struct RevealCommentCallback<'info> {
+        pub arcium_program: Program<'info, Arcium>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COMMENT)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+        /// CHECK: instructions_sysvar, checked by the account constraint
+        pub instructions_sysvar: AccountInfo<'info>,
+
+        #[account(address = derive_mxe_pda!())]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        /// CHECK: computation_account, passed to verify_output for BLS signature verification
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+        pub cluster_account: Account<'info, Cluster>,
+
+        pub vote_comment: Account<'info, VoteComment>,
+    }
+
+    #[init_computation_definition_accounts("reveal_delta", payer)]
+    #[derive(Accounts)]
+    pub struct InitRevealDeltaCompDef<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            mut,
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
+
+        #[account(mut)]
+        /// CHECK: comp_def_account, checked by arcium program.
+        /// Can't check it here as it's not initialized yet.
+        pub comp_def_account: UncheckedAccount<'info>,
+
+        #[account(mut)]
+        /// CHECK: address_lookup_table, checked by arcium program
+        pub address_lookup_table: UncheckedAccount<'info>,
+
+        /// CHECK: lut_program, checked by arcium program
+        pub lut_program: UncheckedAccount<'info>,
+
+        pub arcium_program: Program<'info, Arcium>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[queue_computation_accounts("reveal_delta", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, id: u32)]
+    pub struct RevealDelta<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        #[account(
+            init_if_needed,
+            space = 9,
+            payer = payer,
+            seeds = [&SIGN_PDA_SEED],
+            bump,
+            address = derive_sign_pda!(),
+        )]
+        pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+
+        #[account(
+            address = derive_mxe_pda!()
+        )]
+        pub mxe_account: Account<'info, MXEAccount>,
+
+        #[account(
+            mut,
+            address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: mempool_account, checked by the arcium program
+        pub mempool_account: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: executing_pool, checked by the arcium program
+        pub executing_pool: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        /// CHECK: computation_account, checked by the arcium program.
+        pub computation_account: UncheckedAccount<'info>,
+
+        #[account(
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DELTA)
+        )]
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+        #[account(
+            mut,
+            address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
+        )]
+        pub cluster_account: Account<'info, Cluster>,
+
+        #[account(
+            mut,
+            address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+        )]
+        pub pool_account: Account<'info, FeePool>,
+
+        #[account(
+            mut,
+            address = ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+        )]
+        pub clock_account: Account<'info, ClockAccount>,
+
+        pub system_program: Program<'info, System>,
 
         pub arcium_program: Program<'info, Arcium>,
 
-        pub system_program: Program<'info, System>,
+        #[account(
+            mut,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
     }
 
-    #[callback_accounts("create_poll")]
+    #[callback_accounts("reveal_delta")]
     #[derive(Accounts)]
-    pub struct CreatePollCallback<'info> {
+    pub struct RevealDeltaCallback<'info> {
         pub arcium_program: Program<'info, Arcium>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_DELTA)
         )]
         pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
 
@@ -151,15 +5021,113 @@ pub mod election {
         #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
         pub cluster_account: Account<'info, Cluster>,
 
-        /// CHECK: poll_account, checked by the callback account key passed in queue_computation
         #[account(mut)]
-        pub poll_account: Account<'info, Poll>,
+        pub poll_account: Box<Account<'info, Poll>>,
     }
 
-    #[queue_computation_accounts("create_poll", payer)]
     #[derive(Accounts)]
-    #[instruction(computation_offset: u64, id: u32)]
-    pub struct CreatePoll<'info> {
+    #[instruction(id: u32)]
+    pub struct ReleaseTally<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority @ ErrorCode::InvalidAuthority
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        #[account(
+            init_if_needed,
+            payer = authority,
+            space = 8 + AuthorityLog::INIT_SPACE,
+            seeds = [b"authority_log", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub authority_log: Box<Account<'info, AuthorityLog>>,
+
+        pub system_program: Program<'info, System>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(poll_id: u32)]
+    pub struct CommitVote<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+
+        pub system_program: Program<'info, System>,
+
+        /// CHECK: Poll authority pubkey
+
+        #[account(
+            address = poll_account.authority,
+        )]
+        pub authority: UncheckedAccount<'info>,
+
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
+
+        /// Already exists if the voter voted before the poll's tally was reset
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + VoterRecord::INIT_SPACE,
+            seeds = [b"voter", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump,
+        )]
+        pub voter_record: Account<'info, VoterRecord>,
+
+        /// CHECK: voter_list, only read when the poll has `use_voter_list` set, so it may not exist.
+        /// Deserialized in the handler when it's needed.
+        #[account(
+            seeds = [b"voter_list", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub voter_list: UncheckedAccount<'info>,
+
+        /// The voter's account for the poll's `weight_mint`, only needed in token-weighted polls
+        pub weight_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+
+        /// The poll's fee vault, only needed in polls with a `vote_fee`. Created by the first vote.
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + FeeVault::INIT_SPACE,
+            seeds = [b"fee_vault", poll_account.key().as_ref()],
+            bump,
+        )]
+        pub fee_vault: Option<Box<Account<'info, FeeVault>>>,
+    }
+
+    #[queue_computation_accounts("vote", payer)]
+    #[derive(Accounts)]
+    #[instruction(computation_offset: u64, poll_id: u32)]
+    pub struct FinalizeVote<'info> {
         #[account(mut)]
         pub payer: Signer<'info>,
 
@@ -176,7 +5144,7 @@ pub mod election {
         #[account(
             address = derive_mxe_pda!()
         )]
-        pub mxe_account: Account<'info, MXEAccount>,
+        pub mxe_account: Box<Account<'info, MXEAccount>>,
 
         #[account(
             mut,
@@ -200,15 +5168,15 @@ pub mod election {
         pub computation_account: UncheckedAccount<'info>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE)
         )]
-        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+        pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
 
         #[account(
             mut,
             address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
         )]
-        pub cluster_account: Account<'info, Cluster>,
+        pub cluster_account: Box<Account<'info, Cluster>>,
 
         #[account(
             mut,
@@ -226,49 +5194,71 @@ pub mod election {
 
         pub arcium_program: Program<'info, Arcium>,
 
+        /// CHECK: Poll authority pubkey
+
         #[account(
-            init,
-            payer = payer,
-            space = 8 + Poll::INIT_SPACE,
-            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
-            bump,
+            address = poll_account.authority,
         )]
-        pub poll_account: Account<'info, Poll>,
-    }
+        pub authority: UncheckedAccount<'info>,
 
-    #[init_computation_definition_accounts("vote", payer)]
-    #[derive(Accounts)]
-    pub struct InitVoteCompDef<'info> {
-        #[account(mut)]
-        pub payer: Signer<'info>,
+        #[account(
+            mut,
+            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
+            bump = poll_account.bump,
+            has_one = authority,
+            constraint = poll_account.status != PollStatus::Finalized @ ErrorCode::PollFinalized,
+            constraint = poll_account.status != PollStatus::Created @ ErrorCode::PollNotActivated,
+            constraint = poll_account.status.is_initialized() @ ErrorCode::PollNotInitialized,
+            constraint = !poll_account.status.is_frozen() @ ErrorCode::PollFrozen
+        )]
+        pub poll_account: Box<Account<'info, Poll>>,
 
         #[account(
             mut,
-            address = derive_mxe_pda!()
+            seeds = [b"voter", poll_account.key().as_ref(), payer.key().as_ref()],
+            bump = voter_record.bump,
+            constraint = voter_record.committed
+                && voter_record.epoch == poll_account.tally_epoch @ ErrorCode::NoCommittedVote,
         )]
-        pub mxe_account: Box<Account<'info, MXEAccount>>,
+        pub voter_record: Account<'info, VoterRecord>,
 
-        #[account(mut)]
-        /// CHECK: comp_def_account, checked by arcium program.
-        /// Can't check it here as it's not initialized yet.
-        pub comp_def_account: UncheckedAccount<'info>,
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
+    }
 
+    #[derive(Accounts)]
+    #[instruction(template_id: u32)]
+    pub struct CreatePollTemplate<'info> {
         #[account(mut)]
-        /// CHECK: address_lookup_table, checked by arcium program
-        pub address_lookup_table: UncheckedAccount<'info>,
-
-        /// CHECK: lut_program, checked by arcium program
-        pub lut_program: UncheckedAccount<'info>,
+        pub authority: Signer<'info>,
 
-        pub arcium_program: Program<'info, Arcium>,
+        #[account(
+            init,
+            payer = authority,
+            space = 8 + PollTemplate::INIT_SPACE,
+            seeds = [b"poll_template", authority.key().as_ref(), template_id.to_le_bytes().as_ref()],
+            bump,
+        )]
+        pub template: Account<'info, PollTemplate>,
 
         pub system_program: Program<'info, System>,
+
+        #[account(
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
     }
 
-    #[queue_computation_accounts("vote", payer)]
+    #[queue_computation_accounts("create_poll", payer)]
     #[derive(Accounts)]
-    #[instruction(computation_offset: u64, poll_id: u32)]
-    pub struct Vote<'info> {
+    #[instruction(computation_offset: u64, id: u32, template_id: u32)]
+    pub struct CreatePollFromTemplate<'info> {
         #[account(mut)]
         pub payer: Signer<'info>,
 
@@ -285,7 +5275,7 @@ pub mod election {
         #[account(
             address = derive_mxe_pda!()
         )]
-        pub mxe_account: Box<Account<'info, MXEAccount>>,
+        pub mxe_account: Account<'info, MXEAccount>,
 
         #[account(
             mut,
@@ -309,15 +5299,15 @@ pub mod election {
         pub computation_account: UncheckedAccount<'info>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE)
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
         )]
-        pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
 
         #[account(
             mut,
             address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet)
         )]
-        pub cluster_account: Box<Account<'info, Cluster>>,
+        pub cluster_account: Account<'info, Cluster>,
 
         #[account(
             mut,
@@ -335,81 +5325,52 @@ pub mod election {
 
         pub arcium_program: Program<'info, Arcium>,
 
-        /// CHECK: Poll authority pubkey
-
         #[account(
-            address = poll_account.authority,
+            seeds = [b"poll_template", payer.key().as_ref(), template_id.to_le_bytes().as_ref()],
+            bump = template.bump,
         )]
-        pub authority: UncheckedAccount<'info>,
+        pub template: Box<Account<'info, PollTemplate>>,
 
         #[account(
-            seeds = [b"poll", authority.key().as_ref(), poll_id.to_le_bytes().as_ref()],
-            bump = poll_account.bump,
-            has_one = authority
+            init_if_needed,
+            payer = payer,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
+            bump,
         )]
-        pub poll_account: Account<'info, Poll>,
-    }
-
-    #[callback_accounts("vote")]
-    #[derive(Accounts)]
-    pub struct VoteCallback<'info> {
-        pub arcium_program: Program<'info, Arcium>,
+        pub poll_account: Box<Account<'info, Poll>>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE)
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
         )]
-        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-
-        #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-        /// CHECK: instructions_sysvar, checked by the account constraint
-        pub instructions_sysvar: AccountInfo<'info>,
-
-        #[account(address = derive_mxe_pda!())]
-        pub mxe_account: Account<'info, MXEAccount>,
-
-        /// CHECK: computation_account, passed to verify_output for BLS signature verification
-        pub computation_account: UncheckedAccount<'info>,
-
-        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-        pub cluster_account: Account<'info, Cluster>,
-
-        #[account(mut)]
-        pub poll_account: Account<'info, Poll>,
-    }
-
-    #[init_computation_definition_accounts("reveal_result", payer)]
-    #[derive(Accounts)]
-    pub struct InitRevealResultCompDef<'info> {
-        #[account(mut)]
-        pub payer: Signer<'info>,
+        pub config: Account<'info, ProgramConfig>,
 
         #[account(
-            mut,
-            address = derive_mxe_pda!()
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", payer.key().as_ref()],
+            bump,
         )]
-        pub mxe_account: Box<Account<'info, MXEAccount>>,
-
-        #[account(mut)]
-        /// CHECK: comp_def_account, checked by arcium program.
-        /// Can't check it here as it's not initialized yet.
-        pub comp_def_account: UncheckedAccount<'info>,
-
-        #[account(mut)]
-        /// CHECK: address_lookup_table, checked by arcium program
-        pub address_lookup_table: UncheckedAccount<'info>,
-
-        /// CHECK: lut_program, checked by arcium program
-        pub lut_program: UncheckedAccount<'info>,
-
-        pub arcium_program: Program<'info, Arcium>,
+        pub authority_state: Box<Account<'info, AuthorityState>>,
 
-        pub system_program: Program<'info, System>,
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", payer.key().as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
     }
 
-    #[queue_computation_accounts("reveal_result", payer)]
+    #[queue_computation_accounts("create_poll", payer)]
     #[derive(Accounts)]
-    #[instruction(computation_offset: u64, id: u32)]
-    pub struct RevealResult<'info> {
+    #[instruction(computation_offset: u64, id: u32, authority: Pubkey)]
+    pub struct CreatePollRelayed<'info> {
         #[account(mut)]
         pub payer: Signer<'info>,
 
@@ -450,7 +5411,7 @@ pub mod election {
         pub computation_account: UncheckedAccount<'info>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL)
+            address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_POLL)
         )]
         pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
 
@@ -477,39 +5438,94 @@ pub mod election {
         pub arcium_program: Program<'info, Arcium>,
 
         #[account(
-            seeds = [b"poll", payer.key().as_ref(), id.to_le_bytes().as_ref()],
-            bump = poll_account.bump
+            init_if_needed,
+            payer = payer,
+            space = 8 + Poll::INIT_SPACE,
+            seeds = [b"poll", authority.as_ref(), id.to_le_bytes().as_ref()],
+            bump,
         )]
-        pub poll_account: Account<'info, Poll>,
-    }
+        pub poll_account: Box<Account<'info, Poll>>,
 
-    #[callback_accounts("reveal_result")]
-    #[derive(Accounts)]
-    pub struct RevealResultCallback<'info> {
-        pub arcium_program: Program<'info, Arcium>,
+        #[account(
+            mut,
+            seeds = [b"config"],
+            bump = config.bump,
+            constraint = !config.paused @ ErrorCode::ProgramPaused
+        )]
+        pub config: Account<'info, ProgramConfig>,
 
         #[account(
-            address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL)
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityState::INIT_SPACE,
+            seeds = [b"authority_state", authority.as_ref()],
+            bump,
         )]
-        pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+        pub authority_state: Box<Account<'info, AuthorityState>>,
+
+        #[account(
+            init_if_needed,
+            payer = payer,
+            space = 8 + AuthorityPollIndex::INIT_SPACE,
+            seeds = [b"poll_index", authority.as_ref()],
+            bump,
+        )]
+        pub poll_index: Box<Account<'info, AuthorityPollIndex>>,
 
         #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
         /// CHECK: instructions_sysvar, checked by the account constraint
         pub instructions_sysvar: AccountInfo<'info>,
-
-        #[account(address = derive_mxe_pda!())]
-        pub mxe_account: Account<'info, MXEAccount>,
-
-        /// CHECK: computation_account, passed to verify_output for BLS signature verification
-        pub computation_account: UncheckedAccount<'info>,
-
-        #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-        pub cluster_account: Account<'info, Cluster>,
     }
+
 }
 
 pub use crate::election::{
-    CreatePoll, CreatePollCallback,
+    CreatePoll, CreatePollCallback, UpdateQuestion,
     InitVoteCompDef, Vote, VoteCallback,
     InitRevealResultCompDef, RevealResult, RevealResultCallback,
+    InitRevealWeightQuorumCompDef, RevealWeightQuorum, RevealWeightQuorumCallback,
+    GetVoterRecord,
+    AggregatePolls,
+    InitAggregateChildCompDef, AggregateChild, AggregateChildCallback,
+    AddVoters,
+    InitRevealDecidedCompDef, RevealDecided, RevealDecidedCallback,
+    InitializeConfig, SetPaused, UpdateAdmin,
+    InitRevealTopKCompDef, RevealTopK, RevealTopKCallback,
+    SnapshotTally,
+    FinalizePoll,
+    InitRevealTallySharedCompDef, RevealTallyShared, RevealTallySharedCallback,
+    SetRevealCooldown,
+    SetMaxActivePolls,
+    PreallocatePoll, ActivatePoll,
+    SetWormholeProgram, PostResultCrossChain,
+    ClonePoll,
+    FreezePoll,
+    InitRevealReferendumCompDef, RevealReferendum, RevealReferendumCallback,
+    InitRevealToCompDef, RevealTo, RevealToCallback,
+    CreatePollIfNeeded,
+    InitRevealSpreadCompDef, RevealSpread, RevealSpreadCallback,
+    InitVoteQuadraticCompDef, VoteQuadratic, VoteQuadraticCallback,
+    GetProgramStats,
+    ClosePoll,
+    ResetTally,
+    InitRevealWithIntervalCompDef, RevealWithInterval, RevealWithIntervalCallback,
+    InitRevealBoolCompDef, RevealBool, RevealBoolCallback,
+    WithdrawFees,
+    InitRevealFullRankingCompDef, RevealFullRanking, RevealFullRankingCallback,
+    InitRotateNonceCompDef, RotateNonce, RotateNonceCallback,
+    CommitVote, FinalizeVote,
+    CreatePollTemplate, CreatePollFromTemplate,
+    BatchCheckVoted,
+    ProveTurnout,
+    ResizePoll,
+    CreatePollRelayed,
+    GetResultPacked,
+    InitRevealSampleCompDef, RevealSample, RevealSampleCallback,
+    InitVoteManyCompDef, VoteMany, VoteManyCallback,
+    InitVerifyVoteClaimCompDef, VerifyVoteClaim, VerifyVoteClaimCallback,
+    InitRevealCommentCompDef, RevealComment, RevealCommentCallback,
+    InitRevealDeltaCompDef, RevealDelta, RevealDeltaCallback,
+    ReleaseTally,
 };
+#[cfg(feature = "debug")]
+pub use crate::election::DebugLayout;
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_AUTHORITY_LOG_ENTRIES;
+
+/// A privileged action taken on a poll.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AuthorityAction {
+    /// `freeze_poll` stopped the poll taking votes
+    Freeze,
+    /// `update_question` replaced the question
+    UpdateQuestion,
+    /// `add_voters` added wallets to the voter list
+    AddVoters,
+    /// One of the reveal instructions queued a reveal of the result or tally
+    Reveal,
+    /// `finalize_poll` locked the result
+    Finalize,
+    /// `withdraw_fees` sent the poll's collected vote fees to the authority
+    WithdrawFees,
+    /// `rotate_nonce` re-encrypted the tally under a fresh nonce
+    RotateNonce,
+    /// `release_tally` gave up on a computation holding the tally
+    ReleaseTally,
+}
+
+/// One privileged action, and who took it when.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AuthorityLogEntry {
+    /// What was done
+    pub action: AuthorityAction,
+    /// Who signed for it: the poll authority, or whoever revealed a poll that allows public reveals
+    pub actor: Pubkey,
+    /// Unix timestamp of the action
+    pub timestamp: i64,
+}
+
+/// An audit trail of the privileged actions taken on a poll, for contested elections.
+///
+/// Holds the last `MAX_AUTHORITY_LOG_ENTRIES` entries as a ring buffer, like `TallyHistory`.
+/// Entries are only ever added, never edited. The log is created by the first action.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityLog {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll the actions were taken on
+    pub poll: Pubkey,
+    /// Number of actions ever logged. The newest is at `(total_entries - 1) % MAX_AUTHORITY_LOG_ENTRIES`.
+    pub total_entries: u64,
+    #[max_len(MAX_AUTHORITY_LOG_ENTRIES)]
+    pub entries: Vec<AuthorityLogEntry>,
+}
+
+impl AuthorityLog {
+    /// Logs an action taken now, overwriting the oldest entry once the buffer is full.
+    /// Fills in the log's bump and poll too, in case this is its first entry.
+    pub fn record(
+        &mut self,
+        bump: u8,
+        poll: Pubkey,
+        actor: Pubkey,
+        action: AuthorityAction,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.poll = poll;
+
+        let entry = AuthorityLogEntry {
+            action,
+            actor,
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+        if self.entries.len() < MAX_AUTHORITY_LOG_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            let oldest = (self.total_entries % MAX_AUTHORITY_LOG_ENTRIES as u64) as usize;
+            self.entries[oldest] = entry;
+        }
+        self.total_entries += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_INDEXED_POLLS;
+
+/// The polls an authority has open, so a client can list an organizer's polls by reading one
+/// account instead of scanning the program's accounts.
+///
+/// Polls are added when they're opened (by any of the poll-creating instructions, or
+/// `activate_poll`) and removed by `close_poll`, in the order they were opened. The list holds
+/// at most `MAX_INDEXED_POLLS`: a poll opened while it's full isn't listed, and counts towards
+/// `unindexed_polls` instead. Polls opened before the index was kept aren't listed either.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityPollIndex {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll authority whose polls these are
+    pub authority: Pubkey,
+    /// Number of polls opened while the list was full, and so never listed. Never goes down,
+    /// so while it's above 0 the list may be missing polls; scan for them instead.
+    pub unindexed_polls: u32,
+    /// The authority's open polls, oldest first
+    #[max_len(MAX_INDEXED_POLLS)]
+    pub polls: Vec<Pubkey>,
+}
+
+impl AuthorityPollIndex {
+    /// Lists a newly opened poll, or counts it as unindexed if the list is full
+    pub fn add_poll(&mut self, bump: u8, authority: Pubkey, poll: Pubkey) {
+        self.bump = bump;
+        self.authority = authority;
+        if self.polls.len() < MAX_INDEXED_POLLS {
+            self.polls.push(poll);
+        } else {
+            self.unindexed_polls += 1;
+        }
+    }
+
+    /// Takes a closed poll off the list, if it's on it
+    pub fn remove_poll(&mut self, bump: u8, authority: Pubkey, poll: Pubkey) {
+        self.bump = bump;
+        self.authority = authority;
+        self.polls.retain(|listed| *listed != poll);
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Per-authority bookkeeping, stored in one PDA per poll authority.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityState {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll authority this belongs to
+    pub authority: Pubkey,
+    /// Number of the authority's polls that are open, i.e. opened and not yet closed with
+    /// `close_poll`. Polls opened before this was kept aren't counted.
+    pub active_poll_count: u32,
+    /// The `relay_nonce` the authority's next consent to `create_poll_relayed` must sign. Bumped
+    /// by each relayed poll, so a consent can only be used once.
+    pub relay_nonce: u64,
+}
+
+impl AuthorityState {
+    /// Counts a newly opened poll, failing if the authority already has the config's
+    /// `max_active_polls` open. 0 means there's no limit.
+    pub fn add_poll(&mut self, bump: u8, authority: Pubkey, max_active_polls: u32) -> Result<()> {
+        self.bump = bump;
+        self.authority = authority;
+        require!(
+            max_active_polls == 0 || self.active_poll_count < max_active_polls,
+            ErrorCode::TooManyActivePolls
+        );
+        self.active_poll_count += 1;
+        Ok(())
+    }
+
+    /// Stops counting a closed poll
+    pub fn remove_poll(&mut self, bump: u8, authority: Pubkey) {
+        self.bump = bump;
+        self.authority = authority;
+        // A poll opened before the count was kept was never added
+        self.active_poll_count = self.active_poll_count.saturating_sub(1);
+    }
+}
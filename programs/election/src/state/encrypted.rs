@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+// Each wrapper is a one-field tuple struct, which Borsh encodes exactly like the bare
+// `[u8; 32]`, so transactions are unchanged. They only stop one kind of 32 bytes being
+// passed where another is expected.
+
+/// An x25519 public key, used to encrypt values shared with the MXE
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EncPubkey(pub [u8; 32]);
+
+/// An encrypted choice: the index of the chosen option, as one Rescue ciphertext
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EncChoice(pub [u8; 32]);
+
+/// Any other encrypted value that fits in one Rescue ciphertext, like a quadratic vote's credits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext(pub [u8; 32]);
+
+/// How a client encrypted its vote: an x25519 key exchange with the MXE, then a cipher.
+/// Identified by its Borsh discriminant, 0 to 3 in order.
+///
+/// The vote circuits only decrypt `X25519Rescue`. A vote encrypted any other way would still
+/// be counted, as whatever option its ciphertext happens to decrypt to, so votes name their
+/// scheme and polls reject the ones they don't accept before queueing anything.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EncScheme {
+    /// Rescue in counter mode, as Arcium clients' `RescueCipher`
+    X25519Rescue,
+    /// AES-128 in counter mode, as Arcium clients' `Aes128Cipher`
+    X25519Aes128,
+    /// AES-192 in counter mode, as Arcium clients' `Aes192Cipher`
+    X25519Aes192,
+    /// AES-256 in counter mode, as Arcium clients' `Aes256Cipher`
+    X25519Aes256,
+}
+
+impl EncScheme {
+    /// The scheme's bit in `PollSettings::supported_schemes`
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl EncPubkey {
+    /// x25519 points of small order, with the top bit (ignored by x25519) cleared. A shared
+    /// secret with any of them is one of a few known values, so nothing encrypted with it is hidden.
+    const SMALL_ORDER_POINTS: [[u8; 32]; 7] = [
+        Self::le_bytes(0x00, 0x00, 0x00),
+        Self::le_bytes(0x01, 0x00, 0x00),
+        [
+            0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f,
+            0xc4, 0x6a, 0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16,
+            0x5f, 0x49, 0xb8, 0x00,
+        ],
+        [
+            0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83,
+            0xef, 0x5b, 0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd,
+            0xd0, 0x9f, 0x11, 0x57,
+        ],
+        // p - 1, p and p + 1, for p = 2^255 - 19
+        Self::le_bytes(0xec, 0xff, 0x7f),
+        Self::le_bytes(0xed, 0xff, 0x7f),
+        Self::le_bytes(0xee, 0xff, 0x7f),
+    ];
+
+    /// A little-endian 32-byte value: `low`, then 30 bytes of `fill`, then `high`
+    const fn le_bytes(low: u8, fill: u8, high: u8) -> [u8; 32] {
+        let mut bytes = [fill; 32];
+        bytes[0] = low;
+        bytes[31] = high;
+        bytes
+    }
+
+    /// Whether the key can be used to encrypt for the MXE: it isn't all zeroes, or any other
+    /// point of small order. Doesn't check the key is a point on the curve, as every u-coordinate
+    /// is valid x25519 input.
+    pub fn is_valid(&self) -> bool {
+        let mut key = self.0;
+        key[31] &= 0x7f;
+        !Self::SMALL_ORDER_POINTS.contains(&key)
+    }
+}
+
+impl AsRef<[u8]> for EncPubkey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
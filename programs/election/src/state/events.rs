@@ -1,12 +1,238 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{MAX_COMMENT_LENGTH, MAX_TOP_K},
+    state::Poll,
+};
+
 #[event]
+#[derive(Clone)]
 pub struct VoteEvent {
     pub timestamp: i64,
 }
 
 #[event]
+#[derive(Clone)]
 pub struct RevealResultEvent {
-    /// The winning option: 0 = Neo robot, 1 = Humane AI PIN, 2 = friend.com
+    /// The index of the winning option
     pub output: u8,
+    /// Whether several options tied for the most votes (the tie-break seed picked `output`)
+    pub is_tie: bool,
+    /// Number of votes whose choice wasn't one of the poll's options
+    pub spoiled: u64,
+    /// How many votes the winner got, if the poll opted in with `reveal_winner_count`
+    pub winner_count: Option<u64>,
+    /// Which options have reached the poll's `option_quota`, bit `i` for option `i`.
+    /// Always 0 without a quota.
+    pub full_options: u16,
+}
+
+/// Emitted once a poll's encrypted counters are set up and it can be voted in, however it
+/// was created
+#[event]
+pub struct PollCreatedEvent {
+    /// The new poll
+    pub poll: Pubkey,
+    /// The poll's authority
+    pub authority: Pubkey,
+    /// The poll's id
+    pub id: u32,
+    /// The poll question
+    pub question: String,
+    /// How many options voters can choose between
+    pub num_options: u8,
+    /// The poll's `option_uris`, for frontends building the ballot
+    pub option_uris: Vec<String>,
+}
+
+#[event]
+pub struct PollFinalizedEvent {
+    /// The finalized poll
+    pub poll: Pubkey,
+    /// The index of the winning option
+    pub winner: u8,
+}
+
+#[event]
+pub struct PollFrozenEvent {
+    /// The frozen poll
+    pub poll: Pubkey,
+    /// Number of votes counted when the poll was frozen
+    pub total_votes: u64,
+}
+
+#[event]
+pub struct TallyResetEvent {
+    /// The poll whose tally was reset
+    pub poll: Pubkey,
+    /// The poll's new `tally_epoch`
+    pub tally_epoch: u32,
+    /// Number of votes the discarded tally held
+    pub discarded_votes: u64,
+}
+
+#[event]
+pub struct AggregateEvent {
+    /// Number of polls summed
+    pub total_polls: u32,
+    /// Sum of the polls' counted votes
+    pub total_votes: u64,
+    /// How many of the polls have been revealed
+    pub total_revealed: u32,
+}
+
+#[event]
+pub struct DecidedEvent {
+    /// The index of the leading option
+    pub winner: u8,
+    /// Whether the leader can no longer be overtaken by the votes still to come
+    pub decided: bool,
+}
+
+#[event]
+pub struct TopKEvent {
+    /// The indices of the options with the most votes, most votes first, padded with `u8::MAX`
+    pub ranking: [u8; MAX_TOP_K],
+}
+
+/// One place in a `FullRankingEvent`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RankedOption {
+    /// The option's index
+    pub option: u8,
+    /// The votes, or total weight, the option got
+    pub votes: u64,
+}
+
+#[event]
+pub struct FullRankingEvent {
+    /// Every one of the poll's options, most votes first, with ties going to the lower option.
+    /// Holds at most `MAX_OPTIONS` entries, one per option.
+    pub ranking: Vec<RankedOption>,
+}
+
+#[event]
+pub struct ReferendumEvent {
+    /// Whether the yes share reached the poll's `pass_threshold_pct`
+    pub passed: bool,
+    /// The yes share, in whole percent, rounded down
+    pub yes_pct: u8,
+}
+
+#[event]
+pub struct SpreadEvent {
+    /// The highest option count minus the lowest
+    pub spread: u64,
+}
+
+#[event]
+pub struct SampledResultEvent {
+    /// The index of the winning option
+    pub winner: u8,
+    /// The margin of error, in whole percent: 100 / floor(sqrt(votes)), or 100 with no votes
+    pub moe_pct: u8,
+}
+
+#[event]
+pub struct SampleRevealEvent {
+    /// The index of the option that won the sample, an estimate of the poll's winner
+    pub winner: u8,
+    /// How many votes were drawn
+    pub sample_size: u8,
+    /// The seed the votes were drawn with
+    pub seed: u64,
+}
+
+#[event]
+pub struct BinaryResultEvent {
+    /// Whether option 1 (yes) got more votes than option 0 (no)
+    pub yes_wins: bool,
+    /// Whether both options got the same votes, in which case `yes_wins` is false
+    pub is_tie: bool,
+}
+
+#[event]
+pub struct RetryLaterEvent {
+    /// The poll whose computation failed
+    pub poll: Pubkey,
+    /// The slot from which the vote or reveal can be sent again
+    pub retry_after_slot: u64,
+}
+
+#[event]
+pub struct FeesWithdrawnEvent {
+    /// Lamports sent to the poll authority
+    pub amount: u64,
+}
+
+#[event]
+pub struct SharedTallyEvent {
+    /// The poll the tally is from
+    pub poll: Pubkey,
+    /// The x25519 key the tally was encrypted for
+    pub encryption_key: [u8; 32],
+    /// The nonce the tally was encrypted with
+    pub nonce: u128,
+    /// The encrypted `PollTally`: the vote counters, total weight, then spoiled count
+    pub ciphertexts: [[u8; 32]; Poll::POLL_TALLY_CIPHERTEXTS],
+}
+
+#[event]
+pub struct WeightQuorumEvent {
+    /// Whether the poll's total vote weight reached its `weight_quorum`
+    pub quorum_met: bool,
+}
+
+/// Emitted when a voter's claim about their own vote has been checked with `verify_vote_claim`
+#[event]
+pub struct VoteClaimEvent {
+    /// The poll the vote was cast in
+    pub poll: Pubkey,
+    /// The voter who made the claim
+    pub voter: Pubkey,
+    /// The vote's receipt, see `VoterRecord::receipt`, tying the claim to the receipt the voter
+    /// was given
+    pub receipt: [u8; 32],
+    /// The option the voter claimed to have chosen
+    pub claimed_choice: u8,
+    /// Whether the vote's encrypted choice is `claimed_choice`
+    pub matches: bool,
+}
+
+#[event]
+pub struct CommentRevealEvent {
+    /// The poll the comment was sent in
+    pub poll: Pubkey,
+    /// The voter who sent it
+    pub voter: Pubkey,
+    /// The x25519 key the comment was re-encrypted for
+    pub encryption_key: [u8; 32],
+    /// The nonce the comment was re-encrypted with
+    pub nonce: u128,
+    /// The comment's bytes, each encrypted for `encryption_key`
+    pub ciphertexts: [[u8; 32]; MAX_COMMENT_LENGTH],
+}
+
+/// Emitted when the `vote` circuit aborts a vote because the updated tally broke one of its
+/// invariants, a sign of a bug in the circuit or a tampered tally. The tally is left as it was.
+#[event]
+pub struct VoteAbortedEvent {
+    /// The poll the vote was sent to
+    pub poll: Pubkey,
+    /// The voter whose vote wasn't counted
+    pub voter: Pubkey,
+}
+
+/// Emitted by `reveal_delta` with the option currently in the lead, so a live leaderboard only
+/// needs to redraw when `changed` is true
+#[event]
+pub struct RevealDeltaEvent {
+    /// The poll revealed
+    pub poll: Pubkey,
+    /// The option in the lead at the previous `reveal_delta`, None if this is the first
+    pub previous_winner: Option<u8>,
+    /// The option in the lead now
+    pub winner: u8,
+    /// Whether `winner` differs from `previous_winner`. True for the first reveal.
+    pub changed: bool,
 }
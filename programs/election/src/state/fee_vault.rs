@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Holds the vote fees a poll has collected until the poll authority withdraws them, so
+/// they're kept apart from the authority's own funds.
+///
+/// The fees are the account's lamports above its rent-exempt minimum.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll whose fees this holds
+    pub poll: Pubkey,
+    /// Lamports ever paid in as vote fees
+    pub total_collected: u64,
+    /// Lamports ever withdrawn by the poll authority
+    pub total_withdrawn: u64,
+}
@@ -1,5 +1,33 @@
 pub mod poll;
 pub mod events;
+pub mod voter_record;
+pub mod voter_list;
+pub mod program_config;
+pub mod tally_history;
+pub mod sealed_result;
+pub mod encrypted;
+pub mod authority_log;
+pub mod fee_vault;
+pub mod authority_state;
+pub mod poll_template;
+pub mod result_attestation;
+pub mod results_account;
+pub mod vote_comment;
+pub mod authority_poll_index;
 
 pub use poll::*;
 pub use events::*;
+pub use voter_record::*;
+pub use voter_list::*;
+pub use program_config::*;
+pub use tally_history::*;
+pub use sealed_result::*;
+pub use encrypted::*;
+pub use authority_log::*;
+pub use fee_vault::*;
+pub use authority_state::*;
+pub use poll_template::*;
+pub use result_attestation::*;
+pub use results_account::*;
+pub use vote_comment::*;
+pub use authority_poll_index::*;
@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use crate::constants::{
+    CU_PRICE_MICRO_PER_PRIORITY, MAX_CONVICTION_MULTIPLIER, MAX_OPTIONS, MAX_OPTION_URI_LENGTH,
+    MAX_POLL_SIZE, MAX_QUESTION_LENGTH, PACKED_RESULT_LENGTH, TALLY_HOLD_TIMEOUT_SLOTS,
+};
+use crate::error::ErrorCode;
 
 /// Represents a confidential poll with encrypted vote tallies.
 #[account]
@@ -6,15 +13,663 @@ use anchor_lang::prelude::*;
 pub struct Poll {
     /// PDA bump seed
     pub bump: u8,
-    /// Encrypted vote counters: [neo_robot_count, humane_ai_pin_count, friend_com_count] as 32-byte ciphertexts
-    pub vote_counts: [[u8; 32]; 3],
+    /// Encrypted vote counters, one 32-byte ciphertext per option. Only the first `num_options` are used.
+    pub vote_counts: [[u8; 32]; MAX_OPTIONS],
+    /// Encrypted sum of the weights of every counted vote, stored right after `vote_counts`
+    /// because both are one `PollTally` encrypted under `nonce`
+    pub total_weight: [u8; 32],
+    /// Encrypted count of votes whose choice wasn't one of the poll's options
+    pub spoiled: [u8; 32],
     /// Unique identifier for this poll
     pub id: u32,
     /// Public key of the poll creator (only they can reveal results)
     pub authority: Pubkey,
     /// Cryptographic nonce for the encrypted vote counters
     pub nonce: u128,
-    /// The poll question (max 50 characters)
-    #[max_len(50)]
+    /// The poll question (max `MAX_QUESTION_LENGTH` bytes)
+    #[max_len(MAX_QUESTION_LENGTH)]
     pub question: String,
+    /// Number of options voters can choose between
+    pub num_options: u8,
+    /// Number of votes counted so far (public, unlike the per-option tallies)
+    pub total_votes: u64,
+    /// Where the poll is in its lifecycle. Only changed through `Poll::transition`.
+    pub status: PollStatus,
+    /// Whether a `reveal_result` computation is queued and its callback hasn't landed yet,
+    /// so `close_poll` can't close the account out from under it
+    pub reveal_pending: bool,
+    /// Whether a computation that rewrites the tally (a vote, or aggregating a child into this
+    /// poll) is queued and its callback hasn't landed yet. Each computation reads the stored
+    /// tally, so two running at once would both start from the same tally and the second
+    /// callback would overwrite the first's vote. Tally updates are serialized instead.
+    pub tally_pending: bool,
+    /// Unix timestamp of the latest `reveal_result` call, for the config's reveal cooldown. 0 if never revealed.
+    pub last_reveal_ts: i64,
+    /// The index of the winning option from the latest reveal. Only meaningful once the poll
+    /// has been revealed.
+    pub winner: u8,
+    /// Whether this poll's tally has been added to its parent's
+    pub aggregated: bool,
+    /// Optional behaviour chosen by the authority when the poll was created
+    pub settings: PollSettings,
+    /// How many nonces `derive_nonce` has handed out
+    pub nonce_counter: u64,
+    /// The slot the poll was created in. Token-weighted polls' weights are meant to be
+    /// balances as of this slot.
+    pub snapshot_slot: u64,
+    /// How many times `reset_tally` has reset the poll. Voters whose `VoterRecord` is from an
+    /// earlier epoch can vote again.
+    pub tally_epoch: u32,
+    /// Which kind of poll this is, decided by its settings when it's opened. Instructions made
+    /// for one kind of poll check it, rather than each checking the settings it depends on.
+    pub poll_type: PollType,
+    /// The slot before which votes and reveals are rejected, set when a computation failed
+    /// because the Arcium cluster was rotating its keys. 0 if that's never happened.
+    pub retry_after_slot: u64,
+    /// Number of distinct voters whose votes have been counted in this tally epoch. Unlike
+    /// `total_votes`, doesn't include votes added from child polls.
+    pub turnout: u64,
+    /// Commitment to the voters counted in this tally epoch, in the order they were counted.
+    /// See `Poll::record_turnout`.
+    pub voter_root: [u8; 32],
+    /// Whether `winner` tied for the most votes with another option in the latest reveal, and
+    /// was picked by the tie-break seed. Only meaningful once the poll has been revealed.
+    pub is_tie: bool,
+    /// The option in the lead at the latest `reveal_delta`, which the next one compares its
+    /// winner with. None until the first, and again after `reset_tally`.
+    pub live_winner: Option<u8>,
+    /// The slot `tally_pending` was last set in, so a tally whose computation never lands can
+    /// be released with `release_tally` once `TALLY_HOLD_TIMEOUT_SLOTS` have passed
+    pub tally_held_slot: u64,
+    /// The computation account of the computation holding the tally. Its callback is the only
+    /// one that may store a tally, so one landing after its hold was released is dropped even
+    /// if another computation has held the tally since.
+    pub tally_holder: Pubkey,
+}
+
+// Every poll is allocated at its maximum size, so a poll that's too large to create is a
+// build error rather than something `create_poll` finds out at runtime
+const _: () = assert!(
+    8 + Poll::INIT_SPACE <= MAX_POLL_SIZE,
+    "Poll is larger than MAX_POLL_SIZE; lower MAX_OPTIONS or MAX_QUESTION_LENGTH"
+);
+
+/// Optional poll behaviour, fixed when the poll is created.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PollSettings {
+    /// Minimum total vote weight for the poll to be quorate
+    pub weight_quorum: u64,
+    /// Unix timestamp from which votes are rejected. 0 means voting never closes.
+    pub close_ts: i64,
+    /// Let anyone, not just the authority, reveal the result once voting has closed
+    pub public_reveal_after_close: bool,
+    /// Seed deciding which option wins a tie. It's committed here, before anyone votes,
+    /// so it can't be chosen after the fact to favour whichever options ended up tied.
+    pub tie_break_seed: u64,
+    /// The poll this poll's tally can be aggregated into, e.g. a precinct's region
+    pub parent: Option<Pubkey>,
+    /// Only let wallets on the poll's `VoterList` vote
+    pub use_voter_list: bool,
+    /// How many voters are expected to vote in total, so `reveal_decided` knows how many votes
+    /// are still to come. 0 means unknown.
+    pub expected_voters: u64,
+    /// Weight each vote by the voter's balance of this SPL token instead of counting every vote
+    /// once.
+    ///
+    /// Programs can't read historical balances, so balances are read when each vote is cast and
+    /// recorded in its `VoterRecord`. Auditors should check them against balances at the poll's
+    /// `snapshot_slot`. Only use tokens that can't be borrowed or moved between wallets while the
+    /// poll is open (e.g. non-transferable or locked governance tokens): otherwise the same
+    /// tokens, or a flash loan, can be used to vote from several wallets.
+    pub weight_mint: Option<Pubkey>,
+    /// Who can read the result. See `EncMode`.
+    pub enc_mode: EncMode,
+    /// The x25519 public key `reveal_tally_shared` encrypts the tally for, in `EncMode::Shared` polls
+    pub tally_recipient: [u8; 32],
+    /// Have `reveal_result` also reveal how many votes the winner got
+    pub reveal_winner_count: bool,
+    /// Have the program derive the poll's creation nonce and each vote's nonce (see
+    /// `Poll::derive_nonce`), ignoring the nonces passed to `create_poll` and `vote`
+    pub derive_nonces: bool,
+    /// Let `post_result_cross_chain` publish the finalized winner as a Wormhole message
+    pub cross_chain: bool,
+    /// How much priority fee the poll's MPC computations pay, from 0 (none) to
+    /// `MAX_COMPUTATION_PRIORITY`, so high-stakes polls can be processed faster
+    pub computation_priority: u8,
+    /// Makes the poll a yes/no referendum, which passes if at least this percent of the votes
+    /// are yes (option 1). Checked by `reveal_referendum`. 0 means the poll isn't a referendum.
+    pub pass_threshold_pct: u8,
+    /// Seconds after `close_ts` during which the result can't be revealed, e.g. until polls have
+    /// closed in every region. Requires a `close_ts`. At most `MAX_EMBARGO_SECS`, and 0 means no
+    /// embargo.
+    pub embargo_secs: i64,
+    /// Most weight a single vote can count for in a token-weighted poll, however large the
+    /// voter's balance, to limit whales' influence. 0 means no cap.
+    pub max_weight: u64,
+    /// Makes the poll a quadratic vote: each voter spends up to this many credits with
+    /// `vote_quadratic`, and their option gains the square root of what they spend.
+    /// Can't be combined with `weight_mint`. 0 means votes count once, with `vote`.
+    pub quadratic_credits: u64,
+    /// An option that records voters abstaining: it's counted in `total_votes`, so it helps
+    /// meet `vote_quorum`, but it can never win. None means every option is a side.
+    pub abstain_option: Option<u8>,
+    /// Fewest votes, abstentions included, `reveal_result` needs to reveal a winner.
+    /// 0 means no quorum.
+    pub vote_quorum: u64,
+    /// Most votes each option can take, e.g. the seats in a signup poll. Further votes for a
+    /// full option are spoiled. Only for polls where every vote counts once. 0 means no quota.
+    pub option_quota: u64,
+    /// Whether the result can only be revealed once, so `reveal_result` can't run again after
+    /// the first reveal, even once the reveal cooldown has passed
+    pub single_reveal: bool,
+    /// Let anyone reveal the result with `crank_reveal` once `close_ts` has passed, so it
+    /// doesn't wait on the authority. Needs a `close_ts`, or the crank never becomes due.
+    pub auto_reveal: bool,
+    /// Lamports each vote pays into the poll's `FeeVault`, against spam. 0 means voting is free.
+    pub vote_fee: u64,
+    /// Makes the poll a conviction vote, where votes cast earlier weigh more: each vote's weight
+    /// is multiplied by 1 plus the whole buckets of this many seconds left until `close_ts`
+    /// when it's cast, up to `MAX_CONVICTION_MULTIPLIER`. Needs a `close_ts`, and can't be
+    /// combined with `quadratic_credits` or `option_quota`. 0 means when a vote is cast
+    /// doesn't matter.
+    pub conviction_bucket_secs: i64,
+    /// A URI for each option, in option order, pointing to an offchain description or image
+    /// for frontends to show on the ballot. Empty means the poll has none. Every byte is
+    /// instruction data, so long URIs for many options won't fit in one transaction.
+    #[max_len(MAX_OPTIONS, MAX_OPTION_URI_LENGTH)]
+    pub option_uris: Vec<String>,
+    /// Bound voting by `start_slot` and `end_slot` instead of `close_ts`. Slots are counted by
+    /// the cluster rather than read from validators' clocks, so the window can't drift.
+    /// Needs `close_ts` to be 0, so can't be combined with anything timed by it.
+    pub use_slots: bool,
+    /// With `use_slots`, the slot votes are accepted from. 0 means from creation.
+    pub start_slot: u64,
+    /// With `use_slots`, the slot from which votes are rejected. 0 means voting never closes.
+    pub end_slot: u64,
+    /// The `EncScheme`s votes may be encrypted with, bit `i` for the scheme with identifier
+    /// `i`. Votes naming any other scheme fail with `UnsupportedScheme`. Can only hold schemes
+    /// the circuits decrypt (`DECRYPTABLE_SCHEMES`), so for now must be 1.
+    pub supported_schemes: u8,
+    /// Never reveal anything about the tally: every reveal instruction fails with
+    /// `RevealDisabled`, and the poll can't be aggregated into a parent that could be revealed,
+    /// so the tally stays sealed under the MXE key for good
+    pub reveal_disabled: bool,
+    /// In token-weighted polls, how many of the weight token's decimal places to drop before
+    /// counting a balance: a vote weighs `balance / 10^weight_scale`, rounded down, before
+    /// `max_weight` (which is in the same scaled units) and any conviction multiplier. Keeps
+    /// the `u64` counters from saturating with large-supply or high-decimal tokens, at the
+    /// cost of ignoring balances below one scaled unit. At most `MAX_WEIGHT_SCALE`, and 0 in
+    /// polls that aren't token-weighted.
+    pub weight_scale: u8,
+    /// Makes the poll a `ReceiptFree` poll, for coercion resistance: votes get no receipt,
+    /// can't be checked with `verify_vote_claim`, and are only counted in `vote_many` batches,
+    /// so no single submission lines up with a change to the tally. Only for polls where
+    /// every vote counts once, unweighted.
+    pub receipt_free: bool,
+}
+
+/// Who a poll's result is revealed to.
+///
+/// Votes are tallied under the MXE's key in both modes, so every circuit can work on the tally
+/// while voting is open. The modes differ in how the result comes out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EncMode {
+    /// The poll authority reveals the winner publicly with `reveal_result` (and the other reveals)
+    Mxe,
+    /// `reveal_tally_shared` re-encrypts the whole tally for `tally_recipient`, who decrypts it
+    /// offchain. For a multiparty reveal, the recipient key should be held by a committee, e.g.
+    /// split with a threshold scheme; the program can't check how the key is held.
+    /// Public reveals are disabled.
+    Shared,
+}
+
+/// Where a poll is in its lifecycle.
+///
+/// A poll moves through these in order, except that `reset_tally` sends a poll back to
+/// `Initialized` and `close_poll` cancels it. `PollStatus::transition` is the one place that
+/// decides which moves are allowed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PollStatus {
+    /// The account exists but the poll hasn't been opened: preallocated by `preallocate_poll`
+    /// and waiting for `activate_poll`. New accounts are zeroed, so start here.
+    Created,
+    /// Opened with its question and settings, waiting for the `create_poll` computation to
+    /// store the encrypted counters. Until then they're zeroes, not valid ciphertexts, so the
+    /// poll can't be voted on or revealed.
+    Initialized,
+    /// Taking votes
+    Open,
+    /// Stopped taking votes by `freeze_poll`, so its tally can be revealed
+    Frozen,
+    /// A `reveal_result` computation has completed. Voting had stopped, by a freeze or
+    /// `close_ts`, before it was queued.
+    Revealed,
+    /// The result has been locked by `finalize_poll`, after which the poll can't change
+    Finalized,
+    /// Closed by `close_poll`. The account is closed in the same instruction, so nothing can
+    /// follow this.
+    Cancelled,
+}
+
+impl PollStatus {
+    /// Whether a poll can move from `from` to `to`:
+    ///
+    /// - `Created` to `Initialized`, when the poll is opened
+    /// - `Initialized` to `Open`, when its counters are stored
+    /// - `Open` to `Frozen`, when it's frozen
+    /// - `Open`, `Frozen` or `Revealed` to `Revealed`, when a reveal completes. An open poll
+    ///   is only revealed once its `close_ts` has passed, and a revealed one can be revealed
+    ///   again.
+    /// - `Revealed` to `Finalized`, when the result is locked
+    /// - `Open`, `Frozen` or `Revealed` back to `Initialized`, when `reset_tally` discards the
+    ///   tally
+    /// - Any status but `Cancelled` to `Cancelled`, when the poll is closed. A `Created` or
+    ///   `Initialized` poll never took a vote, so there's nothing to lose by closing it.
+    pub fn can_transition(from: PollStatus, to: PollStatus) -> bool {
+        use PollStatus::*;
+        matches!(
+            (from, to),
+            (Created, Initialized)
+                | (Initialized, Open)
+                | (Open, Frozen)
+                | (Open | Frozen | Revealed, Revealed)
+                | (Revealed, Finalized)
+                | (Open | Frozen | Revealed, Initialized)
+                | (Created | Initialized | Open | Frozen | Revealed | Finalized, Cancelled)
+        )
+    }
+
+    /// Checks the move from `from` to `to` is allowed, returning the new status.
+    ///
+    /// # Errors
+    /// `InvalidStateTransition` if `can_transition` doesn't allow it
+    pub fn transition(from: PollStatus, to: PollStatus) -> Result<PollStatus> {
+        require!(
+            Self::can_transition(from, to),
+            ErrorCode::InvalidStateTransition
+        );
+        Ok(to)
+    }
+
+    /// Whether the `create_poll` computation has stored the poll's encrypted counters, so
+    /// they're valid ciphertexts a circuit can read
+    pub fn is_initialized(self) -> bool {
+        matches!(
+            self,
+            PollStatus::Open | PollStatus::Frozen | PollStatus::Revealed | PollStatus::Finalized
+        )
+    }
+
+    /// Whether the poll has stopped taking votes for good, so its tally can be revealed.
+    /// Polls can also stop taking votes at their `close_ts`, whatever their status.
+    pub fn is_frozen(self) -> bool {
+        matches!(
+            self,
+            PollStatus::Frozen | PollStatus::Revealed | PollStatus::Finalized
+        )
+    }
+
+    /// Whether a `reveal_result` computation has completed for the poll, so `winner` holds
+    /// its result
+    pub fn is_revealed(self) -> bool {
+        matches!(self, PollStatus::Revealed | PollStatus::Finalized)
+    }
+}
+
+/// The kinds of poll, each voted in or revealed with its own instructions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PollType {
+    /// One vote per voter, for one option, counted with `vote`
+    SingleChoice,
+    /// Votes weighted by the voter's balance of `weight_mint`, counted with `vote`
+    Weighted,
+    /// Votes that spend `quadratic_credits`, counted with `vote_quadratic`
+    Quadratic,
+    /// A yes/no question with a `pass_threshold_pct`, revealed with `reveal_referendum`.
+    /// Its votes can be token-weighted.
+    Referendum,
+    /// One unweighted vote per voter with no receipt, committed with `commit_vote` and counted
+    /// in `vote_many` batches. See `PollSettings::receipt_free`.
+    ReceiptFree,
+}
+
+impl PollType {
+    /// The kind of poll the settings describe. Settings combining two kinds that can't be
+    /// mixed are rejected by `open_poll` before this is called.
+    pub fn of(settings: &PollSettings) -> Self {
+        if settings.receipt_free {
+            PollType::ReceiptFree
+        } else if settings.pass_threshold_pct > 0 {
+            PollType::Referendum
+        } else if settings.quadratic_credits > 0 {
+            PollType::Quadratic
+        } else if settings.weight_mint.is_some() {
+            PollType::Weighted
+        } else {
+            PollType::SingleChoice
+        }
+    }
+}
+
+impl Poll {
+    /// Moves the poll to `to`, if `PollStatus::transition` allows it from its current status
+    pub fn transition(&mut self, to: PollStatus) -> Result<()> {
+        self.status = PollStatus::transition(self.status, to)?;
+        Ok(())
+    }
+
+    /// Number of ciphertexts in an encrypted `PollTally`: the vote counters, the total weight
+    /// and the spoiled vote count
+    pub const POLL_TALLY_CIPHERTEXTS: usize = MAX_OPTIONS + 2;
+
+    /// Stores an encrypted `PollTally` returned by a circuit, splitting it into its fields.
+    pub fn set_poll_tally(
+        &mut self,
+        ciphertexts: [[u8; 32]; Self::POLL_TALLY_CIPHERTEXTS],
+        nonce: u128,
+    ) {
+        self.vote_counts.copy_from_slice(&ciphertexts[..MAX_OPTIONS]);
+        self.total_weight = ciphertexts[MAX_OPTIONS];
+        self.spoiled = ciphertexts[MAX_OPTIONS + 1];
+        self.nonce = nonce;
+    }
+
+    /// The option `reveal_result` starts counting from when resolving a tie.
+    ///
+    /// Derived by hashing the tie-break seed with the poll id, so polls sharing a seed
+    /// still resolve ties differently.
+    pub fn tie_break_offset(&self) -> u8 {
+        let seed_hash = hashv(&[
+            &self.settings.tie_break_seed.to_le_bytes(),
+            &self.id.to_le_bytes(),
+        ]);
+        let mut seed_hash_prefix = [0u8; 8];
+        seed_hash_prefix.copy_from_slice(&seed_hash.to_bytes()[..8]);
+        (u64::from_le_bytes(seed_hash_prefix) % self.num_options as u64) as u8
+    }
+
+    /// Hands out the next deterministic nonce for this poll: the first 16 bytes of
+    /// `sha256("nonce" || poll address || nonce_counter)`, after which the counter is bumped.
+    ///
+    /// Clients compute the same value from the poll's public `nonce_counter` before encrypting,
+    /// so they don't have to generate or track nonces. A nonce must never encrypt two messages
+    /// under the same key; the counter makes each nonce unique per poll, and voters who read
+    /// the same counter at once encrypt under their own (different) keys, so neither repeats.
+    pub fn derive_nonce(&mut self, poll: &Pubkey) -> u128 {
+        let nonce_hash = hashv(&[b"nonce", poll.as_ref(), &self.nonce_counter.to_le_bytes()]);
+        let mut nonce_hash_prefix = [0u8; 16];
+        nonce_hash_prefix.copy_from_slice(&nonce_hash.to_bytes()[..16]);
+        self.nonce_counter += 1;
+        u128::from_le_bytes(nonce_hash_prefix)
+    }
+
+    /// The compute unit price, in micro-lamports, to queue this poll's computations with
+    pub fn computation_cu_price_micro(&self) -> u64 {
+        self.settings.computation_priority as u64 * CU_PRICE_MICRO_PER_PRIORITY
+    }
+
+    /// What a vote cast at `voted_at` has its weight multiplied by: 1 plus the whole
+    /// `conviction_bucket_secs` buckets left until `close_ts`, up to
+    /// `MAX_CONVICTION_MULTIPLIER`. Always 1 in polls without conviction voting.
+    ///
+    /// Votes can't be changed once cast, so the time a vote is held until the poll closes is
+    /// known when it's cast, and the weighting is applied then rather than at reveal.
+    pub fn conviction_multiplier(&self, voted_at: i64) -> u64 {
+        if self.settings.conviction_bucket_secs == 0 {
+            return 1;
+        }
+        let buckets_left =
+            (self.settings.close_ts - voted_at).max(0) / self.settings.conviction_bucket_secs;
+        (1 + buckets_left as u64).min(MAX_CONVICTION_MULTIPLIER)
+    }
+
+    /// The option the circuits leave out when picking a winner, `u8::MAX` (no option) if the
+    /// poll has no abstain option
+    pub fn abstain_index(&self) -> u8 {
+        self.settings.abstain_option.unwrap_or(u8::MAX)
+    }
+
+    /// Holds the poll's tally for `computation`, which will replace it, from `slot` until its
+    /// callback lands or the hold is released with `release_tally`.
+    pub fn hold_tally_at(&mut self, slot: u64, computation: Pubkey) {
+        self.tally_pending = true;
+        self.tally_held_slot = slot;
+        self.tally_holder = computation;
+    }
+
+    /// Whether the tally is held for `computation`, so its callback may store the tally.
+    pub fn holds_tally_for(&self, computation: Pubkey) -> bool {
+        self.tally_pending && self.tally_holder == computation
+    }
+
+    /// Whether the tally has been held for `TALLY_HOLD_TIMEOUT_SLOTS` or more at `slot`, so its
+    /// computation can be given up on.
+    pub fn tally_hold_expired(&self, slot: u64) -> bool {
+        self.tally_pending && slot >= hold_expiry(self.tally_held_slot)
+    }
+
+    /// Whether the poll's result is still under its embargo, so it can't be revealed yet.
+    pub fn embargoed(&self, now: i64) -> bool {
+        self.settings.embargo_secs > 0
+            && now < embargo_end(self.settings.close_ts, self.settings.embargo_secs)
+    }
+
+    /// Whether the poll's `close_ts`, or its `end_slot` if it `use_slots`, has passed. Polls
+    /// without either never close.
+    pub fn voting_closed(&self, clock: &Clock) -> bool {
+        if self.settings.use_slots {
+            self.settings.end_slot != 0 && clock.slot >= self.settings.end_slot
+        } else {
+            self.settings.close_ts != 0 && clock.unix_timestamp >= self.settings.close_ts
+        }
+    }
+
+    /// Whether the poll `use_slots` and its `start_slot` hasn't been reached, so votes are
+    /// rejected until it is
+    pub fn voting_not_started(&self, clock: &Clock) -> bool {
+        self.settings.use_slots && clock.slot < self.settings.start_slot
+    }
+
+    /// Adds a counted voter to the poll's turnout, returning their position in it.
+    ///
+    /// `voter_root` is a hash chain over the voters, starting from 32 zero bytes each epoch:
+    /// see `Poll::next_voter_root`. A voter's `VoterRecord` is counted at most once per epoch,
+    /// so an observer who reads the poll's counted records for the epoch, orders them by
+    /// `turnout_index` and chains them can check both `turnout` and who voted, without
+    /// trusting anyone's count.
+    pub fn record_turnout(&mut self, voter: &Pubkey) -> u64 {
+        let turnout_index = self.turnout;
+        self.voter_root = Self::next_voter_root(&self.voter_root, voter);
+        self.turnout += 1;
+        turnout_index
+    }
+
+    /// The voter root after adding `voter`: `sha256("turnout" || voter_root || voter)`
+    pub fn next_voter_root(voter_root: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+        hashv(&[b"turnout", voter_root, voter.as_ref()]).to_bytes()
+    }
+
+    /// The poll's latest result packed into `PACKED_RESULT_LENGTH` bytes, with no padding or
+    /// length prefix: `id` (u32 LE), `num_options` (u8), `winner` (u8), `is_tie` (0 or 1) and
+    /// `total_votes` (u64 LE), at offsets 0, 4, 5, 6 and 7.
+    pub fn packed_result(&self) -> [u8; PACKED_RESULT_LENGTH] {
+        let mut packed = [0u8; PACKED_RESULT_LENGTH];
+        packed[0..4].copy_from_slice(&self.id.to_le_bytes());
+        packed[4] = self.num_options;
+        packed[5] = self.winner;
+        packed[6] = self.is_tie as u8;
+        packed[7..15].copy_from_slice(&self.total_votes.to_le_bytes());
+        packed
+    }
+}
+
+/// What `prove_turnout` returns, Borsh-encoded in field order: the poll's address, its
+/// `tally_epoch` as a little-endian u32, its `turnout` as a little-endian u64 and its
+/// 32-byte `voter_root`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TurnoutProof {
+    pub poll: Pubkey,
+    pub epoch: u32,
+    pub turnout: u64,
+    pub voter_root: [u8; 32],
+}
+
+/// What `debug_layout` returns: where a poll's encrypted fields are in its account, in bytes.
+/// Borsh-encoded in field order as little-endian u32s, then a bool.
+#[cfg(feature = "debug")]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PollLayout {
+    pub vote_counts_offset: u32,
+    pub vote_counts_length: u32,
+    /// The `PollTally` circuits read: `vote_counts`, `total_weight` and `spoiled`
+    pub poll_tally_offset: u32,
+    pub poll_tally_length: u32,
+    pub nonce_offset: u32,
+    pub nonce_length: u32,
+    pub account_length: u32,
+    /// Whether the account's bytes up to the end of `nonce` are what the offsets were worked
+    /// out from. If not, the offsets are wrong.
+    pub matches_account: bool,
+}
+
+/// The first slot a tally held in `held_slot` can be released in
+fn hold_expiry(held_slot: u64) -> u64 {
+    held_slot.saturating_add(TALLY_HOLD_TIMEOUT_SLOTS)
+}
+
+/// When an embargo of `embargo_secs` after `close_ts` lifts. Saturates rather than overflowing,
+/// which would wrap to a timestamp long past and lift the embargo straight away.
+fn embargo_end(close_ts: i64, embargo_secs: i64) -> i64 {
+    close_ts.saturating_add(embargo_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATUSES: [PollStatus; 7] = [
+        PollStatus::Created,
+        PollStatus::Initialized,
+        PollStatus::Open,
+        PollStatus::Frozen,
+        PollStatus::Revealed,
+        PollStatus::Finalized,
+        PollStatus::Cancelled,
+    ];
+
+    #[test]
+    fn a_poll_moves_through_its_lifecycle() {
+        let lifecycle = [
+            PollStatus::Created,
+            PollStatus::Initialized,
+            PollStatus::Open,
+            PollStatus::Frozen,
+            PollStatus::Revealed,
+            PollStatus::Finalized,
+            PollStatus::Cancelled,
+        ];
+        for step in lifecycle.windows(2) {
+            assert_eq!(PollStatus::transition(step[0], step[1]).unwrap(), step[1]);
+        }
+    }
+
+    #[test]
+    fn polls_can_be_revealed_again_reset_and_closed_early() {
+        let moves = [
+            // Revealed once close_ts has passed, without a freeze
+            (PollStatus::Open, PollStatus::Revealed),
+            (PollStatus::Revealed, PollStatus::Revealed),
+            (PollStatus::Open, PollStatus::Initialized),
+            (PollStatus::Frozen, PollStatus::Initialized),
+            (PollStatus::Revealed, PollStatus::Initialized),
+            (PollStatus::Open, PollStatus::Cancelled),
+            (PollStatus::Frozen, PollStatus::Cancelled),
+            // Polls that never opened, e.g. because their counters never landed
+            (PollStatus::Created, PollStatus::Cancelled),
+            (PollStatus::Initialized, PollStatus::Cancelled),
+        ];
+        for (from, to) in moves {
+            assert!(PollStatus::can_transition(from, to), "{from:?} to {to:?}");
+        }
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let moves = [
+            // Votes and reveals need counters
+            (PollStatus::Created, PollStatus::Open),
+            (PollStatus::Initialized, PollStatus::Frozen),
+            (PollStatus::Initialized, PollStatus::Revealed),
+            // Freezing can't be undone, or repeated
+            (PollStatus::Frozen, PollStatus::Open),
+            (PollStatus::Frozen, PollStatus::Frozen),
+            // Only a revealed result can be locked, and a locked one can't change
+            (PollStatus::Frozen, PollStatus::Finalized),
+            (PollStatus::Finalized, PollStatus::Revealed),
+            (PollStatus::Finalized, PollStatus::Initialized),
+        ];
+        for (from, to) in moves {
+            assert!(!PollStatus::can_transition(from, to), "{from:?} to {to:?}");
+            assert!(PollStatus::transition(from, to).is_err());
+        }
+    }
+
+    #[test]
+    fn a_cancelled_poll_goes_nowhere() {
+        for to in ALL_STATUSES {
+            assert!(
+                !PollStatus::can_transition(PollStatus::Cancelled, to),
+                "to {to:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_polls_with_counters_are_initialized() {
+        let initialized: Vec<PollStatus> = ALL_STATUSES
+            .into_iter()
+            .filter(|status| status.is_initialized())
+            .collect();
+        assert_eq!(
+            initialized,
+            [
+                PollStatus::Open,
+                PollStatus::Frozen,
+                PollStatus::Revealed,
+                PollStatus::Finalized
+            ]
+        );
+    }
+
+    #[test]
+    fn embargoes_near_the_end_of_time_never_lift_early() {
+        assert_eq!(embargo_end(1_000, 60), 1_060);
+        // Would wrap to a negative timestamp, lifting the embargo at once
+        assert_eq!(embargo_end(i64::MAX - 10, i64::MAX), i64::MAX);
+        assert_eq!(embargo_end(i64::MAX, crate::constants::MAX_EMBARGO_SECS), i64::MAX);
+    }
+
+    #[test]
+    fn held_tallies_can_only_be_released_once_the_timeout_passes() {
+        assert_eq!(hold_expiry(1_000), 1_000 + TALLY_HOLD_TIMEOUT_SLOTS);
+        assert_eq!(hold_expiry(u64::MAX - 1), u64::MAX);
+    }
+
+    #[test]
+    fn only_the_holding_computation_may_store_the_tally() {
+        let data = vec![0; 8 + Poll::INIT_SPACE];
+        let mut poll = Poll::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        poll.hold_tally_at(1_000, first);
+        assert!(poll.holds_tally_for(first));
+        assert!(!poll.holds_tally_for(second));
+
+        // Released, then held again before the first computation's callback lands
+        poll.tally_pending = false;
+        assert!(!poll.holds_tally_for(first));
+        poll.hold_tally_at(1_000 + TALLY_HOLD_TIMEOUT_SLOTS, second);
+        assert!(!poll.holds_tally_for(first));
+        assert!(poll.holds_tally_for(second));
+    }
 }
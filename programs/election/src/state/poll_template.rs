@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_OPTIONS, MAX_OPTION_LABEL_LENGTH},
+    state::PollSettings,
+};
+
+/// Defaults for a series of similar polls, which `create_poll_from_template` opens polls from.
+/// One per (authority, template id).
+#[account]
+#[derive(InitSpace)]
+pub struct PollTemplate {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The authority whose polls this template is for
+    pub authority: Pubkey,
+    /// Identifies the template among the authority's templates
+    pub id: u32,
+    /// Each option's label, for clients to show. Polls from the template have this many options;
+    /// the labels themselves aren't stored in the polls.
+    #[max_len(MAX_OPTIONS, MAX_OPTION_LABEL_LENGTH)]
+    pub option_labels: Vec<String>,
+    /// How long polls from the template stay open, in seconds from when they're created.
+    /// Sets their `close_ts` unless their settings already have one. 0 means they never close.
+    pub duration_secs: i64,
+    /// Settings for polls from the template, quorum and mode included
+    pub settings: PollSettings,
+}
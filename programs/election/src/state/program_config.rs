@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Program-wide settings, stored in a single PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    /// PDA bump seed
+    pub bump: u8,
+    /// Who can change the config
+    pub admin: Pubkey,
+    /// Rejects creating polls, voting and revealing while set, e.g. while a vulnerability is fixed
+    pub paused: bool,
+    /// Seconds a poll must wait between `reveal_result` calls, so repeated reveals can't
+    /// flood the cluster with MPC computations
+    pub reveal_cooldown: i64,
+    /// The Wormhole core bridge program cross-chain results are posted to.
+    /// The default pubkey means cross-chain posting isn't set up.
+    pub wormhole_program: Pubkey,
+    /// Number of polls opened, including clones
+    pub total_polls: u64,
+    /// Number of polls whose result has been revealed with `reveal_result`
+    pub total_revealed: u64,
+    /// Number of votes counted across every poll
+    pub total_votes_all_polls: u64,
+    /// Most polls one authority can have open at once, set with `set_max_active_polls`.
+    /// 0 means there's no limit.
+    pub max_active_polls: u32,
+}
+
+/// What `get_program_stats` returns: the config's counters, Borsh-encoded in field order as
+/// three little-endian u64s (24 bytes).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProgramStats {
+    pub total_polls: u64,
+    pub total_revealed: u64,
+    pub total_votes_all_polls: u64,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// A digest of a poll's revealed result, written by `reveal_result_callback` for third parties
+/// to check or sign. One per poll, rewritten by each reveal that changes the poll's result.
+#[account]
+#[derive(InitSpace)]
+pub struct ResultAttestation {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll the result is of
+    pub poll: Pubkey,
+    /// The poll's id
+    pub id: u32,
+    /// The index of the winning option
+    pub winner: u8,
+    /// The poll's tally nonce when the result was revealed
+    pub nonce: u128,
+    /// The slot the result was revealed in
+    pub slot: u64,
+    /// `ResultAttestation::digest` of the fields above
+    pub digest: [u8; 32],
+}
+
+impl ResultAttestation {
+    /// The digest of a revealed result: `sha256("result_attestation" || program id || poll ||
+    /// id || winner || nonce || slot)`, with the integers little-endian (u32, u8, u128, u64).
+    ///
+    /// The program id binds the digest to this deployment, so a matching digest in an account
+    /// owned by the program shows this program revealed that result at that slot. The 32 bytes
+    /// are also what an auditor signs with Ed25519 to attest to the result offchain.
+    pub fn digest(poll: &Pubkey, id: u32, winner: u8, nonce: u128, slot: u64) -> [u8; 32] {
+        hashv(&[
+            b"result_attestation",
+            crate::ID.as_ref(),
+            poll.as_ref(),
+            &id.to_le_bytes(),
+            &[winner],
+            &nonce.to_le_bytes(),
+            &slot.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
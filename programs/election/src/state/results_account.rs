@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// A poll's revealed result, written by `reveal_result_callback` when the reveal asks for it.
+/// One per poll, so other programs and clients can read the result from a small account of
+/// its own instead of the poll. Rewritten by each reveal that changes the poll's result.
+#[account]
+#[derive(InitSpace)]
+pub struct ResultsAccount {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll the result is of
+    pub poll: Pubkey,
+    /// The poll's id
+    pub id: u32,
+    /// The index of the winning option
+    pub winner: u8,
+    /// Whether several options tied for the most votes (the tie-break seed picked `winner`)
+    pub is_tie: bool,
+    /// Number of votes whose choice wasn't one of the poll's options
+    pub spoiled: u64,
+    /// The poll's `total_votes` when the result was revealed
+    pub total_votes: u64,
+    /// The slot the result was revealed in, 0 until a reveal has completed
+    pub slot: u64,
+}
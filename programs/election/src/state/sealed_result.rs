@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// A poll's winner, encrypted for one recipient by `reveal_to`. One per (poll, recipient).
+#[account]
+#[derive(InitSpace)]
+pub struct SealedResult {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll the result is of
+    pub poll: Pubkey,
+    /// The recipient's x25519 public key the winner is encrypted for
+    pub recipient: [u8; 32],
+    /// Nonce the winner is encrypted under
+    pub nonce: u128,
+    /// The index of the winning option, encrypted for `recipient`
+    pub winner: [u8; 32],
+    /// Whether a `reveal_to` computation has completed and `winner` is set
+    pub sealed: bool,
+}
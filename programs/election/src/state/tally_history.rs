@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_OPTIONS, MAX_TALLY_SNAPSHOTS};
+
+/// A poll's encrypted vote counters as they were at one slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TallySnapshot {
+    /// The slot the snapshot was taken in
+    pub slot: u64,
+    /// The nonce the counters were encrypted under
+    pub nonce: u128,
+    /// The poll's encrypted vote counters
+    pub vote_counts: [[u8; 32]; MAX_OPTIONS],
+}
+
+/// An audit trail of how a poll's encrypted tally evolved.
+///
+/// Holds the last `MAX_TALLY_SNAPSHOTS` snapshots as a ring buffer. Snapshots are only ever
+/// added, never edited, and stay encrypted like the poll itself.
+#[account]
+#[derive(InitSpace)]
+pub struct TallyHistory {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll the snapshots are of
+    pub poll: Pubkey,
+    /// Number of snapshots ever taken. The newest is at `(total_snapshots - 1) % MAX_TALLY_SNAPSHOTS`.
+    pub total_snapshots: u64,
+    #[max_len(MAX_TALLY_SNAPSHOTS)]
+    pub snapshots: Vec<TallySnapshot>,
+}
+
+impl TallyHistory {
+    /// Adds a snapshot, overwriting the oldest once the buffer is full.
+    pub fn push(&mut self, snapshot: TallySnapshot) {
+        if self.snapshots.len() < MAX_TALLY_SNAPSHOTS {
+            self.snapshots.push(snapshot);
+        } else {
+            let oldest = (self.total_snapshots % MAX_TALLY_SNAPSHOTS as u64) as usize;
+            self.snapshots[oldest] = snapshot;
+        }
+        self.total_snapshots += 1;
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_COMMENT_LENGTH;
+
+/// An encrypted comment sent with a vote: `MAX_COMMENT_LENGTH` bytes of text, each byte a
+/// Rescue ciphertext. Encrypted with the same key as the vote's choice but its own nonce.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EncComment {
+    /// Nonce the comment is encrypted with. Must differ from the choice's, or the two would
+    /// share a keystream.
+    pub nonce: u128,
+    /// The comment's bytes, padded with zeroes, each encrypted separately
+    pub ciphertexts: [[u8; 32]; MAX_COMMENT_LENGTH],
+}
+
+/// A voter's encrypted comment on a poll, kept apart from the tally. One per (poll, voter),
+/// rewritten if the voter votes again after a reset.
+///
+/// Comments never reach the tally or any result: only `reveal_comment` reads them, and it
+/// re-encrypts the comment for the poll authority rather than revealing it.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteComment {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The comment's bytes, each encrypted. Stored straight after the bump, so `reveal_comment`
+    /// can pass them to the cluster as a fixed range of the account.
+    pub ciphertexts: [[u8; 32]; MAX_COMMENT_LENGTH],
+    /// The poll the comment was sent in
+    pub poll: Pubkey,
+    /// The voter who sent it
+    pub voter: Pubkey,
+    /// The voter's x25519 public key the comment was encrypted with
+    pub encryption_pubkey: [u8; 32],
+    /// Nonce the comment was encrypted with
+    pub nonce: u128,
+}
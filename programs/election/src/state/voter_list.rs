@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_VOTER_LIST_SIZE;
+
+/// The wallets allowed to vote in a poll that has `use_voter_list` set.
+///
+/// Stored onchain, so it's simple to manage but bounded to `MAX_VOTER_LIST_SIZE` entries.
+#[account]
+#[derive(InitSpace)]
+pub struct VoterList {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll this list controls
+    pub poll: Pubkey,
+    /// Wallets allowed to vote
+    #[max_len(MAX_VOTER_LIST_SIZE)]
+    pub voters: Vec<Pubkey>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// Records that a voter has voted in a poll. One per (poll, voter), so each voter votes once.
+#[account]
+#[derive(InitSpace)]
+pub struct VoterRecord {
+    /// PDA bump seed
+    pub bump: u8,
+    /// The poll this vote was cast in
+    pub poll: Pubkey,
+    /// The wallet that cast the vote
+    pub voter: Pubkey,
+    /// Unix timestamp when the vote was submitted
+    pub voted_at: i64,
+    /// The encrypted choice exactly as submitted, so the voter can decrypt it with their shared secret
+    pub choice: [u8; 32],
+    /// The voter's x25519 public key the choice was encrypted with
+    pub vote_encryption_pubkey: [u8; 32],
+    /// Nonce the choice was encrypted with
+    pub vote_nonce: u128,
+    /// The weight the vote was counted with
+    pub weight: u64,
+    /// Commitment to the vote, see `VoterRecord::receipt`. Lets the voter prove they voted
+    /// without revealing their choice. Zeroed in receipt-free polls.
+    pub receipt: [u8; 32],
+    /// Whether the vote's computation has completed and the vote is in the poll's tally
+    pub counted: bool,
+    /// The poll's `tally_epoch` when the vote was cast
+    pub epoch: u32,
+    /// The slot the vote was submitted in
+    pub voted_slot: u64,
+    /// Whether the vote was committed with `commit_vote` and is waiting for `finalize_vote` to
+    /// count it. Stays set if counting fails, so the voter can retry.
+    pub committed: bool,
+    /// The voter's position in the poll's turnout for `epoch`, see `Poll::record_turnout`.
+    /// Only meaningful once `counted` is set.
+    pub turnout_index: u64,
+}
+
+impl VoterRecord {
+    /// The receipt for a vote: `sha256("receipt" || encrypted choice || poll nonce)`, using the
+    /// poll's tally nonce when the vote was cast.
+    ///
+    /// The ciphertext can only be decrypted by the voter (and the MXE), so publishing the
+    /// receipt, or the inputs to it, doesn't reveal the choice.
+    pub fn receipt(choice: &[u8; 32], poll_nonce: u128) -> [u8; 32] {
+        hashv(&[b"receipt", choice, &poll_nonce.to_le_bytes()]).to_bytes()
+    }
+}
+
+/// What `get_voter_record` returns. `has_voted` is false and the rest zeroed if the voter hasn't voted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoterRecordView {
+    pub has_voted: bool,
+    pub voted_at: i64,
+    pub choice: [u8; 32],
+    pub vote_encryption_pubkey: [u8; 32],
+    pub vote_nonce: u128,
+    pub receipt: [u8; 32],
+    pub counted: bool,
+}
@@ -126,6 +126,36 @@ export const parseAnchorEventFromLogs = (
   return null;
 };
 
+/**
+ * Parses Anchor events emitted with `emit_cpi!` from a transaction's inner instructions.
+ * Each one is a self-CPI whose data is Anchor's 8-byte event tag followed by the event,
+ * so unlike logs it can't be truncated by the RPC provider.
+ *
+ * @param innerInstructions - The transaction's inner instructions, with base58 data
+ * @param eventDiscriminator - 8-byte discriminator for the event type
+ * @returns Parsed event data (from the discriminator on, as in logs) or null if not found
+ */
+export const parseAnchorCpiEvent = (
+  innerInstructions: ReadonlyArray<{ instructions: ReadonlyArray<{ data: string }> }>,
+  eventDiscriminator: Uint8Array
+): Buffer | null => {
+  // Little-endian bytes of anchor_lang::event::EVENT_IX_TAG, sha256("anchor:event")[..8]
+  const EVENT_IX_TAG = Buffer.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+  for (const { instructions } of innerInstructions) {
+    for (const instruction of instructions) {
+      const data = Buffer.from(bs58.decode(instruction.data));
+      if (data.length >= 16 &&
+          data.subarray(0, 8).equals(EVENT_IX_TAG) &&
+          data.subarray(8, 16).equals(Buffer.from(eventDiscriminator))) {
+        return data.subarray(8);
+      }
+    }
+  }
+
+  return null;
+};
+
 /**
  * Parses RevealResultEvent from transaction.
  * Event structure: [discriminator: 8 bytes][output: u8]
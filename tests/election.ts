@@ -1,13 +1,18 @@
 import { getSetComputeUnitLimitInstruction } from "@solana-program/compute-budget";
-import { randomBytes } from "crypto";
+import { createHash, generateKeyPairSync, randomBytes, sign, verify } from "crypto";
 import { promises as fs } from "fs";
+import assert from "node:assert";
 import { connect, type Connection } from "solana-kite";
 import {
   type KeyPairSigner,
   type Address,
   type Instruction,
+  AccountRole,
   address,
+  generateKeyPairSigner,
+  getAddressEncoder,
   lamports,
+  signBytes,
 } from "@solana/kit";
 import { RescueCipher } from "./arcium-solana-kit/rescue-cipher.js";
 import {
@@ -21,11 +26,16 @@ import {
   awaitComputationFinalization,
   getComputationDefinitionAccountOffset,
   deserializeLE,
+  serializeLE,
   buildFinalizeCompDefInstruction,
   getMXELutAccountAddress,
   getLutProgramAddress,
 } from "./arcium-solana-kit/helpers.js";
-import { awaitRevealResult } from "./arcium-solana-kit/event-listener.js";
+import {
+  awaitRevealResult,
+  parseAnchorEventFromLogs,
+  parseAnchorCpiEvent,
+} from "./arcium-solana-kit/event-listener.js";
 import * as os from "os";
 import { describe, test, before } from "node:test";
 import {
@@ -37,9 +47,98 @@ import {
   getInitVoteCompDefInstruction,
   getInitRevealResultCompDefInstruction,
   getInitCreatePollCompDefInstruction,
+  getInitRevealWeightQuorumCompDefInstruction,
+  getInitRevealDecidedCompDefInstruction,
+  getRevealDecidedInstructionAsync,
+  getInitRevealTopKCompDefInstruction,
+  getRevealTopKInstructionAsync,
+  getSnapshotTallyInstructionAsync,
+  getFinalizePollInstructionAsync,
+  getFreezePollInstructionAsync,
+  getPreallocatePollInstructionAsync,
+  getClonePollInstructionAsync,
+  getActivatePollInstructionAsync,
+  getPostResultCrossChainInstructionAsync,
+  getInitRevealTallySharedCompDefInstruction,
+  getRevealTallySharedInstructionAsync,
+  getInitRevealReferendumCompDefInstruction,
+  getRevealReferendumInstructionAsync,
+  getInitRevealToCompDefInstruction,
+  getRevealToInstructionAsync,
+  fetchSealedResult,
+  getInitRevealSpreadCompDefInstruction,
+  getRevealSpreadInstructionAsync,
+  getInitVoteQuadraticCompDefInstruction,
+  getVoteQuadraticInstructionAsync,
+  getGetProgramStatsInstructionAsync,
+  getProgramStatsDecoder,
+  getClosePollInstructionAsync,
+  getResetTallyInstructionAsync,
+  getInitRevealWithIntervalCompDefInstruction,
+  getRevealWithIntervalInstructionAsync,
+  getInitRevealBoolCompDefInstruction,
+  getRevealBoolInstructionAsync,
+  getInitRevealFullRankingCompDefInstruction,
+  getRevealFullRankingInstructionAsync,
+  getCommitVoteInstructionAsync,
+  getFinalizeVoteInstructionAsync,
+  getCreatePollTemplateInstructionAsync,
+  getCreatePollFromTemplateInstructionAsync,
+  fetchPollTemplate,
+  getPollCreatedEventDecoder,
+  getBatchCheckVotedInstruction,
+  getProveTurnoutInstructionAsync,
+  getResizePollInstructionAsync,
+  getCreatePollRelayedInstructionAsync,
+  getPollSettingsEncoder,
+  getGetResultPackedInstructionAsync,
+  getTurnoutProofDecoder,
+  fetchResultAttestation,
+  fetchResultsAccount,
+  getInitRotateNonceCompDefInstruction,
+  getRotateNonceInstructionAsync,
+  getInitRevealSampleCompDefInstruction,
+  getRevealSampleInstructionAsync,
+  getInitVoteManyCompDefInstruction,
+  getVoteManyInstructionAsync,
+  getInitVerifyVoteClaimCompDefInstruction,
+  getVerifyVoteClaimInstructionAsync,
+  getInitRevealCommentCompDefInstruction,
+  getRevealCommentInstructionAsync,
+  getInitRevealDeltaCompDefInstruction,
+  getRevealDeltaInstructionAsync,
+  getCreatePollCallbackInstruction,
+  getVoteCallbackInstructionAsync,
+  EncMode,
+  EncScheme,
+  PollType,
+  PollStatus,
+  getInitAggregateChildCompDefInstruction,
+  getAggregateChildInstructionAsync,
   getCreatePollInstructionAsync,
+  getCreatePollIfNeededInstructionAsync,
   getVoteInstructionAsync,
   getRevealResultInstructionAsync,
+  getCrankRevealInstructionAsync,
+  getUpdateQuestionInstructionAsync,
+  getReleaseTallyInstructionAsync,
+  getAggregatePollsInstruction,
+  getAddVotersInstructionAsync,
+  getInitializeConfigInstructionAsync,
+  getSetPausedInstructionAsync,
+  getUpdateAdminInstructionAsync,
+  fetchProgramConfig,
+  fetchPoll,
+  fetchVoterRecord,
+  fetchTallyHistory,
+  fetchAuthorityLog,
+  fetchFeeVault,
+  getWithdrawFeesInstructionAsync,
+  getSetMaxActivePollsInstructionAsync,
+  fetchAuthorityState,
+  fetchAuthorityPollIndex,
+  AuthorityAction,
+  type PollSettingsArgs,
 } from "../dist/election-client/index.js";
 import * as path from "path";
 import idl from "../target/idl/election.json" with { type: "json" };
@@ -48,6 +147,8 @@ describe("Election", () => {
   // Election program ID - read from IDL to support dynamic program IDs across environments
   // Arcium generates different program IDs per environment, so we read the actual deployed ID
   const ELECTION_PROGRAM_ID = address(idl.address);
+  const ED25519_PROGRAM_ADDRESS = address("Ed25519SigVerify111111111111111111111111111");
+  const BPF_LOADER_UPGRADEABLE_ADDRESS = address("BPFLoaderUpgradeab1e11111111111111111111111");
 
   // Solana Kit connection for transaction sending
   let connection: Connection;
@@ -70,6 +171,48 @@ describe("Election", () => {
     "friend.com",
   ];
 
+  // Limits from programs/election/src/constants.rs
+  const MAX_OPTIONS = 16;
+  const MAX_QUESTION_LENGTH = 200;
+  const MAX_COMMENT_LENGTH = 8;
+
+  // Polls with no quorum that stay open forever, only the authority can reveal, and a fixed tie-break seed
+  const DEFAULT_POLL_SETTINGS: PollSettingsArgs = {
+    weightQuorum: 0n,
+    closeTs: 0n,
+    publicRevealAfterClose: false,
+    tieBreakSeed: 0n,
+    parent: null,
+    useVoterList: false,
+    expectedVoters: 0n,
+    weightMint: null,
+    encMode: EncMode.Mxe,
+    tallyRecipient: new Uint8Array(32),
+    revealWinnerCount: false,
+    deriveNonces: false,
+    crossChain: false,
+    computationPriority: 0,
+    passThresholdPct: 0,
+    embargoSecs: 0n,
+    maxWeight: 0n,
+    quadraticCredits: 0n,
+    abstainOption: null,
+    voteQuorum: 0n,
+    optionQuota: 0n,
+    singleReveal: false,
+    autoReveal: false,
+    voteFee: 0n,
+    convictionBucketSecs: 0n,
+    optionUris: [],
+    useSlots: false,
+    startSlot: 0n,
+    endSlot: 0n,
+    supportedSchemes: 1,
+    revealDisabled: false,
+    weightScale: 0,
+    receiptFree: false,
+  };
+
   const getOptionName = (index: number): string =>
     OPTION_NAMES[index] ?? `Option ${index}`;
 
@@ -89,9 +232,27 @@ describe("Election", () => {
     // deployment/test session. Re-initializing them in the same session would cause "account
     // already in use" errors since the accounts already exist onchain. This setup is separate
     // from test logic and only needs to happen once before running any tests.
-    await initCreatePollCompDef(pollAuthority, false, false);
-    await initVoteCompDef(pollAuthority, false, false);
-    await initRevealResultCompDef(pollAuthority, false, false);
+    await initAllCompDefs(pollAuthority);
+
+    // Like the computation definitions, the program config is created once per deployment
+    const { pda: configAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["config"]
+    );
+    const existingConfig = await connection.rpc.getAccountInfo(configAddress).send();
+    if (!existingConfig.value) {
+      // Only the program's upgrade authority, which deployed it, can create the config
+      const { pda: programData } = await connection.getPDAAndBump(
+        BPF_LOADER_UPGRADEABLE_ADDRESS,
+        [ELECTION_PROGRAM_ID]
+      );
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getInitializeConfigInstructionAsync({ admin: pollAuthority, programData }),
+        ],
+      });
+    }
 
     // Create the poll (owner creates it) before tests run.
     // The poll is an onchain account that persists, so it's created once and reused across tests.
@@ -129,7 +290,9 @@ describe("Election", () => {
       computationOffset: pollComputationOffset,
       id: pollId,
       question,
+      numOptions: OPTION_NAMES.length,
       nonce: deserializeLE(pollNonce),
+      settings: DEFAULT_POLL_SETTINGS,
     });
 
     const createPollSignature =
@@ -236,9 +399,11 @@ describe("Election", () => {
         authority: pollAuthority.address,
         computationOffset: voteComputationOffset,
         pollId: pollId,
-        choice: new Uint8Array(ciphertext[0]),
-        voteEncryptionPubkey: encryptionPublicKey,
+        choice: [new Uint8Array(ciphertext[0])],
+        voteEncryptionPubkey: [encryptionPublicKey],
         voteNonce: deserializeLE(nonce),
+        scheme: EncScheme.X25519Rescue,
+        comment: null,
       });
 
       const queueVoteSignature =
@@ -284,6 +449,14 @@ describe("Election", () => {
       carolKeys.publicKey
     );
 
+    // Only frozen polls can be revealed
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: pollId }),
+      ],
+    });
+
     // Reveal results and verify against expected outcome
     const revealComputationOffset = getRandomBigInt();
 
@@ -313,6 +486,7 @@ describe("Election", () => {
         getComputationDefinitionAccountOffset("reveal_result")
       ),
       computationOffset: revealComputationOffset,
+      authority: pollAuthority.address,
       id: pollId,
     });
 
@@ -336,168 +510,4716 @@ describe("Election", () => {
     );
   });
 
-  /**
-   * Initializes a computation definition for a given circuit.
-   * This helper consolidates the logic for initializing create_poll, vote, and reveal_result circuits.
-   *
-   * @param circuitName - The name of the circuit ("create_poll", "vote", or "reveal_result")
-   * @param pollAuthority - The keypair signer for the poll authority
-   * @param uploadRawCircuit - Whether to upload the raw circuit file
-   * @param offchainSource - Whether the circuit source is stored offchain
-   * @param getInitInstruction - Function to get the initialization instruction for this circuit
-   * @param displayName - Human-readable name for logging
-   * @param needsComputeBudget - Whether this circuit needs additional compute budget (only create_poll does)
-   * @returns Promise resolving to the transaction signature (or empty string if skipped)
-   */
-  const initCompDef = async (
-    circuitName: "create_poll" | "vote" | "reveal_result",
-    pollAuthority: KeyPairSigner,
-    uploadRawCircuit: boolean,
-    offchainSource: boolean,
-    getInitInstruction: (params: {
-      payer: KeyPairSigner;
-      mxeAccount: Address;
-      compDefAccount: Address;
-      addressLookupTable: Address;
-      lutProgram: Address;
-    }) => Instruction,
-    displayName: string,
-    needsComputeBudget: boolean = false
-  ): Promise<string> => {
-    const offset = getComputationDefinitionAccountOffset(circuitName);
-    const compDefPDA = await getComputationDefinitionAccountAddress(
-      connection,
+  test("a poll can't be revealed before its encrypted counters are initialized", async () => {
+    const uninitializedPollId = 459;
+    const computationOffset = getRandomBigInt();
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: uninitializedPollId,
+      question: "Early: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+    // Don't wait for the computation, so the counters are still placeholders
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [createPollInstruction],
+    });
+
+    const revealComputationOffset = getRandomBigInt();
+    const earlyRevealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", revealComputationOffset)),
+      authority: pollAuthority.address,
+      id: uninitializedPollId,
+    });
+    await assertInstructionFails(pollAuthority, earlyRevealInstruction, "PollNotInitialized");
+
+    await awaitComputationFinalization(computationOffset, ELECTION_PROGRAM_ID, "confirmed");
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, uninitializedPollId)
+    );
+    assert.equal(poll.data.status, PollStatus.Open);
+  });
+
+  test("every circuit's computation definition is initialized", async () => {
+    for (const { circuitName } of COMP_DEFS) {
+      const compDefAddress = await getComputationDefinitionAccountAddress(
+        connection,
+        ELECTION_PROGRAM_ID,
+        getComputationDefinitionAccountOffset(circuitName)
+      );
+      const compDefAccount = await connection.rpc.getAccountInfo(compDefAddress).send();
+      assert.ok(compDefAccount.value, `${circuitName} computation definition is missing`);
+    }
+  });
+
+  test("the poll authority can fix the question before anyone votes", async () => {
+    const freshPollId = 421;
+    await createPoll(pollAuthority, freshPollId, "Wrost tech invention of 2025?");
+
+    const correctedQuestion = "Worst tech invention of 2025?";
+    const updateQuestionInstruction = await getUpdateQuestionInstructionAsync({
+      authority: pollAuthority,
+      id: freshPollId,
+      question: correctedQuestion,
+    });
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [updateQuestionInstruction],
+    });
+
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, freshPollId)
+    );
+    assert.equal(poll.data.question, correctedQuestion);
+  });
+
+  test("the question can't be changed once voting has started", async () => {
+    const votedPollId = 422;
+    await createPoll(pollAuthority, votedPollId, "Best tech invention of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, votedPollId, VoteOption.NeoRobot);
+
+    const updateQuestionInstruction = await getUpdateQuestionInstructionAsync({
+      authority: pollAuthority,
+      id: votedPollId,
+      question: "Worst tech invention of 2025?",
+    });
+
+    await assertInstructionFails(
+      pollAuthority,
+      updateQuestionInstruction,
+      "VotingAlreadyStarted"
+    );
+  });
+
+  test("the question can't be changed while a vote is queued", async () => {
+    const queuedPollId = 524;
+    await createPoll(pollAuthority, queuedPollId, "Queued: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { voteInstruction, computationOffset } = await buildVote(
+      voter,
+      pollAuthority,
+      queuedPollId,
+      VoteOption.NeoRobot
+    );
+    // Don't wait for the computation, so the vote isn't counted in total_votes yet
+    await connection.sendTransactionFromInstructions({
+      feePayer: voter,
+      instructions: [voteInstruction],
+    });
+
+    const updateQuestionInstruction = await getUpdateQuestionInstructionAsync({
+      authority: pollAuthority,
+      id: queuedPollId,
+      question: "Best tech invention of 2025?",
+    });
+    await assertInstructionFails(
+      pollAuthority,
+      updateQuestionInstruction,
+      "VotingAlreadyStarted"
+    );
+
+    await awaitComputationFinalization(computationOffset, ELECTION_PROGRAM_ID, "confirmed");
+  });
+
+  test("a held tally can't be released before the timeout", async () => {
+    const heldPollId = 525;
+    await createPoll(pollAuthority, heldPollId, "Held: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { voteInstruction, computationOffset } = await buildVote(
+      voter,
+      pollAuthority,
+      heldPollId,
+      VoteOption.NeoRobot
+    );
+    // Don't wait for the computation, so the tally is still held
+    await connection.sendTransactionFromInstructions({
+      feePayer: voter,
+      instructions: [voteInstruction],
+    });
+
+    const releaseTallyInstruction = await getReleaseTallyInstructionAsync({
+      authority: pollAuthority,
+      id: heldPollId,
+    });
+    await assertInstructionFails(
+      pollAuthority,
+      releaseTallyInstruction,
+      "TallyHoldNotExpired"
+    );
+
+    await awaitComputationFinalization(computationOffset, ELECTION_PROGRAM_ID, "confirmed");
+
+    // Once the callback stores the tally there's nothing left to release
+    await assertInstructionFails(
+      pollAuthority,
+      releaseTallyInstruction,
+      "TallyHoldNotExpired"
+    );
+  });
+
+  test("voters can read back their own voter record", async () => {
+    const recordPollId = 425;
+    await createPoll(pollAuthority, recordPollId, "Most useful tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const submittedVote = await castVote(
+      voter,
+      "Voter",
+      pollAuthority,
+      recordPollId,
+      VoteOption.FriendCom
+    );
+
+    const pollAddress = await getPollAddress(pollAuthority.address, recordPollId);
+    const { pda: voterRecordAddress } = await connection.getPDAAndBump(
       ELECTION_PROGRAM_ID,
-      offset
+      ["voter", pollAddress, voter.address]
     );
+    const voterRecord = await fetchVoterRecord(connection.rpc, voterRecordAddress);
 
-    // Check if the comp def account already exists
-    const existingAccount = await connection.rpc
-      .getAccountInfo(compDefPDA)
-      .send();
+    assert.equal(voterRecord.data.voter, voter.address);
+    assert.deepEqual(
+      new Uint8Array(voterRecord.data.choice),
+      submittedVote.choice
+    );
+    assert.equal(voterRecord.data.voteNonce, submittedVote.voteNonce);
+  });
 
-    let transactionSignature: string;
-    if (existingAccount.value) {
-      console.log(
-        `${displayName} computation definition already exists, skipping initialization`
-      );
-      return "";
-    } else {
-      const initInstruction = getInitInstruction({
-        payer: pollAuthority,
-        mxeAccount: await getMXEAccountAddress(connection, ELECTION_PROGRAM_ID),
-        compDefAccount: compDefPDA,
-        addressLookupTable: await getMXELutAccountAddress(connection, ELECTION_PROGRAM_ID),
-        lutProgram: getLutProgramAddress(),
-      });
+  test("a voter's receipt commits to their encrypted vote and the poll's nonce", async () => {
+    const receiptPollId = 458;
+    await createPoll(pollAuthority, receiptPollId, "Receipts: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, receiptPollId);
+    // The receipt uses the poll's nonce from when the vote was cast
+    const pollBeforeVote = await fetchPoll(connection.rpc, pollAddress);
 
-      const instructions: Array<Instruction> = [];
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const submittedVote = await castVote(
+      voter,
+      "Voter",
+      pollAuthority,
+      receiptPollId,
+      VoteOption.NeoRobot
+    );
 
-      if (needsComputeBudget) {
-        // create_poll circuit requires higher compute budget due to initialization complexity
-        const COMPUTE_UNIT_LIMIT = 1_400_000;
-        const computeBudgetInstruction = getSetComputeUnitLimitInstruction({
-          units: COMPUTE_UNIT_LIMIT,
-        });
-        instructions.push(computeBudgetInstruction);
-      }
+    const { pda: voterRecordAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["voter", pollAddress, voter.address]
+    );
+    const voterRecord = await fetchVoterRecord(connection.rpc, voterRecordAddress);
 
-      instructions.push(initInstruction);
+    const pollNonceBytes = Buffer.alloc(16);
+    pollNonceBytes.writeBigUInt64LE(pollBeforeVote.data.nonce & 0xffffffffffffffffn);
+    pollNonceBytes.writeBigUInt64LE(pollBeforeVote.data.nonce >> 64n, 8);
+    const expectedReceipt = createHash("sha256")
+      .update("receipt")
+      .update(submittedVote.choice)
+      .update(pollNonceBytes)
+      .digest();
+    assert.deepEqual(new Uint8Array(voterRecord.data.receipt), new Uint8Array(expectedReceipt));
+    assert.equal(voterRecord.data.counted, true);
+  });
 
-      transactionSignature = await connection.sendTransactionFromInstructions({
-        feePayer: pollAuthority,
-        instructions,
-        skipPreflight: true,
-      });
+  test("anyone can reveal a public poll, but only once voting has closed", async () => {
+    const publicPollId = 426;
+    // Long enough for the poll to be created and the early reveal attempted before it closes
+    const VOTING_PERIOD_SECONDS = 30;
+    const closeTs = BigInt(Math.floor(Date.now() / 1000) + VOTING_PERIOD_SECONDS);
+    await createPoll(
+      pollAuthority,
+      publicPollId,
+      "Most overhyped tech of 2025?",
+      OPTION_NAMES.length,
+      { closeTs, publicRevealAfterClose: true }
+    );
 
-      if (needsComputeBudget) {
-        console.log("Transaction sent:", transactionSignature);
-      }
+    const [stranger] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    const earlyComputationOffset = getRandomBigInt();
+    const earlyRevealInstruction = await getRevealResultInstructionAsync({
+      payer: stranger,
+      ...(await getQueueComputationAccounts("reveal_result", earlyComputationOffset)),
+      authority: pollAuthority.address,
+      id: publicPollId,
+    });
+    await assertInstructionFails(
+      stranger,
+      earlyRevealInstruction,
+      "InvalidAuthority"
+    );
+
+    await waitUntilPassed(closeTs);
+    await revealResult(pollAuthority, publicPollId, stranger);
+  });
+
+  test("several polls can be summarized in one instruction", async () => {
+    const aggregatedPollIds = [427, 428, 429];
+    for (const aggregatedPollId of aggregatedPollIds) {
+      await createPoll(pollAuthority, aggregatedPollId, "Tech of the year?");
     }
 
-    if (uploadRawCircuit) {
-      const circuitPath = path.join(
-        process.cwd(),
-        "build",
-        `${circuitName}.arcis`
-      );
-      const rawCircuit = await fs.readFile(circuitPath);
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(
+      voter,
+      "Voter",
+      pollAuthority,
+      aggregatedPollIds[0],
+      VoteOption.NeoRobot
+    );
 
-      const uploadSigs = await uploadCircuit(
-        connection,
-        pollAuthority,
-        circuitName,
-        ELECTION_PROGRAM_ID,
-        rawCircuit,
-        true,
-        500
-      );
+    const pollAccounts = await Promise.all(
+      aggregatedPollIds.map(async (aggregatedPollId) => ({
+        address: await getPollAddress(pollAuthority.address, aggregatedPollId),
+        role: AccountRole.READONLY,
+      }))
+    );
+    const aggregateInstruction = getAggregatePollsInstruction();
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        {
+          ...aggregateInstruction,
+          accounts: [...aggregateInstruction.accounts, ...pollAccounts],
+        },
+      ],
+    });
 
-      console.log(
-        `Uploaded ${circuitName} circuit in ${uploadSigs.length} transactions`
-      );
-    } else if (!offchainSource) {
-      const finalizeInstruction = await buildFinalizeCompDefInstruction(
-        connection,
+    const aggregateEvent = await getEventData(signature, "AggregateEvent");
+    // Layout: [discriminator: 8][total_polls: u32][total_votes: u64][total_revealed: u32]
+    assert.equal(aggregateEvent.readUInt32LE(8), aggregatedPollIds.length);
+    assert.equal(aggregateEvent.readBigUInt64LE(12), 1n);
+    assert.equal(aggregateEvent.readUInt32LE(20), 0);
+  });
+
+  test("child polls can be aggregated into a parent poll", async () => {
+    const parentPollId = 430;
+    const childPollIds = [431, 432];
+    await createPoll(pollAuthority, parentPollId, "Regional: worst tech of 2025?");
+    const parentPollAddress = await getPollAddress(
+      pollAuthority.address,
+      parentPollId
+    );
+
+    // Long enough to create both child polls and vote in them before they close
+    const VOTING_PERIOD_SECONDS = 90;
+    const closeTs = BigInt(Math.floor(Date.now() / 1000) + VOTING_PERIOD_SECONDS);
+    for (const childPollId of childPollIds) {
+      await createPoll(
         pollAuthority,
-        Buffer.from(offset).readUInt32LE(),
-        ELECTION_PROGRAM_ID
+        childPollId,
+        "Precinct: worst tech of 2025?",
+        OPTION_NAMES.length,
+        { closeTs, parent: parentPollAddress }
       );
+    }
 
-      await connection.sendTransactionFromInstructions({
-        feePayer: pollAuthority,
-        instructions: [finalizeInstruction],
-        skipPreflight: true,
+    // The first precinct is split, but Neo robot wins once both precincts are combined
+    const [alice, bob, carol, dave] = await connection.createWallets(4, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, childPollIds[0], VoteOption.FriendCom);
+    await castVote(bob, "Bob", pollAuthority, childPollIds[1], VoteOption.NeoRobot);
+    await castVote(carol, "Carol", pollAuthority, childPollIds[1], VoteOption.NeoRobot);
+    await castVote(dave, "Dave", pollAuthority, childPollIds[0], VoteOption.NeoRobot);
+
+    await waitUntilPassed(closeTs);
+
+    for (const childPollId of childPollIds) {
+      const computationOffset = getRandomBigInt();
+      const aggregateChildInstruction = await getAggregateChildInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("aggregate_child", computationOffset)),
+        childPoll: await getPollAddress(pollAuthority.address, childPollId),
+        parentId: parentPollId,
       });
+      await sendAndAwaitComputation(
+        pollAuthority,
+        [aggregateChildInstruction],
+        computationOffset
+      );
     }
-    return transactionSignature;
-  };
 
-  const initCreatePollCompDef = async (
-    pollAuthority: KeyPairSigner,
-    uploadRawCircuit: boolean,
-    offchainSource: boolean
-  ): Promise<string> => {
-    return initCompDef(
-      "create_poll",
+    const { winner } = await revealResult(pollAuthority, parentPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("only wallets on the voter list can vote in a poll that uses one", async () => {
+    const listedPollId = 433;
+    await createPoll(
       pollAuthority,
-      uploadRawCircuit,
-      offchainSource,
-      getInitCreatePollCompDefInstruction,
-      "Create poll",
-      true
+      listedPollId,
+      "Board: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { useVoterList: true }
     );
-  };
 
-  const initVoteCompDef = async (
-    pollAuthority: KeyPairSigner,
-    uploadRawCircuit: boolean,
-    offchainSource: boolean
-  ): Promise<string> => {
-    return initCompDef(
-      "vote",
+    const [eligibleVoter, ineligibleVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    const addVotersInstruction = await getAddVotersInstructionAsync({
+      authority: pollAuthority,
+      id: listedPollId,
+      voters: [eligibleVoter.address],
+    });
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [addVotersInstruction],
+    });
+
+    await castVote(
+      eligibleVoter,
+      "Eligible voter",
       pollAuthority,
-      uploadRawCircuit,
-      offchainSource,
-      getInitVoteCompDefInstruction,
-      "Vote",
-      false
+      listedPollId,
+      VoteOption.HumaneAIPIN
     );
-  };
 
-  const initRevealResultCompDef = async (
-    pollAuthority: KeyPairSigner,
-    uploadRawCircuit: boolean,
-    offchainSource: boolean
-  ): Promise<string> => {
-    return initCompDef(
-      "reveal_result",
+    const computationOffset = getRandomBigInt();
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const nonce = randomBytes(16);
+    const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.HumaneAIPIN)],
+      nonce
+    );
+    const ineligibleVoteInstruction = await getVoteInstructionAsync({
+      payer: ineligibleVoter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: listedPollId,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(
+      ineligibleVoter,
+      ineligibleVoteInstruction,
+      "VoterNotEligible"
+    );
+  });
+
+  test("a poll can be called early once the leader can't be overtaken", async () => {
+    const decidedPollId = 434;
+    await createPoll(
+      pollAuthority,
+      decidedPollId,
+      "Committee: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { expectedVoters: 3n }
+    );
+
+    // Two of three expected votes for Neo robot: the last vote can only make it 2-1
+    const [alice, bob] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, decidedPollId, VoteOption.NeoRobot);
+    await castVote(bob, "Bob", pollAuthority, decidedPollId, VoteOption.NeoRobot);
+
+    const computationOffset = getRandomBigInt();
+    const revealDecidedInstruction = await getRevealDecidedInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_decided", computationOffset)),
+      id: decidedPollId,
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealDecidedInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][winner: u8][decided: bool]
+    const decidedEvent = await getEventData(finalizeSignature, "DecidedEvent");
+    assert.equal(decidedEvent[8], VoteOption.NeoRobot);
+    assert.equal(decidedEvent[9], 1);
+  });
+
+  test("the reveal reports how many votes were spoiled", async () => {
+    const spoiledPollId = 435;
+    await createPoll(pollAuthority, spoiledPollId, "Audit: worst tech of 2025?");
+
+    const [validVoter, spoilingVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(validVoter, "Valid voter", pollAuthority, spoiledPollId, VoteOption.FriendCom);
+    // One past the last option, so it isn't counted for any of them
+    await castVote(
+      spoilingVoter,
+      "Spoiling voter",
       pollAuthority,
-      uploadRawCircuit,
-      offchainSource,
-      getInitRevealResultCompDefInstruction,
-      "Reveal result",
-      false
+      spoiledPollId,
+      OPTION_NAMES.length
     );
+
+    const { winner, spoiled } = await revealResult(pollAuthority, spoiledPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+    assert.equal(spoiled, 1n);
+  });
+
+  test("votes are spoiled against the poll's own option count", async () => {
+    const fourOptionPollId = 493;
+    await createPoll(pollAuthority, fourOptionPollId, "Options: worst tech of 2025?", 4);
+
+    const [fourthOptionVoter, spoilingVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    // Out of range in a 3-option poll, but the fourth option here
+    await castVote(fourthOptionVoter, "Fourth option voter", pollAuthority, fourOptionPollId, 3);
+    await castVote(spoilingVoter, "Spoiling voter", pollAuthority, fourOptionPollId, 5);
+
+    const { winner, spoiled } = await revealResult(pollAuthority, fourOptionPollId);
+    assert.equal(winner, 3);
+    assert.equal(spoiled, 1n);
+  });
+
+  test("polls can reveal the winner's count alongside the winner", async () => {
+    const countedPollId = 443;
+    await createPoll(
+      pollAuthority,
+      countedPollId,
+      "Announced: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { revealWinnerCount: true }
+    );
+
+    const [alice, bob, carol] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, countedPollId, VoteOption.HumaneAIPIN);
+    await castVote(bob, "Bob", pollAuthority, countedPollId, VoteOption.NeoRobot);
+    await castVote(carol, "Carol", pollAuthority, countedPollId, VoteOption.HumaneAIPIN);
+
+    const { winner, winnerCount } = await revealResult(pollAuthority, countedPollId);
+    assert.equal(winner, VoteOption.HumaneAIPIN);
+    assert.equal(winnerCount, 2n);
+  });
+
+  test("an embargoed result can't be revealed until the embargo ends", async () => {
+    const embargoedPollId = 465;
+    // Long enough for the poll to be created and voted in before it closes
+    const VOTING_PERIOD_SECONDS = 30;
+    const EMBARGO_SECONDS = 3600n;
+    const closeTs = BigInt(Math.floor(Date.now() / 1000) + VOTING_PERIOD_SECONDS);
+    await createPoll(
+      pollAuthority,
+      embargoedPollId,
+      "Embargoed: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { closeTs, embargoSecs: EMBARGO_SECONDS }
+    );
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, embargoedPollId, VoteOption.NeoRobot);
+
+    // Voting has closed, but the embargo runs for another hour
+    await waitUntilPassed(closeTs);
+    const computationOffset = getRandomBigInt();
+    const revealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: pollAuthority.address,
+      id: embargoedPollId,
+    });
+    await assertInstructionFails(pollAuthority, revealInstruction, "ResultEmbargoed");
+  });
+
+  test("a poll can't be revealed again within the reveal cooldown", async () => {
+    const cooldownPollId = 445;
+    await createPoll(pollAuthority, cooldownPollId, "Cooldown: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, cooldownPollId, VoteOption.NeoRobot);
+    await revealResult(pollAuthority, cooldownPollId);
+
+    const computationOffset = getRandomBigInt();
+    const revealAgainInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: pollAuthority.address,
+      id: cooldownPollId,
+    });
+    await assertInstructionFails(pollAuthority, revealAgainInstruction, "RevealTooSoon");
+  });
+
+  test("the top options can be revealed in order", async () => {
+    const rankedPollId = 437;
+    await createPoll(pollAuthority, rankedPollId, "Board: rank the worst tech of 2025");
+
+    // Counts of [1, 5, 3], so the ranking is Humane AI PIN, friend.com, then Neo robot
+    const votesPerOption = [1, 5, 3];
+    const voters = await connection.createWallets(
+      votesPerOption.reduce((total, votes) => total + votes, 0),
+      { airdropAmount: lamports(1_000_000_000n) }
+    );
+    let voterIndex = 0;
+    for (const [option, votes] of votesPerOption.entries()) {
+      for (let vote = 0; vote < votes; vote++) {
+        await castVote(
+          voters[voterIndex],
+          `Voter ${voterIndex}`,
+          pollAuthority,
+          rankedPollId,
+          option
+        );
+        voterIndex++;
+      }
+    }
+
+    const computationOffset = getRandomBigInt();
+    const revealTopKInstruction = await getRevealTopKInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_top_k", computationOffset)),
+      id: rankedPollId,
+      k: 3,
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealTopKInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][ranking: [u8; 3]]
+    const topKEvent = await getEventData(finalizeSignature, "TopKEvent");
+    assert.deepEqual(
+      [...topKEvent.subarray(8, 11)],
+      [VoteOption.HumaneAIPIN, VoteOption.FriendCom, VoteOption.NeoRobot]
+    );
+  });
+
+  test("snapshots record how the encrypted tally evolved", async () => {
+    const snapshotPollId = 438;
+    await createPoll(pollAuthority, snapshotPollId, "Audited: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, snapshotPollId);
+
+    const snapshotTally = async () => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getSnapshotTallyInstructionAsync({
+            authority: pollAuthority,
+            id: snapshotPollId,
+          }),
+        ],
+      });
+      return fetchPoll(connection.rpc, pollAddress);
+    };
+
+    const pollBeforeVote = await snapshotTally();
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, snapshotPollId, VoteOption.NeoRobot);
+    const pollAfterVote = await snapshotTally();
+
+    const { pda: tallyHistoryAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["tally_history", pollAddress]
+    );
+    const tallyHistory = await fetchTallyHistory(connection.rpc, tallyHistoryAddress);
+
+    assert.equal(tallyHistory.data.totalSnapshots, 2n);
+    const [firstSnapshot, secondSnapshot] = tallyHistory.data.snapshots;
+    assert.ok(secondSnapshot.slot > firstSnapshot.slot);
+    assert.equal(firstSnapshot.nonce, pollBeforeVote.data.nonce);
+    assert.equal(secondSnapshot.nonce, pollAfterVote.data.nonce);
+    assert.deepEqual(
+      secondSnapshot.voteCounts.map((count) => [...count]),
+      pollAfterVote.data.voteCounts.map((count) => [...count])
+    );
+  });
+
+  test("token-weighted polls count each vote by the voter's balance", async () => {
+    const weightedPollId = 439;
+    const weightMint = await connection.createTokenMint({
+      mintAuthority: pollAuthority,
+      decimals: 0,
+      name: "Election governance",
+      symbol: "ELECT",
+      uri: "https://example.com",
+      additionalMetadata: {},
+    });
+    await createPoll(
+      pollAuthority,
+      weightedPollId,
+      "DAO: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { weightMint }
+    );
+
+    // One vote each, so only the weights can decide the result
+    const [whale, minnow] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const balances = [
+      { voter: whale, name: "Whale", balance: 10n, choice: VoteOption.FriendCom },
+      { voter: minnow, name: "Minnow", balance: 3n, choice: VoteOption.NeoRobot },
+    ];
+    for (const { voter, name, balance, choice } of balances) {
+      await connection.mintTokens(weightMint, pollAuthority, balance, voter.address);
+      await castVote(
+        voter,
+        name,
+        pollAuthority,
+        weightedPollId,
+        choice,
+        await connection.getTokenAccountAddress(voter.address, weightMint, true)
+      );
+    }
+
+    const { winner, isTie } = await revealResult(pollAuthority, weightedPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+    assert.equal(isTie, false);
+  });
+
+  test("a weight cap limits how much a single balance can count", async () => {
+    const cappedPollId = 466;
+    const MAX_WEIGHT = 10n;
+    const weightMint = await connection.createTokenMint({
+      mintAuthority: pollAuthority,
+      decimals: 0,
+      name: "Election governance",
+      symbol: "ELECT",
+      uri: "https://example.com",
+      additionalMetadata: {},
+    });
+    await createPoll(
+      pollAuthority,
+      cappedPollId,
+      "Capped DAO: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { weightMint, maxWeight: MAX_WEIGHT, revealWinnerCount: true }
+    );
+
+    // Uncapped, the whale's 100 would outweigh the minnows' 12
+    const [whale, firstMinnow, secondMinnow] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const balances = [
+      { voter: whale, name: "Whale", balance: 100n, choice: VoteOption.FriendCom },
+      { voter: firstMinnow, name: "First minnow", balance: 6n, choice: VoteOption.NeoRobot },
+      { voter: secondMinnow, name: "Second minnow", balance: 6n, choice: VoteOption.NeoRobot },
+    ];
+    for (const { voter, name, balance, choice } of balances) {
+      await connection.mintTokens(weightMint, pollAuthority, balance, voter.address);
+      await castVote(
+        voter,
+        name,
+        pollAuthority,
+        cappedPollId,
+        choice,
+        await connection.getTokenAccountAddress(voter.address, weightMint, true)
+      );
+    }
+
+    const { pda: whaleRecordAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["voter", await getPollAddress(pollAuthority.address, cappedPollId), whale.address]
+    );
+    const whaleRecord = await fetchVoterRecord(connection.rpc, whaleRecordAddress);
+    assert.equal(whaleRecord.data.weight, MAX_WEIGHT);
+
+    const { winner, winnerCount } = await revealResult(pollAuthority, cappedPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+    assert.equal(winnerCount, 12n);
+  });
+
+  test("quadratic votes count the square root of the credits spent", async () => {
+    const quadraticPollId = 467;
+    await createPoll(
+      pollAuthority,
+      quadraticPollId,
+      "Quadratic: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { quadraticCredits: 10n, revealWinnerCount: true }
+    );
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const cipher = new RescueCipher(voterKeys.sharedSecret);
+    const nonce = randomBytes(16);
+
+    // Plain votes would skip the credit budget
+    const plainOffset = getRandomBigInt();
+    const plainNonce = randomBytes(16);
+    await assertInstructionFails(
+      voter,
+      await getVoteInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("vote", plainOffset)),
+        authority: pollAuthority.address,
+        pollId: quadraticPollId,
+        choice: [new Uint8Array(cipher.encrypt([BigInt(VoteOption.FriendCom)], plainNonce)[0])],
+        voteEncryptionPubkey: [voterKeys.publicKey],
+        voteNonce: deserializeLE(plainNonce),
+        scheme: EncScheme.X25519Rescue,
+        comment: null,
+      }),
+      "WrongPollType"
+    );
+
+    // The choice and the credits are encrypted together, as a QuadraticChoice
+    const [choiceCiphertext, creditsCiphertext] = cipher.encrypt(
+      [BigInt(VoteOption.NeoRobot), 9n],
+      nonce
+    );
+
+    const computationOffset = getRandomBigInt();
+    const voteQuadraticInstruction = await getVoteQuadraticInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote_quadratic", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: quadraticPollId,
+      choice: [new Uint8Array(choiceCiphertext)],
+      credits: [new Uint8Array(creditsCiphertext)],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+    });
+    await sendAndAwaitComputation(voter, [voteQuadraticInstruction], computationOffset);
+
+    // 9 credits buy 3 votes
+    const { winner, winnerCount } = await revealResult(pollAuthority, quadraticPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+    assert.equal(winnerCount, 3n);
+  });
+
+  test("program stats count the polls opened, revealed and voted in", async () => {
+    const statsPollId = 468;
+    const before = await getProgramStats(pollAuthority);
+
+    await createPoll(pollAuthority, statsPollId, "Stats: worst tech of 2025?");
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, statsPollId, VoteOption.FriendCom);
+    await revealResult(pollAuthority, statsPollId);
+
+    const after = await getProgramStats(pollAuthority);
+    assert.equal(after.totalPolls, before.totalPolls + 1n);
+    assert.equal(after.totalRevealed, before.totalRevealed + 1n);
+    assert.equal(after.totalVotesAllPolls, before.totalVotesAllPolls + 1n);
+  });
+
+  test("abstentions count towards the quorum but can't win", async () => {
+    const abstainPollId = 469;
+    const ABSTAIN = VoteOption.FriendCom;
+    await createPoll(
+      pollAuthority,
+      abstainPollId,
+      "Abstain: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { abstainOption: ABSTAIN, voteQuorum: 3n }
+    );
+
+    // One vote for a side wouldn't meet the quorum alone
+    const [voter, firstAbstainer, secondAbstainer] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, abstainPollId, VoteOption.NeoRobot);
+    await castVote(firstAbstainer, "First abstainer", pollAuthority, abstainPollId, ABSTAIN);
+    await castVote(secondAbstainer, "Second abstainer", pollAuthority, abstainPollId, ABSTAIN);
+
+    // The abstentions outnumber the side's votes, but the side still wins
+    const { winner, isTie } = await revealResult(pollAuthority, abstainPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+    assert.equal(isTie, false);
+  });
+
+  test("a poll can't be closed while a reveal is pending", async () => {
+    const closedPollId = 470;
+    await createPoll(pollAuthority, closedPollId, "Close: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, closedPollId, VoteOption.NeoRobot);
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: closedPollId }),
+      ],
+    });
+
+    // Queue the reveal without waiting for its callback
+    const computationOffset = getRandomBigInt();
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getRevealResultInstructionAsync({
+          payer: pollAuthority,
+          ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+          authority: pollAuthority.address,
+          id: closedPollId,
+        }),
+      ],
+    });
+
+    const closePollInstruction = await getClosePollInstructionAsync({
+      authority: pollAuthority,
+      id: closedPollId,
+    });
+    await assertInstructionFails(pollAuthority, closePollInstruction, "RevealPending");
+
+    // Once the reveal has landed, the poll can be closed
+    await awaitComputationFinalization(computationOffset, ELECTION_PROGRAM_ID, "confirmed");
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [closePollInstruction],
+    });
+    const closedPoll = await connection.rpc
+      .getAccountInfo(await getPollAddress(pollAuthority.address, closedPollId))
+      .send();
+    assert.equal(closedPoll.value, null);
+  });
+
+  test("a vote can't start until the previous vote's tally has landed", async () => {
+    const serializedPollId = 471;
+    await createPoll(
+      pollAuthority,
+      serializedPollId,
+      "Busy: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { revealWinnerCount: true }
+    );
+
+    const [firstVoter, secondVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const firstVote = await buildVote(
+      firstVoter,
+      pollAuthority,
+      serializedPollId,
+      VoteOption.NeoRobot
+    );
+    const secondVote = await buildVote(
+      secondVoter,
+      pollAuthority,
+      serializedPollId,
+      VoteOption.NeoRobot
+    );
+
+    // Both votes would read the same tally, so the second is turned away while the first runs
+    await connection.sendTransactionFromInstructions({
+      feePayer: firstVoter,
+      instructions: [firstVote.voteInstruction],
+    });
+    await assertInstructionFails(secondVoter, secondVote.voteInstruction, "TallyBusy");
+
+    // Retried once the first vote has landed, the second vote counts as well
+    await awaitComputationFinalization(
+      firstVote.computationOffset,
+      ELECTION_PROGRAM_ID,
+      "confirmed"
+    );
+    await sendAndAwaitComputation(
+      secondVoter,
+      [secondVote.voteInstruction],
+      secondVote.computationOffset
+    );
+
+    const { winner, winnerCount } = await revealResult(pollAuthority, serializedPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+    assert.equal(winnerCount, 2n);
+  });
+
+  test("the config admin can hand the role on, and only to a real key", async () => {
+    const [newAdmin] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const updateAdmin = async (admin: KeyPairSigner, nextAdmin: Address) =>
+      getUpdateAdminInstructionAsync({ admin, newAdmin: nextAdmin });
+
+    await assertInstructionFails(
+      newAdmin,
+      await updateAdmin(newAdmin, newAdmin.address),
+      "InvalidAuthority"
+    );
+    await assertInstructionFails(
+      pollAuthority,
+      await updateAdmin(pollAuthority, address("11111111111111111111111111111111")),
+      "InvalidAuthority"
+    );
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [await updateAdmin(pollAuthority, newAdmin.address)],
+    });
+    try {
+      // The old admin has lost the role
+      await assertInstructionFails(
+        pollAuthority,
+        await getSetPausedInstructionAsync({ admin: pollAuthority, paused: true }),
+        "InvalidAuthority"
+      );
+    } finally {
+      // Hand it back, so the other tests can still pause the program
+      await connection.sendTransactionFromInstructions({
+        feePayer: newAdmin,
+        instructions: [await updateAdmin(newAdmin, pollAuthority.address)],
+      });
+    }
+    const { pda: configAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "config",
+    ]);
+    const config = await fetchProgramConfig(connection.rpc, configAddress);
+    assert.equal(config.data.admin, pollAuthority.address);
+  });
+
+  test("a paused poll's tally can be reset and voted on again", async () => {
+    const resetPollId = 472;
+    await createPoll(
+      pollAuthority,
+      resetPollId,
+      "Reset: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { revealWinnerCount: true }
+    );
+
+    const [voter, lateVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, resetPollId, VoteOption.NeoRobot);
+
+    const resetComputationOffset = getRandomBigInt();
+    const resetTallyInstruction = await getResetTallyInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", resetComputationOffset)),
+      id: resetPollId,
+      nonce: deserializeLE(randomBytes(16)),
+    });
+    // Resets are refused unless the program is paused
+    await assertInstructionFails(pollAuthority, resetTallyInstruction, "ProgramNotPaused");
+
+    const setPaused = async (paused: boolean) => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getSetPausedInstructionAsync({ admin: pollAuthority, paused }),
+        ],
+      });
+    };
+    await setPaused(true);
+    try {
+      await sendAndAwaitComputation(
+        pollAuthority,
+        [resetTallyInstruction],
+        resetComputationOffset
+      );
+    } finally {
+      // Unpause even if the reset fails, so the other tests can still run
+      await setPaused(false);
+    }
+
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, resetPollId)
+    );
+    assert.equal(poll.data.totalVotes, 0n);
+    assert.equal(poll.data.tallyEpoch, 1);
+
+    // The earlier voter can vote again, and their old vote is gone
+    await castVote(voter, "Voter", pollAuthority, resetPollId, VoteOption.FriendCom);
+    await castVote(lateVoter, "Late voter", pollAuthority, resetPollId, VoteOption.FriendCom);
+
+    const { winner, winnerCount } = await revealResult(pollAuthority, resetPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+    assert.equal(winnerCount, 2n);
+  });
+
+  test("a sampled poll's margin of error shrinks as the sample grows", async () => {
+    const samples = [
+      // 100 / sqrt(1)
+      { id: 473, votes: [VoteOption.NeoRobot], moePct: 100 },
+      // 100 / sqrt(4)
+      {
+        id: 474,
+        votes: [
+          VoteOption.NeoRobot,
+          VoteOption.NeoRobot,
+          VoteOption.NeoRobot,
+          VoteOption.FriendCom,
+        ],
+        moePct: 50,
+      },
+    ];
+
+    for (const sample of samples) {
+      await createPoll(pollAuthority, sample.id, "Sample: worst tech of 2025?");
+      const voters = await connection.createWallets(sample.votes.length, {
+        airdropAmount: lamports(1_000_000_000n),
+      });
+      for (const [index, choice] of sample.votes.entries()) {
+        await castVote(voters[index], `Voter ${index + 1}`, pollAuthority, sample.id, choice);
+      }
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getFreezePollInstructionAsync({ authority: pollAuthority, id: sample.id }),
+        ],
+      });
+
+      const computationOffset = getRandomBigInt();
+      const revealWithIntervalInstruction = await getRevealWithIntervalInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("reveal_with_interval", computationOffset)),
+        id: sample.id,
+      });
+      const finalizeSignature = await sendAndAwaitComputation(
+        pollAuthority,
+        [revealWithIntervalInstruction],
+        computationOffset
+      );
+
+      // Layout: [discriminator: 8][winner: u8][moe_pct: u8]
+      const sampledResultEvent = await getEventData(finalizeSignature, "SampledResultEvent");
+      assert.equal(sampledResultEvent.readUInt8(8), VoteOption.NeoRobot);
+      assert.equal(sampledResultEvent.readUInt8(9), sample.moePct);
+    }
+  });
+
+  test("a callback sent directly instead of by Arcium is rejected", async () => {
+    // Looks like the callback for a computation, but the transaction's top-level instruction
+    // is the election program's own, not Arcium's
+    const queueComputationAccounts = await getQueueComputationAccounts(
+      "create_poll",
+      getRandomBigInt()
+    );
+    const forgedCallbackInstruction = getCreatePollCallbackInstruction({
+      compDefAccount: queueComputationAccounts.compDefAccount,
+      mxeAccount: queueComputationAccounts.mxeAccount,
+      computationAccount: queueComputationAccounts.computationAccount,
+      clusterAccount: queueComputationAccounts.clusterAccount,
+      pollAccount: await getPollAddress(pollAuthority.address, pollId),
+      output: { __kind: "Failure" },
+    });
+
+    await assertInstructionFails(pollAuthority, forgedCallbackInstruction, "NotArciumCallback");
+  });
+
+  test("votes for an option that has reached its quota are spoiled", async () => {
+    const quotaPollId = 475;
+    await createPoll(
+      pollAuthority,
+      quotaPollId,
+      "Seats: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { optionQuota: 1n }
+    );
+
+    const [firstVoter, secondVoter, thirdVoter] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(firstVoter, "First voter", pollAuthority, quotaPollId, VoteOption.NeoRobot);
+    // Neo robot's only seat is taken, so this vote is spoiled
+    await castVote(secondVoter, "Second voter", pollAuthority, quotaPollId, VoteOption.NeoRobot);
+    await castVote(thirdVoter, "Third voter", pollAuthority, quotaPollId, VoteOption.FriendCom);
+
+    const { spoiled, fullOptions } = await revealResult(pollAuthority, quotaPollId);
+    assert.equal(spoiled, 1n);
+    assert.equal(fullOptions, (1 << VoteOption.NeoRobot) | (1 << VoteOption.FriendCom));
+  });
+
+  test("a single reveal poll can't be revealed twice", async () => {
+    const singleRevealPollId = 476;
+    await createPoll(
+      pollAuthority,
+      singleRevealPollId,
+      "Once: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { singleReveal: true }
+    );
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, singleRevealPollId, VoteOption.FriendCom);
+
+    const { winner } = await revealResult(pollAuthority, singleRevealPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+
+    const computationOffset = getRandomBigInt();
+    const secondRevealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: pollAuthority.address,
+      id: singleRevealPollId,
+    });
+    // Rejected before the reveal cooldown is checked
+    await assertInstructionFails(pollAuthority, secondRevealInstruction, "AlreadyRevealed");
+  });
+
+  test("vote and reveal events are also emitted as CPI events", async () => {
+    const cpiEventPollId = 477;
+    await createPoll(pollAuthority, cpiEventPollId, "Indexed: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { voteInstruction, computationOffset: voteComputationOffset } = await buildVote(
+      voter,
+      pollAuthority,
+      cpiEventPollId,
+      VoteOption.HumaneAIPIN
+    );
+    const voteSignature = await sendAndAwaitComputation(
+      voter,
+      [voteInstruction],
+      voteComputationOffset
+    );
+    assert.deepEqual(
+      await getCpiEventData(voteSignature, "VoteEvent"),
+      await getEventData(voteSignature, "VoteEvent")
+    );
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: cpiEventPollId }),
+      ],
+    });
+    const revealComputationOffset = getRandomBigInt();
+    const revealResultInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", revealComputationOffset)),
+      authority: pollAuthority.address,
+      id: cpiEventPollId,
+    });
+    const revealSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealResultInstruction],
+      revealComputationOffset
+    );
+
+    const revealResultEvent = await getCpiEventData(revealSignature, "RevealResultEvent");
+    assert.equal(revealResultEvent[8], VoteOption.HumaneAIPIN);
+    assert.deepEqual(revealResultEvent, await getEventData(revealSignature, "RevealResultEvent"));
+  });
+
+  test("anyone can crank an auto reveal poll's reveal once it has closed", async () => {
+    const autoRevealPollId = 478;
+    // Long enough for the poll to be created, voted on, and the early crank attempted before it closes
+    const VOTING_PERIOD_SECONDS = 30;
+    const closeTs = BigInt(Math.floor(Date.now() / 1000) + VOTING_PERIOD_SECONDS);
+    await createPoll(
+      pollAuthority,
+      autoRevealPollId,
+      "Unattended: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { closeTs, autoReveal: true }
+    );
+
+    const [voter, crank] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, autoRevealPollId, VoteOption.NeoRobot);
+
+    const earlyComputationOffset = getRandomBigInt();
+    const earlyCrankInstruction = await getCrankRevealInstructionAsync({
+      payer: crank,
+      ...(await getQueueComputationAccounts("reveal_result", earlyComputationOffset)),
+      authority: pollAuthority.address,
+      id: autoRevealPollId,
+    });
+    await assertInstructionFails(crank, earlyCrankInstruction, "RevealNotDue");
+
+    await waitUntilPassed(closeTs);
+    const computationOffset = getRandomBigInt();
+    const crankInstruction = await getCrankRevealInstructionAsync({
+      payer: crank,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: pollAuthority.address,
+      id: autoRevealPollId,
+    });
+    const signature = await sendAndAwaitComputation(crank, [crankInstruction], computationOffset);
+
+    const revealResultEvent = await getEventData(signature, "RevealResultEvent");
+    assert.equal(revealResultEvent[8], VoteOption.NeoRobot);
+  });
+
+  test("a vote with an all-zero encryption key is rejected before it's queued", async () => {
+    const zeroKeyPollId = 479;
+    await createPoll(pollAuthority, zeroKeyPollId, "Keys: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { computationOffset, choice, voteNonce } = await buildVote(
+      voter,
+      pollAuthority,
+      zeroKeyPollId,
+      VoteOption.NeoRobot
+    );
+    const zeroKeyVoteInstruction = await getVoteInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: zeroKeyPollId,
+      choice,
+      voteEncryptionPubkey: [new Uint8Array(32)],
+      voteNonce,
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(voter, zeroKeyVoteInstruction, "InvalidEncryptionKey");
+  });
+
+  test("each type of poll rejects instructions for other types", async () => {
+    const [voter, weightMintAuthority] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    const singleChoicePollId = 480;
+    const weightedPollId = 481;
+    const referendumPollId = 482;
+    const quadraticPollId = 483;
+    await createPoll(pollAuthority, singleChoicePollId, "Types: worst tech of 2025?");
+    // The mint is never read, as no vote is counted
+    await createPoll(
+      pollAuthority,
+      weightedPollId,
+      "Types: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { weightMint: weightMintAuthority.address }
+    );
+    await createPoll(pollAuthority, referendumPollId, "Types: ban AI pins?", 2, {
+      passThresholdPct: 50,
+    });
+    await createPoll(
+      pollAuthority,
+      quadraticPollId,
+      "Types: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { quadraticCredits: 10n }
+    );
+
+    const quadraticVoteFails = async (pollId: number) => {
+      const { computationOffset, choice, voteEncryptionPubkey, voteNonce } = await buildVote(
+        voter,
+        pollAuthority,
+        pollId,
+        VoteOption.NeoRobot
+      );
+      const voteQuadraticInstruction = await getVoteQuadraticInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("vote_quadratic", computationOffset)),
+        authority: pollAuthority.address,
+        pollId,
+        choice,
+        credits: choice,
+        voteEncryptionPubkey,
+        voteNonce,
+        scheme: EncScheme.X25519Rescue,
+      });
+      await assertInstructionFails(voter, voteQuadraticInstruction, "WrongPollType");
+    };
+
+    const revealReferendumFails = async (pollId: number) => {
+      const computationOffset = getRandomBigInt();
+      const revealReferendumInstruction = await getRevealReferendumInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("reveal_referendum", computationOffset)),
+        id: pollId,
+      });
+      await assertInstructionFails(
+        pollAuthority,
+        revealReferendumInstruction,
+        "WrongPollType"
+      );
+    };
+
+    await quadraticVoteFails(singleChoicePollId);
+    await revealReferendumFails(singleChoicePollId);
+    await quadraticVoteFails(weightedPollId);
+    await revealReferendumFails(weightedPollId);
+    await quadraticVoteFails(referendumPollId);
+    // Plain votes in quadratic polls are covered by the quadratic voting test
+    await revealReferendumFails(quadraticPollId);
+
+    const [singleChoicePoll, weightedPoll, referendumPoll, quadraticPoll] = await Promise.all(
+      [singleChoicePollId, weightedPollId, referendumPollId, quadraticPollId].map(
+        async (pollId) =>
+          fetchPoll(connection.rpc, await getPollAddress(pollAuthority.address, pollId))
+      )
+    );
+    assert.equal(singleChoicePoll.data.pollType, PollType.SingleChoice);
+    assert.equal(weightedPoll.data.pollType, PollType.Weighted);
+    assert.equal(referendumPoll.data.pollType, PollType.Referendum);
+    assert.equal(quadraticPoll.data.pollType, PollType.Quadratic);
+  });
+
+  test("a two-option poll's result can be revealed as a bool", async () => {
+    const binaryPollId = 484;
+    const NO = 0;
+    const YES = 1;
+    await createPoll(pollAuthority, binaryPollId, "Bool: ban AI pins?", 2);
+
+    // 3 no votes and 5 yes votes
+    const votes = [NO, NO, NO, YES, YES, YES, YES, YES];
+    const voters = await connection.createWallets(votes.length, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [index, choice] of votes.entries()) {
+      await castVote(voters[index], `Voter ${index + 1}`, pollAuthority, binaryPollId, choice);
+    }
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: binaryPollId }),
+      ],
+    });
+
+    const computationOffset = getRandomBigInt();
+    const revealBoolInstruction = await getRevealBoolInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_bool", computationOffset)),
+      id: binaryPollId,
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealBoolInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][yes_wins: bool][is_tie: bool]
+    const binaryResultEvent = await getEventData(finalizeSignature, "BinaryResultEvent");
+    assert.equal(binaryResultEvent[8], 1);
+    assert.equal(binaryResultEvent[9], 0);
+  });
+
+  test("a poll's authority log records each privileged action", async () => {
+    const loggedPollId = 485;
+    await createPoll(pollAuthority, loggedPollId, "Logged: worts tech of 2025?");
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getUpdateQuestionInstructionAsync({
+          authority: pollAuthority,
+          id: loggedPollId,
+          question: "Logged: worst tech of 2025?",
+        }),
+      ],
+    });
+    // Freezes the poll, then reveals it
+    await revealResult(pollAuthority, loggedPollId);
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFinalizePollInstructionAsync({ authority: pollAuthority, id: loggedPollId }),
+      ],
+    });
+
+    const { pda: authorityLogAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["authority_log", await getPollAddress(pollAuthority.address, loggedPollId)]
+    );
+    const authorityLog = await fetchAuthorityLog(connection.rpc, authorityLogAddress);
+
+    assert.equal(authorityLog.data.totalEntries, 4n);
+    assert.deepEqual(
+      authorityLog.data.entries.map((entry) => entry.action),
+      [
+        AuthorityAction.UpdateQuestion,
+        AuthorityAction.Freeze,
+        AuthorityAction.Reveal,
+        AuthorityAction.Finalize,
+      ]
+    );
+    for (const [index, entry] of authorityLog.data.entries.entries()) {
+      assert.equal(entry.actor, pollAuthority.address);
+      if (index > 0) {
+        assert.ok(entry.timestamp >= authorityLog.data.entries[index - 1].timestamp);
+      }
+    }
+  });
+
+  test("vote fees collect in the poll's fee vault until the authority withdraws them", async () => {
+    const feePollId = 486;
+    const VOTE_FEE = 1_000_000n;
+    await createPoll(
+      pollAuthority,
+      feePollId,
+      "Fees: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { voteFee: VOTE_FEE }
+    );
+    const { pda: feeVaultAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["fee_vault", await getPollAddress(pollAuthority.address, feePollId)]
+    );
+
+    const [firstVoter, secondVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(
+      firstVoter,
+      "First voter",
+      pollAuthority,
+      feePollId,
+      VoteOption.NeoRobot,
+      undefined,
+      feeVaultAddress
+    );
+    await castVote(
+      secondVoter,
+      "Second voter",
+      pollAuthority,
+      feePollId,
+      VoteOption.FriendCom,
+      undefined,
+      feeVaultAddress
+    );
+
+    const feeVault = await fetchFeeVault(connection.rpc, feeVaultAddress);
+    assert.equal(feeVault.data.totalCollected, 2n * VOTE_FEE);
+
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getWithdrawFeesInstructionAsync({
+          authority: pollAuthority,
+          feeVault: feeVaultAddress,
+          id: feePollId,
+        }),
+      ],
+    });
+
+    // Layout: [discriminator: 8][amount: u64]
+    const feesWithdrawnEvent = await getEventData(signature, "FeesWithdrawnEvent");
+    assert.equal(feesWithdrawnEvent.readBigUInt64LE(8), 2n * VOTE_FEE);
+    const emptiedFeeVault = await fetchFeeVault(connection.rpc, feeVaultAddress);
+    assert.equal(emptiedFeeVault.data.totalWithdrawn, 2n * VOTE_FEE);
+
+    // Nothing is left but the vault's rent
+    const secondWithdrawal = await getWithdrawFeesInstructionAsync({
+      authority: pollAuthority,
+      feeVault: feeVaultAddress,
+      id: feePollId,
+    });
+    await assertInstructionFails(pollAuthority, secondWithdrawal, "NoFeesToWithdraw");
+  });
+
+  test("authorities can't open more polls than the config's max_active_polls", async () => {
+    const [busyAuthority] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const [firstPollId, secondPollId, thirdPollId] = [487, 488, 489];
+
+    const setMaxActivePolls = async (maxActivePolls: number) => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getSetMaxActivePollsInstructionAsync({ admin: pollAuthority, maxActivePolls }),
+        ],
+      });
+    };
+
+    await setMaxActivePolls(2);
+    try {
+      await createPoll(busyAuthority, firstPollId, "Capped: worst tech of 2025?");
+      await createPoll(busyAuthority, secondPollId, "Capped: worst tech of 2024?");
+
+      const { pda: authorityStateAddress } = await connection.getPDAAndBump(
+        ELECTION_PROGRAM_ID,
+        ["authority_state", busyAuthority.address]
+      );
+      const authorityState = await fetchAuthorityState(connection.rpc, authorityStateAddress);
+      assert.equal(authorityState.data.activePollCount, 2);
+
+      const computationOffset = getRandomBigInt();
+      const thirdPollInstruction = await getCreatePollInstructionAsync({
+        payer: busyAuthority,
+        ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+        id: thirdPollId,
+        question: "Capped: worst tech of 2023?",
+        numOptions: OPTION_NAMES.length,
+        nonce: deserializeLE(randomBytes(16)),
+        settings: DEFAULT_POLL_SETTINGS,
+      });
+      await assertInstructionFails(busyAuthority, thirdPollInstruction, "TooManyActivePolls");
+
+      // Closing a poll frees its place
+      await connection.sendTransactionFromInstructions({
+        feePayer: busyAuthority,
+        instructions: [
+          await getFreezePollInstructionAsync({ authority: busyAuthority, id: firstPollId }),
+          await getClosePollInstructionAsync({ authority: busyAuthority, id: firstPollId }),
+        ],
+      });
+      await createPoll(busyAuthority, thirdPollId, "Capped: worst tech of 2023?");
+
+      const updatedAuthorityState = await fetchAuthorityState(
+        connection.rpc,
+        authorityStateAddress
+      );
+      assert.equal(updatedAuthorityState.data.activePollCount, 2);
+    } finally {
+      // Lift the limit even if the assertions fail, so the other tests can still create polls
+      await setMaxActivePolls(0);
+    }
+  });
+
+  test("reveal_full_ranking reveals every option's count, most votes first", async () => {
+    const rankedPollId = 490;
+    await createPoll(pollAuthority, rankedPollId, "Ranked: worst tech of 2025?", 3);
+
+    // 4 votes for option 0, 9 for option 1 and 1 for option 2
+    const votes = [...Array(4).fill(0), ...Array(9).fill(1), 2];
+    const voters = await connection.createWallets(votes.length, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [index, choice] of votes.entries()) {
+      await castVote(voters[index], `Voter ${index + 1}`, pollAuthority, rankedPollId, choice);
+    }
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: rankedPollId }),
+      ],
+    });
+
+    const computationOffset = getRandomBigInt();
+    const revealFullRankingInstruction = await getRevealFullRankingInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_full_ranking", computationOffset)),
+      id: rankedPollId,
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealFullRankingInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][ranking length: u32]([option: u8][votes: u64])*
+    const fullRankingEvent = await getEventData(finalizeSignature, "FullRankingEvent");
+    const rankingLength = fullRankingEvent.readUInt32LE(8);
+    const ranking = Array.from({ length: rankingLength }, (_, place) => {
+      const entryOffset = 12 + place * 9;
+      return [
+        fullRankingEvent[entryOffset],
+        Number(fullRankingEvent.readBigUInt64LE(entryOffset + 1)),
+      ];
+    });
+    assert.deepEqual(ranking, [
+      [1, 9],
+      [0, 4],
+      [2, 1],
+    ]);
+  });
+
+  test("only Arcium's callbacks can make a poll wait out a cluster key rotation", async () => {
+    // A real rotation can't be triggered on localnet, so this sends the callback variant a
+    // failed computation arrives as directly, which must not touch the poll
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const pollAddress = await getPollAddress(pollAuthority.address, pollId);
+    const { pda: voterRecordAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "voter",
+      pollAddress,
+      voter.address,
+    ]);
+    const queueComputationAccounts = await getQueueComputationAccounts("vote", getRandomBigInt());
+    const forgedCallbackInstruction = await getVoteCallbackInstructionAsync({
+      compDefAccount: queueComputationAccounts.compDefAccount,
+      mxeAccount: queueComputationAccounts.mxeAccount,
+      computationAccount: queueComputationAccounts.computationAccount,
+      clusterAccount: queueComputationAccounts.clusterAccount,
+      pollAccount: pollAddress,
+      voterRecord: voterRecordAddress,
+      output: { __kind: "Failure" },
+    });
+    await assertInstructionFails(pollAuthority, forgedCallbackInstruction, "NotArciumCallback");
+
+    const poll = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(poll.data.retryAfterSlot, 0n);
+  });
+
+  test("rotating a poll's nonce keeps its tally", async () => {
+    const rotatedPollId = 491;
+    await createPoll(pollAuthority, rotatedPollId, "Rotated: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, rotatedPollId);
+
+    const [alice, bob, carol] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, rotatedPollId, VoteOption.NeoRobot);
+    await castVote(bob, "Bob", pollAuthority, rotatedPollId, VoteOption.NeoRobot);
+    await castVote(carol, "Carol", pollAuthority, rotatedPollId, VoteOption.FriendCom);
+    const pollBeforeRotation = await fetchPoll(connection.rpc, pollAddress);
+
+    const computationOffset = getRandomBigInt();
+    const rotateNonceInstruction = await getRotateNonceInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("rotate_nonce", computationOffset)),
+      id: rotatedPollId,
+      nonce: deserializeLE(randomBytes(16)),
+    });
+    await sendAndAwaitComputation(pollAuthority, [rotateNonceInstruction], computationOffset);
+
+    const pollAfterRotation = await fetchPoll(connection.rpc, pollAddress);
+    assert.notEqual(pollAfterRotation.data.nonce, pollBeforeRotation.data.nonce);
+    assert.equal(pollAfterRotation.data.tallyPending, false);
+
+    const { winner } = await revealResult(pollAuthority, rotatedPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("in a conviction poll an earlier vote outweighs a later one", async () => {
+    const convictionPollId = 492;
+    const CONVICTION_BUCKET_SECS = 15n;
+    const closeTs = BigInt(Math.floor(Date.now() / 1000) + 60);
+    await createPoll(
+      pollAuthority,
+      convictionPollId,
+      "Conviction: worst tech of 2025?",
+      undefined,
+      { closeTs, convictionBucketSecs: CONVICTION_BUCKET_SECS }
+    );
+    const pollAddress = await getPollAddress(pollAuthority.address, convictionPollId);
+
+    const [earlyVoter, lateVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(
+      earlyVoter,
+      "Early voter",
+      pollAuthority,
+      convictionPollId,
+      VoteOption.NeoRobot
+    );
+    // Leaves the late vote at most one bucket before the poll closes
+    await waitUntilPassed(closeTs - CONVICTION_BUCKET_SECS - 5n);
+    await castVote(
+      lateVoter,
+      "Late voter",
+      pollAuthority,
+      convictionPollId,
+      VoteOption.FriendCom
+    );
+
+    const getVoteWeight = async (voter: KeyPairSigner) => {
+      const { pda: voterRecordAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+        "voter",
+        pollAddress,
+        voter.address,
+      ]);
+      const voterRecord = await fetchVoterRecord(connection.rpc, voterRecordAddress);
+      return voterRecord.data.weight;
+    };
+    const earlyWeight = await getVoteWeight(earlyVoter);
+    const lateWeight = await getVoteWeight(lateVoter);
+    // Weight is 1 plus the whole buckets left until close_ts
+    assert.ok(earlyWeight >= 3n, `early vote weighs ${earlyWeight}`);
+    assert.ok(lateWeight <= 2n, `late vote weighs ${lateWeight}`);
+
+    await waitUntilPassed(closeTs);
+    const { winner, isTie } = await revealResult(pollAuthority, convictionPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+    assert.equal(isTie, false);
+  });
+
+  test("a committed vote survives a failed finalize and is counted on retry", async () => {
+    const twoPhasePollId = 494;
+    await createPoll(pollAuthority, twoPhasePollId, "Two-phase: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, twoPhasePollId);
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const cipher = new RescueCipher(voterKeys.sharedSecret);
+    const nonce = randomBytes(16);
+    const ciphertext = cipher.encrypt([BigInt(VoteOption.NeoRobot)], nonce);
+    await connection.sendTransactionFromInstructions({
+      feePayer: voter,
+      instructions: [
+        await getCommitVoteInstructionAsync({
+          payer: voter,
+          authority: pollAuthority.address,
+          pollId: twoPhasePollId,
+          choice: [new Uint8Array(ciphertext[0])],
+          voteEncryptionPubkey: [voterKeys.publicKey],
+          voteNonce: deserializeLE(nonce),
+          scheme: EncScheme.X25519Rescue,
+        }),
+      ],
+    });
+
+    const { pda: voterRecordAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["voter", pollAddress, voter.address]
+    );
+    const getVoterRecord = async () =>
+      (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+    const committedRecord = await getVoterRecord();
+    assert.equal(committedRecord.committed, true);
+    assert.equal(committedRecord.counted, false);
+
+    const buildFinalize = async () => {
+      const computationOffset = getRandomBigInt();
+      const finalizeInstruction = await getFinalizeVoteInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("vote", computationOffset)),
+        authority: pollAuthority.address,
+        pollId: twoPhasePollId,
+      });
+      return { finalizeInstruction, computationOffset };
+    };
+
+    // Fails the first finalize by pausing the program while it's sent
+    const setPaused = async (paused: boolean) => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getSetPausedInstructionAsync({ admin: pollAuthority, paused }),
+        ],
+      });
+    };
+    await setPaused(true);
+    try {
+      const { finalizeInstruction } = await buildFinalize();
+      await assertInstructionFails(voter, finalizeInstruction, "ProgramPaused");
+    } finally {
+      await setPaused(false);
+    }
+    assert.equal((await getVoterRecord()).committed, true);
+
+    const { finalizeInstruction, computationOffset } = await buildFinalize();
+    await sendAndAwaitComputation(voter, [finalizeInstruction], computationOffset);
+    const countedRecord = await getVoterRecord();
+    assert.equal(countedRecord.committed, false);
+    assert.equal(countedRecord.counted, true);
+
+    // Counted once, so there's nothing left to finalize
+    const { finalizeInstruction: secondFinalize } = await buildFinalize();
+    await assertInstructionFails(voter, secondFinalize, "NoCommittedVote");
+
+    const { winner } = await revealResult(pollAuthority, twoPhasePollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("polls created from a template take its defaults, which can be overridden", async () => {
+    const templateId = 1;
+    const templateDurationSecs = 3600n;
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getCreatePollTemplateInstructionAsync({
+          authority: pollAuthority,
+          templateId,
+          optionLabels: ["Yes", "No", "Abstain", "Undecided"],
+          durationSecs: templateDurationSecs,
+          settings: { ...DEFAULT_POLL_SETTINGS, voteQuorum: 3n, abstainOption: 2 },
+        }),
+      ],
+    });
+    const { pda: templateAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "poll_template",
+      pollAuthority.address,
+      serializeLE(BigInt(templateId), 4),
+    ]);
+    const template = await fetchPollTemplate(connection.rpc, templateAddress);
+    assert.equal(template.data.optionLabels.length, 4);
+
+    const createFromTemplate = async (
+      id: number,
+      question: string,
+      settings: PollSettingsArgs | null
+    ) => {
+      const computationOffset = getRandomBigInt();
+      const instruction = await getCreatePollFromTemplateInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+        id,
+        templateId,
+        question,
+        nonce: deserializeLE(randomBytes(16)),
+        settings,
+      });
+      await sendAndAwaitComputation(pollAuthority, [instruction], computationOffset);
+      return (
+        await fetchPoll(connection.rpc, await getPollAddress(pollAuthority.address, id))
+      ).data;
+    };
+
+    const before = BigInt(Math.floor(Date.now() / 1000));
+    const defaultPoll = await createFromTemplate(495, "Template: ship it?", null);
+    assert.equal(defaultPoll.numOptions, 4);
+    assert.equal(defaultPoll.settings.voteQuorum, 3n);
+    // Closes the template's duration after it was created, give or take clock drift
+    const closeTs = defaultPoll.settings.closeTs;
+    assert.ok(
+      closeTs >= before + templateDurationSecs - 60n &&
+        closeTs <= before + templateDurationSecs + 60n,
+      `closes at ${closeTs}`
+    );
+
+    const overriddenPoll = await createFromTemplate(496, "Template: ship it now?", {
+      ...DEFAULT_POLL_SETTINGS,
+      voteQuorum: 5n,
+    });
+    assert.equal(overriddenPoll.numOptions, 4);
+    assert.equal(overriddenPoll.settings.voteQuorum, 5n);
+    // The template's duration still applies, as the override sets no close_ts
+    assert.ok(overriddenPoll.settings.closeTs > 0n);
+  });
+
+  test("a poll authority can sign for a reveal that someone else pays for", async () => {
+    const pdaAuthorityPollId = 497;
+    // Stands in for a program's PDA, which signs through invoke_signed exactly as a wallet
+    // signs here: the election program only sees the authority account as a signer
+    const [governance, keeper, voter] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await createPoll(governance, pdaAuthorityPollId, "PDA: worst tech of 2025?");
+    await castVote(voter, "Voter", governance, pdaAuthorityPollId, VoteOption.FriendCom);
+    await connection.sendTransactionFromInstructions({
+      feePayer: governance,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: governance, id: pdaAuthorityPollId }),
+      ],
+    });
+
+    const computationOffset = getRandomBigInt();
+    const revealInstruction = await getRevealResultInstructionAsync({
+      payer: keeper,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: governance.address,
+      id: pdaAuthorityPollId,
+    });
+    // Paying for a reveal doesn't make the keeper the authority
+    await assertInstructionFails(keeper, revealInstruction, "InvalidAuthority");
+
+    const signedRevealInstruction = {
+      ...revealInstruction,
+      accounts: revealInstruction.accounts.map((account) =>
+        account.address === governance.address
+          ? { ...account, role: AccountRole.READONLY_SIGNER, signer: governance }
+          : account
+      ),
+    };
+    const finalizeSignature = await sendAndAwaitComputation(
+      keeper,
+      [signedRevealInstruction],
+      computationOffset
+    );
+    const revealResultEvent = await getEventData(finalizeSignature, "RevealResultEvent");
+    assert.equal(revealResultEvent[8], VoteOption.FriendCom);
+
+    // The reveal is logged as the authority's, not the keeper's
+    const { pda: authorityLogAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      ["authority_log", await getPollAddress(governance.address, pdaAuthorityPollId)]
+    );
+    const authorityLog = await fetchAuthorityLog(connection.rpc, authorityLogAddress);
+    const revealEntry = authorityLog.data.entries.find(
+      (entry) => entry.action === AuthorityAction.Reveal
+    );
+    assert.equal(revealEntry?.actor, governance.address);
+  });
+
+  test("polls can link each option to offchain metadata", async () => {
+    const richPollId = 498;
+    const optionUris = OPTION_NAMES.map(
+      (_, option) => `https://example.com/ballots/${richPollId}/${option}.json`
+    );
+
+    const buildCreatePoll = async (uris: Array<string>) => {
+      const computationOffset = getRandomBigInt();
+      const createPollInstruction = await getCreatePollInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+        id: richPollId,
+        question: "Rich: worst tech of 2025?",
+        numOptions: OPTION_NAMES.length,
+        nonce: deserializeLE(randomBytes(16)),
+        settings: { ...DEFAULT_POLL_SETTINGS, optionUris: uris },
+      });
+      return { createPollInstruction, computationOffset };
+    };
+
+    // Either every option has a URI or none does
+    const { createPollInstruction: missingUri } = await buildCreatePoll(optionUris.slice(1));
+    await assertInstructionFails(pollAuthority, missingUri, "InvalidOptionUris");
+
+    const { createPollInstruction, computationOffset } = await buildCreatePoll(optionUris);
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [createPollInstruction],
+      computationOffset
+    );
+
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, richPollId)
+    );
+    assert.deepEqual(poll.data.settings.optionUris, optionUris);
+
+    const pollCreatedEvent = getPollCreatedEventDecoder().decode(
+      (await getEventData(finalizeSignature, "PollCreatedEvent")).subarray(8)
+    );
+    assert.equal(pollCreatedEvent.id, richPollId);
+    assert.deepEqual(pollCreatedEvent.optionUris, optionUris);
+  });
+
+  test("one call reports which of several polls a voter has voted in", async () => {
+    const dashboardPollIds = [499, 500, 501];
+    for (const dashboardPollId of dashboardPollIds) {
+      await createPoll(pollAuthority, dashboardPollId, "Dashboard: worst tech?");
+    }
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    // Votes in the first and last polls only
+    await castVote(voter, "Voter", pollAuthority, dashboardPollIds[0], VoteOption.NeoRobot);
+    await castVote(voter, "Voter", pollAuthority, dashboardPollIds[2], VoteOption.FriendCom);
+
+    const voterRecordAccounts = await Promise.all(
+      dashboardPollIds.map(async (dashboardPollId) => ({
+        address: (
+          await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+            "voter",
+            await getPollAddress(pollAuthority.address, dashboardPollId),
+            voter.address,
+          ])
+        ).pda,
+        role: AccountRole.READONLY,
+      }))
+    );
+    const batchInstruction = getBatchCheckVotedInstruction({ voter: voter.address });
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: voter,
+      instructions: [
+        {
+          ...batchInstruction,
+          accounts: [...batchInstruction.accounts, ...voterRecordAccounts],
+        },
+      ],
+    });
+
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+    const returnData = transaction?.meta?.returnData;
+    assert.ok(returnData, `No return data in transaction ${signature}`);
+    const voted = Buffer.from(returnData.data[0], "base64").readUInt32LE(0);
+    assert.equal(voted, 0b101);
+  });
+
+  test("slot windows open and close voting at slots rather than timestamps", async () => {
+    const slotPollId = 502;
+    // About 30 seconds to create the poll, then about 30 seconds of voting
+    const WINDOW_SLOTS = 75n;
+    const currentSlot = await connection.rpc.getSlot({ commitment: "confirmed" }).send();
+    const startSlot = currentSlot + WINDOW_SLOTS;
+    const endSlot = startSlot + WINDOW_SLOTS;
+
+    // Slot windows replace close_ts rather than adding to it
+    const computationOffset = getRandomBigInt();
+    const mixedWindowInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: slotPollId,
+      question: "Slots: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: {
+        ...DEFAULT_POLL_SETTINGS,
+        useSlots: true,
+        startSlot,
+        endSlot,
+        closeTs: BigInt(Math.floor(Date.now() / 1000) + 3600),
+      },
+    });
+    await assertInstructionFails(pollAuthority, mixedWindowInstruction, "InvalidSlotWindow");
+
+    await createPoll(pollAuthority, slotPollId, "Slots: worst tech of 2025?", undefined, {
+      useSlots: true,
+      startSlot,
+      endSlot,
+    });
+
+    const [earlyVoter, onTimeVoter, lateVoter] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { voteInstruction: earlyVote } = await buildVote(
+      earlyVoter,
+      pollAuthority,
+      slotPollId,
+      VoteOption.NeoRobot
+    );
+    await assertInstructionFails(earlyVoter, earlyVote, "VotingNotStarted");
+
+    await waitUntilSlot(startSlot);
+    await castVote(onTimeVoter, "On-time voter", pollAuthority, slotPollId, VoteOption.FriendCom);
+
+    await waitUntilSlot(endSlot);
+    const { voteInstruction: lateVote } = await buildVote(
+      lateVoter,
+      pollAuthority,
+      slotPollId,
+      VoteOption.NeoRobot
+    );
+    await assertInstructionFails(lateVoter, lateVote, "VotingClosed");
+
+    const { winner } = await revealResult(pollAuthority, slotPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+  });
+
+  test("a reveal writes a digest of the result that can be recomputed and signed", async () => {
+    const attestedPollId = 503;
+    await createPoll(pollAuthority, attestedPollId, "Attested: worst tech of 2025?");
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, attestedPollId, VoteOption.NeoRobot);
+    const { winner } = await revealResult(pollAuthority, attestedPollId);
+
+    const pollAddress = await getPollAddress(pollAuthority.address, attestedPollId);
+    const { pda: attestationAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "result_attestation",
+      pollAddress,
+    ]);
+    const attestation = (await fetchResultAttestation(connection.rpc, attestationAddress)).data;
+    const poll = (await fetchPoll(connection.rpc, pollAddress)).data;
+    assert.equal(attestation.poll, pollAddress);
+    assert.equal(attestation.id, attestedPollId);
+    assert.equal(attestation.winner, winner);
+    assert.equal(attestation.nonce, poll.nonce);
+
+    // sha256("result_attestation" || program id || poll || id || winner || nonce || slot)
+    const expectedDigest = createHash("sha256")
+      .update("result_attestation")
+      .update(getAddressEncoder().encode(ELECTION_PROGRAM_ID))
+      .update(getAddressEncoder().encode(pollAddress))
+      .update(serializeLE(BigInt(attestedPollId), 4))
+      .update(Uint8Array.of(winner))
+      .update(serializeLE(attestation.nonce, 16))
+      .update(serializeLE(attestation.slot, 8))
+      .digest();
+    assert.deepEqual(Buffer.from(attestation.digest), expectedDigest);
+
+    // An auditor signs the digest, and anyone with their public key can check it
+    const auditor = generateKeyPairSync("ed25519");
+    const signature = sign(null, expectedDigest, auditor.privateKey);
+    assert.ok(verify(null, Buffer.from(attestation.digest), auditor.publicKey, signature));
+  });
+
+  test("a vote encrypted with a scheme the poll doesn't accept is rejected before it's queued", async () => {
+    const schemePollId = 504;
+    await createPoll(pollAuthority, schemePollId, "Schemes: worst tech of 2025?");
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { computationOffset, choice, voteEncryptionPubkey, voteNonce } = await buildVote(
+      voter,
+      pollAuthority,
+      schemePollId,
+      VoteOption.NeoRobot
+    );
+    // The default supportedSchemes only accepts X25519Rescue
+    const aesVoteInstruction = await getVoteInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: schemePollId,
+      choice,
+      voteEncryptionPubkey,
+      voteNonce,
+      scheme: EncScheme.X25519Aes128,
+      comment: null,
+    });
+    await assertInstructionFails(voter, aesVoteInstruction, "UnsupportedScheme");
+  });
+
+  test("each distinct voter changes the poll's voter root, which prove_turnout returns", async () => {
+    const turnoutPollId = 505;
+    await createPoll(pollAuthority, turnoutPollId, "Turnout: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, turnoutPollId);
+    const fetchTurnout = async () => {
+      const poll = (await fetchPoll(connection.rpc, pollAddress)).data;
+      return { turnout: poll.turnout, voterRoot: Buffer.from(poll.voterRoot) };
+    };
+    // sha256("turnout" || voter root || voter), from 32 zero bytes
+    const nextVoterRoot = (voterRoot: Buffer, voter: Address) =>
+      createHash("sha256")
+        .update("turnout")
+        .update(voterRoot)
+        .update(getAddressEncoder().encode(voter))
+        .digest();
+
+    let expected = { turnout: 0n, voterRoot: Buffer.alloc(32) };
+    assert.deepEqual(await fetchTurnout(), expected);
+
+    const voters = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [index, voter] of voters.entries()) {
+      await castVote(voter, `Voter ${index}`, pollAuthority, turnoutPollId, VoteOption.NeoRobot);
+      const previousRoot = expected.voterRoot;
+      expected = {
+        turnout: BigInt(index + 1),
+        voterRoot: nextVoterRoot(previousRoot, voter.address),
+      };
+      const turnout = await fetchTurnout();
+      assert.notDeepEqual(turnout.voterRoot, previousRoot);
+      assert.deepEqual(turnout, expected);
+
+      const { pda: voterRecordAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+        "voter",
+        pollAddress,
+        voter.address,
+      ]);
+      const voterRecord = (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+      assert.equal(voterRecord.turnoutIndex, BigInt(index));
+    }
+
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getProveTurnoutInstructionAsync({
+          authority: pollAuthority.address,
+          id: turnoutPollId,
+        }),
+      ],
+    });
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+    const returnData = transaction?.meta?.returnData;
+    assert.ok(returnData, `No return data in transaction ${signature}`);
+    const proof = getTurnoutProofDecoder().decode(Buffer.from(returnData.data[0], "base64"));
+    assert.equal(proof.poll, pollAddress);
+    assert.equal(proof.epoch, 0);
+    assert.equal(proof.turnout, 2n);
+    assert.deepEqual(Buffer.from(proof.voterRoot), expected.voterRoot);
+  });
+
+  test("resize_poll grows a poll to the current layout, which then takes a longer question", async () => {
+    const resizedPollId = 506;
+    await createPoll(pollAuthority, resizedPollId, "Resized: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, resizedPollId);
+    const pollSize = async () => {
+      const { value } = await connection.rpc
+        .getAccountInfo(pollAddress, { encoding: "base64" })
+        .send();
+      assert.ok(value, `Poll ${pollAddress} not found`);
+      return Buffer.from(value.data[0], "base64").length;
+    };
+    const sizeBefore = await pollSize();
+
+    // The local validator only has polls created by this build, which are already the current
+    // size, so resizing is a no-op here; a poll created before MAX_QUESTION_LENGTH was raised
+    // would grow to the same size
+    const [payer] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await connection.sendTransactionFromInstructions({
+      feePayer: payer,
+      instructions: [
+        await getResizePollInstructionAsync({
+          payer,
+          authority: pollAuthority,
+          id: resizedPollId,
+        }),
+      ],
+    });
+    assert.equal(await pollSize(), sizeBefore);
+
+    const longQuestion = "Q".repeat(MAX_QUESTION_LENGTH);
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getUpdateQuestionInstructionAsync({
+          authority: pollAuthority,
+          id: resizedPollId,
+          question: longQuestion,
+        }),
+      ],
+    });
+    const poll = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(poll.data.question, longQuestion);
+  });
+
+  test("a relayer can create a poll for an authority that only signs its consent offline", async () => {
+    const relayedPollId = 507;
+    const question = "Relayed: worst tech of 2025?";
+    const settings = DEFAULT_POLL_SETTINGS;
+    // Never funded, and never signs a transaction
+    const offlineAuthority = await generateKeyPairSigner();
+    const [relayer] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    // sha256("create_poll_relayed" || program id || authority || id || relay nonce ||
+    // num options || settings || question)
+    const getConsent = (id: number, relayNonce: bigint) =>
+      createHash("sha256")
+        .update("create_poll_relayed")
+        .update(getAddressEncoder().encode(ELECTION_PROGRAM_ID))
+        .update(getAddressEncoder().encode(offlineAuthority.address))
+        .update(serializeLE(BigInt(id), 4))
+        .update(serializeLE(relayNonce, 8))
+        .update(Uint8Array.of(OPTION_NAMES.length))
+        .update(getPollSettingsEncoder().encode(settings))
+        .update(question)
+        .digest();
+    // The authority's first consent, for relay nonce 0
+    const consent = getConsent(relayedPollId, 0n);
+    const consentInstruction = getEd25519Instruction(
+      getAddressEncoder().encode(offlineAuthority.address),
+      consent,
+      await signBytes(offlineAuthority.keyPair.privateKey, consent)
+    );
+    const buildRelayedPoll = async (id: number) => {
+      const computationOffset = getRandomBigInt();
+      const instruction = await getCreatePollRelayedInstructionAsync({
+        payer: relayer,
+        ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+        id,
+        authority: offlineAuthority.address,
+        question,
+        numOptions: OPTION_NAMES.length,
+        nonce: deserializeLE(randomBytes(16)),
+        settings,
+        relayNonce: 0n,
+      });
+      return { computationOffset, instruction };
+    };
+
+    const relayedPoll = await buildRelayedPoll(relayedPollId);
+    await sendAndAwaitComputation(
+      relayer,
+      [consentInstruction, relayedPoll.instruction],
+      relayedPoll.computationOffset
+    );
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(offlineAuthority.address, relayedPollId)
+    );
+    assert.equal(poll.data.authority, offlineAuthority.address);
+    assert.equal(poll.data.question, question);
+    assert.equal(poll.data.status, PollStatus.Open);
+
+    const { pda: authorityStateAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "authority_state",
+      offlineAuthority.address,
+    ]);
+    const authorityState = await fetchAuthorityState(connection.rpc, authorityStateAddress);
+    assert.equal(authorityState.data.relayNonce, 1n);
+
+    // The consent was for relay nonce 0, which has been used, so it can't create another poll
+    const replayedPoll = await buildRelayedPoll(relayedPollId + 1);
+    await assert.rejects(
+      connection.sendTransactionFromInstructions({
+        feePayer: relayer,
+        instructions: [consentInstruction, replayedPoll.instruction],
+      }),
+      /StaleRelayNonce/
+    );
+  });
+
+  test("a poll with reveal_disabled keeps its tally sealed from every reveal", async () => {
+    const sealedPollId = 509;
+    await createPoll(
+      pollAuthority,
+      sealedPollId,
+      "Sealed: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { revealDisabled: true }
+    );
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, sealedPollId, VoteOption.NeoRobot);
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: sealedPollId }),
+      ],
+    });
+
+    const revealOffset = getRandomBigInt();
+    const revealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", revealOffset)),
+      authority: pollAuthority.address,
+      id: sealedPollId,
+    });
+    await assertInstructionFails(pollAuthority, revealInstruction, "RevealDisabled");
+
+    // Nor can the other reveals decrypt it
+    const rankingOffset = getRandomBigInt();
+    const rankingInstruction = await getRevealFullRankingInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_full_ranking", rankingOffset)),
+      id: sealedPollId,
+    });
+    await assertInstructionFails(pollAuthority, rankingInstruction, "RevealDisabled");
+
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, sealedPollId)
+    );
+    assert.equal(poll.data.totalVotes, 1n);
+    assert.equal(poll.data.status, PollStatus.Frozen);
+  });
+
+  test("get_result_packed returns a revealed result as 15 packed bytes", async () => {
+    const packedPollId = 510;
+    await createPoll(pollAuthority, packedPollId, "Packed: worst tech of 2025?");
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, packedPollId, VoteOption.NeoRobot);
+    await revealResult(pollAuthority, packedPollId);
+
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getGetResultPackedInstructionAsync({
+          authority: pollAuthority.address,
+          id: packedPollId,
+        }),
+      ],
+    });
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+    const returnData = transaction?.meta?.returnData;
+    assert.ok(returnData, `No return data in transaction ${signature}`);
+
+    // [id: u32 LE][num_options: u8][winner: u8][is_tie: u8][total_votes: u64 LE]
+    const expected = Buffer.alloc(15);
+    expected.writeUInt32LE(packedPollId, 0);
+    expected.writeUInt8(OPTION_NAMES.length, 4);
+    expected.writeUInt8(VoteOption.NeoRobot, 5);
+    expected.writeUInt8(0, 6);
+    expected.writeBigUInt64LE(1n, 7);
+    assert.deepEqual(Buffer.from(returnData.data[0], "base64"), expected);
+  });
+
+  test("reveal_sample estimates a skewed poll's winner from a sample of its votes", async () => {
+    const samplePollId = 511;
+    await createPoll(pollAuthority, samplePollId, "Sampled: worst tech of 2025?");
+    const votes = [
+      VoteOption.NeoRobot,
+      VoteOption.NeoRobot,
+      VoteOption.NeoRobot,
+      VoteOption.NeoRobot,
+      VoteOption.FriendCom,
+    ];
+    const voters = await connection.createWallets(votes.length, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [index, choice] of votes.entries()) {
+      await castVote(voters[index], `Voter ${index + 1}`, pollAuthority, samplePollId, choice);
+    }
+    const freezeInstruction = await getFreezePollInstructionAsync({
+      authority: pollAuthority,
+      id: samplePollId,
+    });
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [freezeInstruction],
+    });
+
+    // A sample too small to say much about the winner is refused
+    const smallOffset = getRandomBigInt();
+    const smallSampleInstruction = await getRevealSampleInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_sample", smallOffset)),
+      id: samplePollId,
+      sampleSize: 7,
+    });
+    await assertInstructionFails(pollAuthority, smallSampleInstruction, "InvalidSampleSize");
+
+    // The seed comes from the latest slot hash, so the draws can't be predicted, but with 32
+    // draws from a 4 to 1 poll the sample's winner is all but certain
+    const computationOffset = getRandomBigInt();
+    const revealSampleInstruction = await getRevealSampleInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_sample", computationOffset)),
+      id: samplePollId,
+      sampleSize: 32,
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealSampleInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][winner: u8][sample_size: u8][seed: u64]
+    const sampleRevealEvent = await getEventData(finalizeSignature, "SampleRevealEvent");
+    assert.equal(sampleRevealEvent.readUInt8(8), VoteOption.NeoRobot);
+    assert.equal(sampleRevealEvent.readUInt8(9), 32);
+
+    // Samples share the reveal cooldown, so the authority can't redraw until one suits them
+    const redrawOffset = getRandomBigInt();
+    const redrawInstruction = await getRevealSampleInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_sample", redrawOffset)),
+      id: samplePollId,
+      sampleSize: 32,
+    });
+    await assertInstructionFails(pollAuthority, redrawInstruction, "RevealTooSoon");
+  });
+
+  test("a poll's status only moves along the lifecycle", async () => {
+    const lifecyclePollId = 512;
+    await createPoll(pollAuthority, lifecyclePollId, "Lifecycle: worst tech of 2025?");
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, lifecyclePollId, VoteOption.NeoRobot);
+    const pollAddress = await getPollAddress(pollAuthority.address, lifecyclePollId);
+    const status = async () => (await fetchPoll(connection.rpc, pollAddress)).data.status;
+    assert.equal(await status(), PollStatus.Open);
+
+    // Only a revealed result can be finalized
+    const finalizeInstruction = await getFinalizePollInstructionAsync({
+      authority: pollAuthority,
+      id: lifecyclePollId,
+    });
+    await assertInstructionFails(pollAuthority, finalizeInstruction, "InvalidStateTransition");
+
+    const freezeInstruction = await getFreezePollInstructionAsync({
+      authority: pollAuthority,
+      id: lifecyclePollId,
+    });
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [freezeInstruction],
+    });
+    assert.equal(await status(), PollStatus.Frozen);
+    await assertInstructionFails(pollAuthority, freezeInstruction, "PollFrozen");
+
+    await revealResult(pollAuthority, lifecyclePollId);
+    assert.equal(await status(), PollStatus.Revealed);
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [finalizeInstruction],
+    });
+    assert.equal(await status(), PollStatus.Finalized);
+  });
+
+  test("vote_many counts a batch of committed votes in one computation", async () => {
+    const batchPollId = 513;
+    await createPoll(pollAuthority, batchPollId, "Batched: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, batchPollId);
+
+    const choices = [
+      VoteOption.NeoRobot,
+      VoteOption.NeoRobot,
+      VoteOption.FriendCom,
+      VoteOption.NeoRobot,
+      VoteOption.FriendCom,
+      VoteOption.NeoRobot,
+    ];
+    const voters = await connection.createWallets(choices.length, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const voterRecordAddresses: Array<Address> = [];
+    for (const [index, voter] of voters.entries()) {
+      const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+      const cipher = new RescueCipher(voterKeys.sharedSecret);
+      const nonce = randomBytes(16);
+      const ciphertext = cipher.encrypt([BigInt(choices[index])], nonce);
+      await connection.sendTransactionFromInstructions({
+        feePayer: voter,
+        instructions: [
+          await getCommitVoteInstructionAsync({
+            payer: voter,
+            authority: pollAuthority.address,
+            pollId: batchPollId,
+            choice: [new Uint8Array(ciphertext[0])],
+            voteEncryptionPubkey: [voterKeys.publicKey],
+            voteNonce: deserializeLE(nonce),
+            scheme: EncScheme.X25519Rescue,
+          }),
+        ],
+      });
+      const { pda: voterRecordAddress } = await connection.getPDAAndBump(
+        ELECTION_PROGRAM_ID,
+        ["voter", pollAddress, voter.address]
+      );
+      voterRecordAddresses.push(voterRecordAddress);
+    }
+
+    // Anyone can send a batch: here the poll authority does, for voters who've gone offline
+    const buildBatch = async (batchRecordAddresses: Array<Address>) => {
+      const computationOffset = getRandomBigInt();
+      const batchInstruction = await getVoteManyInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("vote_many", computationOffset)),
+        authority: pollAuthority.address,
+        pollId: batchPollId,
+      });
+      const recordAccounts = batchRecordAddresses.map((address) => ({
+        address,
+        role: AccountRole.READONLY,
+      }));
+      return {
+        batchInstruction: {
+          ...batchInstruction,
+          accounts: [...batchInstruction.accounts, ...recordAccounts],
+        },
+        computationOffset,
+      };
+    };
+    // Logs the wall-clock time per vote, to compare batch sizes on the local cluster
+    const countBatch = async (batchRecordAddresses: Array<Address>) => {
+      const { batchInstruction, computationOffset } = await buildBatch(batchRecordAddresses);
+      const start = Date.now();
+      await sendAndAwaitComputation(pollAuthority, [batchInstruction], computationOffset);
+      const msPerVote = (Date.now() - start) / batchRecordAddresses.length;
+      console.log(
+        `vote_many: batch of ${batchRecordAddresses.length} took ${msPerVote.toFixed(0)}ms per vote`
+      );
+    };
+
+    const [first, second] = voterRecordAddresses;
+    const { batchInstruction: duplicateBatch } = await buildBatch([first, second, first]);
+    await assertInstructionFails(pollAuthority, duplicateBatch, "DuplicateBallot");
+
+    await countBatch([first]);
+    await countBatch(voterRecordAddresses.slice(1));
+
+    const poll = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(poll.data.totalVotes, BigInt(choices.length));
+    for (const voterRecordAddress of voterRecordAddresses) {
+      const voterRecord = (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+      assert.equal(voterRecord.counted, true);
+      assert.equal(voterRecord.committed, false);
+    }
+
+    // Counted once, so there's nothing left to batch
+    const { batchInstruction: recountBatch } = await buildBatch([first]);
+    await assertInstructionFails(pollAuthority, recountBatch, "NoCommittedVote");
+
+    const { winner } = await revealResult(pollAuthority, batchPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("verify_vote_claim confirms a voter's true claim and rejects a false one", async () => {
+    const claimPollId = 514;
+    await createPoll(pollAuthority, claimPollId, "Disputed: worst tech of 2025?");
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, claimPollId, VoteOption.NeoRobot);
+    const pollAddress = await getPollAddress(pollAuthority.address, claimPollId);
+    const { pda: voterRecordAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "voter",
+      pollAddress,
+      voter.address,
+    ]);
+    const voterRecord = (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+
+    const verifyClaim = async (claimedChoice: number) => {
+      const computationOffset = getRandomBigInt();
+      const claimInstruction = await getVerifyVoteClaimInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("verify_vote_claim", computationOffset)),
+        authority: pollAuthority.address,
+        pollId: claimPollId,
+        claimedChoice,
+      });
+      const finalizeSignature = await sendAndAwaitComputation(
+        voter,
+        [claimInstruction],
+        computationOffset
+      );
+      // Layout: [discriminator: 8][poll: 32][voter: 32][receipt: 32][claimed_choice: u8][matches: bool]
+      const voteClaimEvent = await getEventData(finalizeSignature, "VoteClaimEvent");
+      assert.deepEqual(
+        new Uint8Array(voteClaimEvent.subarray(72, 104)),
+        new Uint8Array(voterRecord.receipt)
+      );
+      assert.equal(voteClaimEvent.readUInt8(104), claimedChoice);
+      return voteClaimEvent.readUInt8(105) === 1;
+    };
+
+    assert.equal(await verifyClaim(VoteOption.NeoRobot), true);
+    assert.equal(await verifyClaim(VoteOption.FriendCom), false);
+  });
+
+  test("a reveal passed a results account writes the result to it", async () => {
+    const resultsPollId = 515;
+    await createPoll(pollAuthority, resultsPollId, "Stored results: worst tech of 2025?");
+    const voters = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voters[0], "Voter 1", pollAuthority, resultsPollId, VoteOption.NeoRobot);
+    await castVote(voters[1], "Voter 2", pollAuthority, resultsPollId, VoteOption.NeoRobot);
+    await castVote(voters[2], "Voter 3", pollAuthority, resultsPollId, VoteOption.FriendCom);
+
+    const pollAddress = await getPollAddress(pollAuthority.address, resultsPollId);
+    const { pda: resultsAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "results",
+      pollAddress,
+    ]);
+    const { winner, isTie, spoiled } = await revealResult(
+      pollAuthority,
+      resultsPollId,
+      pollAuthority,
+      resultsAddress
+    );
+    assert.equal(winner, VoteOption.NeoRobot);
+
+    const results = (await fetchResultsAccount(connection.rpc, resultsAddress)).data;
+    assert.equal(results.poll, pollAddress);
+    assert.equal(results.id, resultsPollId);
+    assert.equal(results.winner, winner);
+    assert.equal(results.isTie, isTie);
+    assert.equal(results.spoiled, spoiled);
+    assert.equal(results.totalVotes, 3n);
+    assert.ok(results.slot > 0n);
+  });
+
+  test("creating a second poll with an id already in use fails with PollIdInUse", async () => {
+    const takenPollId = 516;
+    await createPoll(pollAuthority, takenPollId, "Taken: worst tech of 2025?");
+
+    const computationOffset = getRandomBigInt();
+    const duplicateInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: takenPollId,
+      question: "Taken again: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+    await assertInstructionFails(pollAuthority, duplicateInstruction, "PollIdInUse");
+
+    // The existing poll is untouched
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, takenPollId)
+    );
+    assert.equal(poll.data.question, "Taken: worst tech of 2025?");
+  });
+
+  test("a vote's encrypted comment is kept apart from the tally and handed to the authority", async () => {
+    const commentPollId = 517;
+    await createPoll(pollAuthority, commentPollId, "Feedback: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, commentPollId);
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const cipher = new RescueCipher(voterKeys.sharedSecret);
+    const choiceNonce = randomBytes(16);
+    const choiceCiphertext = cipher.encrypt([BigInt(VoteOption.NeoRobot)], choiceNonce);
+    // Padded with zeroes to MAX_COMMENT_LENGTH bytes, and encrypted under its own nonce
+    const commentText = "too loud";
+    const commentBytes = new Uint8Array(MAX_COMMENT_LENGTH);
+    commentBytes.set(new TextEncoder().encode(commentText));
+    const commentNonce = randomBytes(16);
+    const commentCiphertexts = cipher.encrypt(
+      [...commentBytes].map((byte) => BigInt(byte)),
+      commentNonce
+    );
+    const { pda: voteCommentAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "comment",
+      pollAddress,
+      voter.address,
+    ]);
+
+    const buildCommentedVote = async (voteComment?: Address) => {
+      const computationOffset = getRandomBigInt();
+      const voteInstruction = await getVoteInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("vote", computationOffset)),
+        authority: pollAuthority.address,
+        pollId: commentPollId,
+        choice: [new Uint8Array(choiceCiphertext[0])],
+        voteEncryptionPubkey: [voterKeys.publicKey],
+        voteNonce: deserializeLE(choiceNonce),
+        scheme: EncScheme.X25519Rescue,
+        comment: {
+          nonce: deserializeLE(commentNonce),
+          ciphertexts: commentCiphertexts.map((ciphertext) => new Uint8Array(ciphertext)),
+        },
+        voteComment,
+      });
+      return { voteInstruction, computationOffset };
+    };
+
+    const { voteInstruction: uncommentedVote } = await buildCommentedVote();
+    await assertInstructionFails(voter, uncommentedVote, "MissingVoteComment");
+
+    const { voteInstruction, computationOffset } = await buildCommentedVote(voteCommentAddress);
+    await sendAndAwaitComputation(voter, [voteInstruction], computationOffset);
+
+    // Stands in for a key the poll authority holds offchain
+    const authorityKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const revealOffset = getRandomBigInt();
+    const revealCommentInstruction = await getRevealCommentInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_comment", revealOffset)),
+      id: commentPollId,
+      voter: voter.address,
+      recipient: [authorityKeys.publicKey],
+      recipientNonce: deserializeLE(randomBytes(16)),
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      pollAuthority,
+      [revealCommentInstruction],
+      revealOffset
+    );
+
+    // Layout: [discriminator: 8][poll: 32][voter: 32][encryption_key: 32][nonce: u128]
+    // [ciphertexts: 32 each]
+    const commentRevealEvent = await getEventData(finalizeSignature, "CommentRevealEvent");
+    const nonce = commentRevealEvent.subarray(104, 120);
+    const ciphertexts: Array<Array<number>> = [];
+    for (let offset = 120; offset < commentRevealEvent.length; offset += 32) {
+      ciphertexts.push([...commentRevealEvent.subarray(offset, offset + 32)]);
+    }
+    const revealedBytes = new RescueCipher(authorityKeys.sharedSecret).decrypt(ciphertexts, nonce);
+    const revealedText = new TextDecoder().decode(
+      Uint8Array.from(revealedBytes.map((byte) => Number(byte)))
+    );
+    assert.equal(revealedText, commentText);
+
+    // The comment plays no part in the result
+    const { winner } = await revealResult(pollAuthority, commentPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("an authority's polls can be listed from their AuthorityPollIndex", async () => {
+    const [organizer] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const pollIds = [518, 519, 520];
+    for (const id of pollIds) {
+      await createPoll(organizer, id, `Indexed ${id}: worst tech of 2025?`);
+    }
+    const pollAddresses = await Promise.all(
+      pollIds.map((id) => getPollAddress(organizer.address, id))
+    );
+
+    const { pda: pollIndexAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "poll_index",
+      organizer.address,
+    ]);
+    const pollIndex = await fetchAuthorityPollIndex(connection.rpc, pollIndexAddress);
+    assert.equal(pollIndex.data.authority, organizer.address);
+    assert.deepEqual(pollIndex.data.polls, pollAddresses);
+    assert.equal(pollIndex.data.unindexedPolls, 0);
+
+    // Closing a poll takes it off the list, keeping the others in order
+    await connection.sendTransactionFromInstructions({
+      feePayer: organizer,
+      instructions: [
+        await getFreezePollInstructionAsync({ authority: organizer, id: pollIds[1] }),
+        await getClosePollInstructionAsync({ authority: organizer, id: pollIds[1] }),
+      ],
+    });
+    const updatedPollIndex = await fetchAuthorityPollIndex(connection.rpc, pollIndexAddress);
+    assert.deepEqual(updatedPollIndex.data.polls, [pollAddresses[0], pollAddresses[2]]);
+  });
+
+  test("weight_scale keeps high-decimal token weights from saturating the counters", async () => {
+    const scaledPollId = 521;
+    const DECIMALS = 18;
+    const TEN_TOKENS = 10n * 10n ** BigInt(DECIMALS);
+    const auditorKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const weightMint = await connection.createTokenMint({
+      mintAuthority: pollAuthority,
+      decimals: DECIMALS,
+      name: "High-decimal governance",
+      symbol: "HDG",
+      uri: "https://example.com",
+      additionalMetadata: {},
+    });
+
+    // Only token-weighted polls can scale their weights
+    const unweightedComputationOffset = getRandomBigInt();
+    const unweightedScaledPoll = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", unweightedComputationOffset)),
+      id: scaledPollId,
+      question: "Scaled: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: { ...DEFAULT_POLL_SETTINGS, weightScale: DECIMALS },
+    });
+    await assertInstructionFails(pollAuthority, unweightedScaledPoll, "InvalidWeightScale");
+
+    await createPoll(
+      pollAuthority,
+      scaledPollId,
+      "Scaled: worst tech of 2025?",
+      OPTION_NAMES.length,
+      {
+        weightMint,
+        weightScale: DECIMALS,
+        encMode: EncMode.Shared,
+        tallyRecipient: auditorKeys.publicKey,
+      }
+    );
+
+    // 10 tokens each, 2 × 10^19 units between them: past u64::MAX unscaled
+    const [first, second, dust] = await connection.createWallets(3, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [voter, name, choice] of [
+      [first, "First holder", VoteOption.NeoRobot],
+      [second, "Second holder", VoteOption.NeoRobot],
+    ] as const) {
+      await connection.mintTokens(weightMint, pollAuthority, TEN_TOKENS, voter.address);
+      await castVote(
+        voter,
+        name,
+        pollAuthority,
+        scaledPollId,
+        choice,
+        await connection.getTokenAccountAddress(voter.address, weightMint, true)
+      );
+    }
+
+    // Less than one whole token weighs nothing once scaled
+    const almostOneToken = 10n ** BigInt(DECIMALS) - 1n;
+    await connection.mintTokens(weightMint, pollAuthority, almostOneToken, dust.address);
+    const { voteInstruction: dustVote } = await buildVote(
+      dust,
+      pollAuthority,
+      scaledPollId,
+      VoteOption.FriendCom,
+      await connection.getTokenAccountAddress(dust.address, weightMint, true)
+    );
+    await assertInstructionFails(dust, dustVote, "NoVotingWeight");
+
+    const tally = await revealTallyShared(pollAuthority, scaledPollId, auditorKeys.sharedSecret);
+    assert.equal(tally[VoteOption.NeoRobot], 20n);
+    // Total weight is stored right after the MAX_OPTIONS vote counters
+    assert.equal(tally[MAX_OPTIONS], 20n);
+  });
+
+  test("reveal_delta reports whether the lead changed since the last live reveal", async () => {
+    const livePollId = 522;
+    await createPoll(pollAuthority, livePollId, "Live: worst tech of 2025?");
+    const pollAddress = await getPollAddress(pollAuthority.address, livePollId);
+    const { pda: configAddress } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "config",
+    ]);
+
+    const buildRevealDelta = async () => {
+      const computationOffset = getRandomBigInt();
+      const revealDeltaInstruction = await getRevealDeltaInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("reveal_delta", computationOffset)),
+        id: livePollId,
+      });
+      return { revealDeltaInstruction, computationOffset };
+    };
+
+    const revealDelta = async () => {
+      // Live reveals share the reveal cooldown with reveal_result
+      const config = await fetchProgramConfig(connection.rpc, configAddress);
+      const poll = await fetchPoll(connection.rpc, pollAddress);
+      if (poll.data.lastRevealTs > 0n) {
+        await waitUntilPassed(poll.data.lastRevealTs + config.data.revealCooldown);
+      }
+      const { revealDeltaInstruction, computationOffset } = await buildRevealDelta();
+      const finalizeSignature = await sendAndAwaitComputation(
+        pollAuthority,
+        [revealDeltaInstruction],
+        computationOffset
+      );
+      // Layout: [discriminator: 8][poll: 32][previous_winner: Option<u8>][winner: u8][changed: bool]
+      const revealDeltaEvent = await getEventData(finalizeSignature, "RevealDeltaEvent");
+      const hasPreviousWinner = revealDeltaEvent.readUInt8(40) === 1;
+      const offset = hasPreviousWinner ? 42 : 41;
+      return {
+        previousWinner: hasPreviousWinner ? revealDeltaEvent.readUInt8(41) : null,
+        winner: revealDeltaEvent.readUInt8(offset),
+        changed: revealDeltaEvent.readUInt8(offset + 1) === 1,
+      };
+    };
+
+    const voters = await connection.createWallets(5, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    for (const [index, voter] of voters.slice(0, 2).entries()) {
+      const name = `Voter ${index + 1}`;
+      await castVote(voter, name, pollAuthority, livePollId, VoteOption.NeoRobot);
+    }
+    // The first live reveal has nothing to compare with, so always reports a change
+    assert.deepEqual(await revealDelta(), {
+      previousWinner: null,
+      winner: VoteOption.NeoRobot,
+      changed: true,
+    });
+    // So the lead can't be polled after every vote
+    const { revealDeltaInstruction: tooSoonInstruction } = await buildRevealDelta();
+    await assertInstructionFails(pollAuthority, tooSoonInstruction, "RevealTooSoon");
+
+    // The poll is still open, and the lead flips
+    for (const [index, voter] of voters.slice(2).entries()) {
+      const name = `Voter ${index + 3}`;
+      await castVote(voter, name, pollAuthority, livePollId, VoteOption.FriendCom);
+    }
+    assert.deepEqual(await revealDelta(), {
+      previousWinner: VoteOption.NeoRobot,
+      winner: VoteOption.FriendCom,
+      changed: true,
+    });
+
+    // With no new votes, nothing has changed
+    assert.deepEqual(await revealDelta(), {
+      previousWinner: VoteOption.FriendCom,
+      winner: VoteOption.FriendCom,
+      changed: false,
+    });
+  });
+
+  test("a receipt-free poll stores no receipt and only counts votes in batches", async () => {
+    const receiptFreePollId = 523;
+    await createPoll(pollAuthority, receiptFreePollId, "Receipt-free: worst tech of 2025?", undefined, {
+      receiptFree: true,
+    });
+    const pollAddress = await getPollAddress(pollAuthority.address, receiptFreePollId);
+
+    const choices = [VoteOption.NeoRobot, VoteOption.FriendCom];
+    const voters = await connection.createWallets(choices.length, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const voterRecordAddresses: Array<Address> = [];
+    for (const [index, voter] of voters.entries()) {
+      const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+      const cipher = new RescueCipher(voterKeys.sharedSecret);
+      const nonce = randomBytes(16);
+      const ciphertext = cipher.encrypt([BigInt(choices[index])], nonce);
+      await connection.sendTransactionFromInstructions({
+        feePayer: voter,
+        instructions: [
+          await getCommitVoteInstructionAsync({
+            payer: voter,
+            authority: pollAuthority.address,
+            pollId: receiptFreePollId,
+            choice: [new Uint8Array(ciphertext[0])],
+            voteEncryptionPubkey: [voterKeys.publicKey],
+            voteNonce: deserializeLE(nonce),
+            scheme: EncScheme.X25519Rescue,
+          }),
+        ],
+      });
+      const { pda: voterRecordAddress } = await connection.getPDAAndBump(
+        ELECTION_PROGRAM_ID,
+        ["voter", pollAddress, voter.address]
+      );
+      voterRecordAddresses.push(voterRecordAddress);
+
+      const voterRecord = (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+      assert.deepEqual(new Uint8Array(voterRecord.receipt), new Uint8Array(32));
+    }
+
+    // Counting one vote alone, or checking a claim about it, would tie the voter to their choice
+    const [firstVoter] = voters;
+    const finalizeOffset = getRandomBigInt();
+    const finalizeInstruction = await getFinalizeVoteInstructionAsync({
+      payer: firstVoter,
+      ...(await getQueueComputationAccounts("vote", finalizeOffset)),
+      authority: pollAuthority.address,
+      pollId: receiptFreePollId,
+    });
+    await assertInstructionFails(firstVoter, finalizeInstruction, "WrongPollType");
+    const claimOffset = getRandomBigInt();
+    const claimInstruction = await getVerifyVoteClaimInstructionAsync({
+      payer: firstVoter,
+      ...(await getQueueComputationAccounts("verify_vote_claim", claimOffset)),
+      authority: pollAuthority.address,
+      pollId: receiptFreePollId,
+      claimedChoice: choices[0],
+    });
+    await assertInstructionFails(firstVoter, claimInstruction, "WrongPollType");
+    const [lateVoter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const { voteInstruction } = await buildVote(
+      lateVoter,
+      pollAuthority,
+      receiptFreePollId,
+      VoteOption.NeoRobot
+    );
+    await assertInstructionFails(lateVoter, voteInstruction, "WrongPollType");
+
+    const batchOffset = getRandomBigInt();
+    const batchInstruction = await getVoteManyInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("vote_many", batchOffset)),
+      authority: pollAuthority.address,
+      pollId: receiptFreePollId,
+    });
+    const recordAccounts = voterRecordAddresses.map((address) => ({
+      address,
+      role: AccountRole.READONLY,
+    }));
+    await sendAndAwaitComputation(
+      pollAuthority,
+      [{ ...batchInstruction, accounts: [...batchInstruction.accounts, ...recordAccounts] }],
+      batchOffset
+    );
+
+    const poll = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(poll.data.totalVotes, BigInt(choices.length));
+    for (const voterRecordAddress of voterRecordAddresses) {
+      const voterRecord = (await fetchVoterRecord(connection.rpc, voterRecordAddress)).data;
+      assert.equal(voterRecord.counted, true);
+      assert.deepEqual(new Uint8Array(voterRecord.receipt), new Uint8Array(32));
+    }
+  });
+
+  test("debug_layout reports where a poll's encrypted fields are", async (context) => {
+    // debug_layout is only built with the debug feature, so the client only has it then
+    const client: Record<string, any> = await import("../dist/election-client/index.js");
+    if (!client.getDebugLayoutInstructionAsync) {
+      context.skip("Program built without the debug feature");
+      return;
+    }
+
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await client.getDebugLayoutInstructionAsync({
+          authority: pollAuthority.address,
+          id: pollId,
+        }),
+      ],
+    });
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+    const returnData = transaction?.meta?.returnData;
+    assert.ok(returnData, `No return data in transaction ${signature}`);
+    const layout = client
+      .getPollLayoutDecoder()
+      .decode(Buffer.from(returnData.data[0], "base64"));
+
+    // After the 8-byte discriminator and the bump
+    assert.equal(layout.voteCountsOffset, 9);
+    assert.equal(layout.voteCountsLength, 32 * 16);
+    // vote_counts, total_weight and spoiled
+    assert.equal(layout.pollTallyOffset, 9);
+    assert.equal(layout.pollTallyLength, 32 * 18);
+    // Then the id and the authority
+    assert.equal(layout.nonceOffset, 9 + 32 * 18 + 4 + 32);
+    assert.equal(layout.nonceLength, 16);
+    assert.equal(layout.matchesAccount, true);
+  });
+
+  test("a finalized poll keeps its result and rejects further changes", async () => {
+    const finalizedPollId = 440;
+    await createPoll(pollAuthority, finalizedPollId, "Final: worst tech of 2025?");
+
+    const [voter, lateVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, finalizedPollId, VoteOption.HumaneAIPIN);
+    await revealResult(pollAuthority, finalizedPollId);
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFinalizePollInstructionAsync({
+          authority: pollAuthority,
+          id: finalizedPollId,
+        }),
+      ],
+    });
+
+    const poll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, finalizedPollId)
+    );
+    assert.equal(poll.data.status, PollStatus.Finalized);
+    assert.equal(poll.data.winner, VoteOption.HumaneAIPIN);
+
+    const voteComputationOffset = getRandomBigInt();
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const nonce = randomBytes(16);
+    const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.NeoRobot)],
+      nonce
+    );
+    const lateVoteInstruction = await getVoteInstructionAsync({
+      payer: lateVoter,
+      ...(await getQueueComputationAccounts("vote", voteComputationOffset)),
+      authority: pollAuthority.address,
+      pollId: finalizedPollId,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(lateVoter, lateVoteInstruction, "PollFinalized");
+
+    const revealComputationOffset = getRandomBigInt();
+    const revealAgainInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", revealComputationOffset)),
+      authority: pollAuthority.address,
+      id: finalizedPollId,
+    });
+    await assertInstructionFails(pollAuthority, revealAgainInstruction, "PollFinalized");
+
+    const updateQuestionInstruction = await getUpdateQuestionInstructionAsync({
+      authority: pollAuthority,
+      id: finalizedPollId,
+      question: "Changed: worst tech of 2025?",
+    });
+    await assertInstructionFails(
+      pollAuthority,
+      updateQuestionInstruction,
+      "PollFinalized"
+    );
+
+    // Finalized, but the poll didn't opt into publishing its result cross-chain
+    const SYSTEM_PROGRAM_ADDRESS = address("11111111111111111111111111111111");
+    const postResultCrossChainInstruction = await getPostResultCrossChainInstructionAsync({
+      payer: pollAuthority,
+      authority: pollAuthority.address,
+      id: finalizedPollId,
+      // Cross-chain posting isn't set up on localnet, so these are never used
+      wormholeProgram: SYSTEM_PROGRAM_ADDRESS,
+      wormholeBridge: pollAuthority.address,
+      wormholeSequence: pollAuthority.address,
+      wormholeFeeCollector: pollAuthority.address,
+    });
+    await assertInstructionFails(
+      pollAuthority,
+      postResultCrossChainInstruction,
+      "NotCrossChain"
+    );
+  });
+
+  test("shared mode polls hand the whole tally to the recipient instead of revealing it", async () => {
+    const sharedPollId = 441;
+    // Stands in for a key held by an election committee
+    const committeeKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    await createPoll(
+      pollAuthority,
+      sharedPollId,
+      "Committee-counted: worst tech?",
+      OPTION_NAMES.length,
+      { encMode: EncMode.Shared, tallyRecipient: committeeKeys.publicKey }
+    );
+
+    const [alice, bob] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, sharedPollId, VoteOption.FriendCom);
+    await castVote(bob, "Bob", pollAuthority, sharedPollId, VoteOption.FriendCom);
+
+    const revealComputationOffset = getRandomBigInt();
+    const publicRevealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", revealComputationOffset)),
+      authority: pollAuthority.address,
+      id: sharedPollId,
+    });
+    await assertInstructionFails(pollAuthority, publicRevealInstruction, "WrongEncMode");
+
+    const [neoRobotVotes, humaneAIPINVotes, friendComVotes] = await revealTallyShared(
+      pollAuthority,
+      sharedPollId,
+      committeeKeys.sharedSecret
+    );
+    assert.deepEqual(
+      [neoRobotVotes, humaneAIPINVotes, friendComVotes],
+      [0n, 0n, 2n]
+    );
+  });
+
+  test("vote counters saturate rather than wrapping round", async () => {
+    const saturatingPollId = 442;
+    const U64_MAX = 2n ** 64n - 1n;
+    const auditorKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const weightMint = await connection.createTokenMint({
+      mintAuthority: pollAuthority,
+      decimals: 0,
+      name: "Saturating governance",
+      symbol: "SAT",
+      uri: "https://example.com",
+      additionalMetadata: {},
+    });
+    await createPoll(
+      pollAuthority,
+      saturatingPollId,
+      "Overflow: worst tech of 2025?",
+      OPTION_NAMES.length,
+      {
+        weightMint,
+        encMode: EncMode.Shared,
+        tallyRecipient: auditorKeys.publicKey,
+      }
+    );
+
+    // The whole supply votes twice by moving between wallets, pushing the counter past u64::MAX
+    const [first, second] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await connection.mintTokens(weightMint, pollAuthority, U64_MAX, first.address);
+    await castVote(
+      first,
+      "First holder",
+      pollAuthority,
+      saturatingPollId,
+      VoteOption.NeoRobot,
+      await connection.getTokenAccountAddress(first.address, weightMint, true)
+    );
+    await connection.transferTokens({
+      sender: first,
+      destination: second.address,
+      mintAddress: weightMint,
+      amount: U64_MAX,
+    });
+    await castVote(
+      second,
+      "Second holder",
+      pollAuthority,
+      saturatingPollId,
+      VoteOption.NeoRobot,
+      await connection.getTokenAccountAddress(second.address, weightMint, true)
+    );
+
+    const tally = await revealTallyShared(
+      pollAuthority,
+      saturatingPollId,
+      auditorKeys.sharedSecret
+    );
+    assert.equal(tally[VoteOption.NeoRobot], U64_MAX);
+    // Total weight is stored right after the MAX_OPTIONS vote counters
+    assert.equal(tally[MAX_OPTIONS], U64_MAX);
+  });
+
+  test("nothing can be created or changed while the program is paused", async () => {
+    const pausedPollId = 527;
+    await createPoll(pollAuthority, pausedPollId, "Paused: best tech of 2025?");
+
+    const setPaused = async (paused: boolean) => {
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getSetPausedInstructionAsync({ admin: pollAuthority, paused }),
+        ],
+      });
+    };
+
+    await setPaused(true);
+    try {
+      const computationOffset = getRandomBigInt();
+      const createPollInstruction = await getCreatePollInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+        id: 436,
+        question: "Paused: worst tech of 2025?",
+        numOptions: OPTION_NAMES.length,
+        nonce: deserializeLE(randomBytes(16)),
+        settings: DEFAULT_POLL_SETTINGS,
+      });
+      await assertInstructionFails(
+        pollAuthority,
+        createPollInstruction,
+        "ProgramPaused"
+      );
+      // Instructions that change an existing poll are refused too
+      await assertInstructionFails(
+        pollAuthority,
+        await getFreezePollInstructionAsync({ authority: pollAuthority, id: pausedPollId }),
+        "ProgramPaused"
+      );
+    } finally {
+      // Unpause even if the assertion fails, so the other tests can still run
+      await setPaused(false);
+    }
+  });
+
+  test("polls can derive nonces so clients don't have to manage them", async () => {
+    const derivedPollId = 444;
+    await createPoll(
+      pollAuthority,
+      derivedPollId,
+      "Derived: worst tech of 2025?",
+      OPTION_NAMES.length,
+      { deriveNonces: true }
+    );
+    const pollAddress = await getPollAddress(pollAuthority.address, derivedPollId);
+
+    const voters = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const voteNonces: Array<bigint> = [];
+    for (const voter of voters) {
+      // Encrypt with the nonce the program will derive from the poll's current counter
+      const poll = await fetchPoll(connection.rpc, pollAddress);
+      const nonce = deriveNonce(pollAddress, poll.data.nonceCounter);
+
+      const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+      const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+        [BigInt(VoteOption.NeoRobot)],
+        nonce
+      );
+      const computationOffset = getRandomBigInt();
+      const voteInstruction = await getVoteInstructionAsync({
+        payer: voter,
+        ...(await getQueueComputationAccounts("vote", computationOffset)),
+        authority: pollAuthority.address,
+        pollId: derivedPollId,
+        choice: [new Uint8Array(ciphertext[0])],
+        voteEncryptionPubkey: [voterKeys.publicKey],
+        // Ignored, since the program derives the nonce
+        voteNonce: 0n,
+        scheme: EncScheme.X25519Rescue,
+        comment: null,
+      });
+      await sendAndAwaitComputation(voter, [voteInstruction], computationOffset);
+
+      const { pda: voterRecordAddress } = await connection.getPDAAndBump(
+        ELECTION_PROGRAM_ID,
+        ["voter", pollAddress, voter.address]
+      );
+      const voterRecord = await fetchVoterRecord(connection.rpc, voterRecordAddress);
+      assert.equal(voterRecord.data.voteNonce, deserializeLE(nonce));
+      voteNonces.push(voterRecord.data.voteNonce);
+    }
+
+    assert.notEqual(voteNonces[0], voteNonces[1]);
+    const { winner } = await revealResult(pollAuthority, derivedPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("create_poll_if_needed can be retried without failing or changing the poll", async () => {
+    const retriedPollId = 461;
+    const firstComputationOffset = getRandomBigInt();
+    const firstInstruction = await getCreatePollIfNeededInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", firstComputationOffset)),
+      id: retriedPollId,
+      question: "Retried: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+    await sendAndAwaitComputation(pollAuthority, [firstInstruction], firstComputationOffset);
+
+    const pollAddress = await getPollAddress(pollAuthority.address, retriedPollId);
+    const pollBeforeRetry = await fetchPoll(connection.rpc, pollAddress);
+
+    // The retry succeeds, but doesn't touch the existing poll
+    const retryComputationOffset = getRandomBigInt();
+    const retryInstruction = await getCreatePollIfNeededInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", retryComputationOffset)),
+      id: retriedPollId,
+      question: "Changed: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [retryInstruction],
+    });
+
+    const pollAfterRetry = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(pollAfterRetry.data.question, "Retried: worst tech of 2025?");
+    assert.equal(pollAfterRetry.data.nonce, pollBeforeRetry.data.nonce);
+    assert.deepEqual(pollAfterRetry.data.voteCounts, pollBeforeRetry.data.voteCounts);
+  });
+
+  test("polls can be preallocated ahead of time and activated later", async () => {
+    const preallocatedPollId = 446;
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getPreallocatePollInstructionAsync({
+          payer: pollAuthority,
+          id: preallocatedPollId,
+        }),
+      ],
+    });
+
+    const pollAddress = await getPollAddress(pollAuthority.address, preallocatedPollId);
+    const preallocatedPoll = await fetchPoll(connection.rpc, pollAddress);
+    assert.equal(preallocatedPoll.data.status, PollStatus.Created);
+
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    const earlyComputationOffset = getRandomBigInt();
+    const earlyVoterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const earlyNonce = randomBytes(16);
+    const earlyCiphertext = new RescueCipher(earlyVoterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.NeoRobot)],
+      earlyNonce
+    );
+    const earlyVoteInstruction = await getVoteInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote", earlyComputationOffset)),
+      authority: pollAuthority.address,
+      pollId: preallocatedPollId,
+      choice: [new Uint8Array(earlyCiphertext[0])],
+      voteEncryptionPubkey: [earlyVoterKeys.publicKey],
+      voteNonce: deserializeLE(earlyNonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(voter, earlyVoteInstruction, "PollNotActivated");
+
+    const computationOffset = getRandomBigInt();
+    const activatePollInstruction = await getActivatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: preallocatedPollId,
+      question: "Scheduled: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+    await sendAndAwaitComputation(
+      pollAuthority,
+      [activatePollInstruction],
+      computationOffset
+    );
+
+    await castVote(voter, "Voter", pollAuthority, preallocatedPollId, VoteOption.NeoRobot);
+    const { winner } = await revealResult(pollAuthority, preallocatedPollId);
+    assert.equal(winner, VoteOption.NeoRobot);
+  });
+
+  test("a preallocated poll that was never activated can be closed", async () => {
+    const unusedPollId = 526;
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getPreallocatePollInstructionAsync({
+          payer: pollAuthority,
+          id: unusedPollId,
+        }),
+      ],
+    });
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getClosePollInstructionAsync({
+          authority: pollAuthority,
+          id: unusedPollId,
+        }),
+      ],
+    });
+    const closedPoll = await connection.rpc
+      .getAccountInfo(await getPollAddress(pollAuthority.address, unusedPollId))
+      .send();
+    assert.equal(closedPoll.value, null);
+  });
+
+  test("polls can pay for faster computations with a higher priority", async () => {
+    const priorityPollIds = [447, 448];
+    const priorities = [0, 10];
+    for (const [index, priorityPollId] of priorityPollIds.entries()) {
+      await createPoll(
+        pollAuthority,
+        priorityPollId,
+        "Priority: worst tech of 2025?",
+        OPTION_NAMES.length,
+        { computationPriority: priorities[index] }
+      );
+      const poll = await fetchPoll(
+        connection.rpc,
+        await getPollAddress(pollAuthority.address, priorityPollId)
+      );
+      assert.equal(poll.data.settings.computationPriority, priorities[index]);
+
+      // The poll's computations are queued with its priority
+      const [voter] = await connection.createWallets(1, {
+        airdropAmount: lamports(1_000_000_000n),
+      });
+      await castVote(voter, "Voter", pollAuthority, priorityPollId, VoteOption.FriendCom);
+      const { winner } = await revealResult(pollAuthority, priorityPollId);
+      assert.equal(winner, VoteOption.FriendCom);
+    }
+
+    const computationOffset = getRandomBigInt();
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: 449,
+      question: "Priority: worst tech of 2025?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: { ...DEFAULT_POLL_SETTINGS, computationPriority: 11 },
+    });
+
+    await assertInstructionFails(
+      pollAuthority,
+      createPollInstruction,
+      "InvalidComputationPriority"
+    );
+  });
+
+  test("a poll must be frozen before it's revealed, and a frozen poll rejects votes", async () => {
+    const frozenPollId = 452;
+    await createPoll(pollAuthority, frozenPollId, "Frozen: worst tech of 2025?");
+
+    const [voter, lateVoter] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, frozenPollId, VoteOption.FriendCom);
+
+    const earlyComputationOffset = getRandomBigInt();
+    const earlyRevealInstruction = await getRevealResultInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_result", earlyComputationOffset)),
+      authority: pollAuthority.address,
+      id: frozenPollId,
+    });
+    await assertInstructionFails(pollAuthority, earlyRevealInstruction, "PollNotFrozen");
+
+    const freezeSignature = await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({
+          authority: pollAuthority,
+          id: frozenPollId,
+        }),
+      ],
+    });
+    const pollFrozenEvent = await getEventData(freezeSignature, "PollFrozenEvent");
+    // Layout: [discriminator: 8][poll: 32][total_votes: u64]
+    assert.equal(pollFrozenEvent.readBigUInt64LE(40), 1n);
+
+    const voteComputationOffset = getRandomBigInt();
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const nonce = randomBytes(16);
+    const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.NeoRobot)],
+      nonce
+    );
+    const lateVoteInstruction = await getVoteInstructionAsync({
+      payer: lateVoter,
+      ...(await getQueueComputationAccounts("vote", voteComputationOffset)),
+      authority: pollAuthority.address,
+      pollId: frozenPollId,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(lateVoter, lateVoteInstruction, "PollFrozen");
+
+    const { winner } = await revealResult(pollAuthority, frozenPollId);
+    assert.equal(winner, VoteOption.FriendCom);
+  });
+
+  test("the winner can be revealed privately to a recipient of the authority's choice", async () => {
+    const sealedPollId = 460;
+    await createPoll(pollAuthority, sealedPollId, "Audited: worst tech of 2025?");
+    const [alice, bob] = await connection.createWallets(2, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(alice, "Alice", pollAuthority, sealedPollId, VoteOption.HumaneAIPIN);
+    await castVote(bob, "Bob", pollAuthority, sealedPollId, VoteOption.HumaneAIPIN);
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getFreezePollInstructionAsync({
+          authority: pollAuthority,
+          id: sealedPollId,
+        }),
+      ],
+    });
+
+    // Stands in for an auditor's key
+    const auditorKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const computationOffset = getRandomBigInt();
+    const revealToInstruction = await getRevealToInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("reveal_to", computationOffset)),
+      authority: pollAuthority.address,
+      id: sealedPollId,
+      recipient: [auditorKeys.publicKey],
+      recipientNonce: deserializeLE(randomBytes(16)),
+    });
+    await sendAndAwaitComputation(pollAuthority, [revealToInstruction], computationOffset);
+
+    const { pda: sealedResultAddress } = await connection.getPDAAndBump(
+      ELECTION_PROGRAM_ID,
+      [
+        "sealed_result",
+        await getPollAddress(pollAuthority.address, sealedPollId),
+        auditorKeys.publicKey,
+      ]
+    );
+    const sealedResult = await fetchSealedResult(connection.rpc, sealedResultAddress);
+    assert.equal(sealedResult.data.sealed, true);
+    assert.deepEqual(new Uint8Array(sealedResult.data.recipient), auditorKeys.publicKey);
+
+    // Only the recipient's shared secret decrypts the stored winner
+    const [winner] = new RescueCipher(auditorKeys.sharedSecret).decrypt(
+      [[...sealedResult.data.winner]],
+      serializeLE(sealedResult.data.nonce, 16)
+    );
+    assert.equal(winner, BigInt(VoteOption.HumaneAIPIN));
+  });
+
+  test("the spread shows whether a vote was concentrated or split", async () => {
+    const spreadPolls = [
+      // Concentrated on one option
+      { id: 462, votesPerOption: [10, 1, 1], spread: 9n },
+      // Split evenly
+      { id: 463, votesPerOption: [4, 4, 4], spread: 0n },
+    ];
+
+    for (const spreadPoll of spreadPolls) {
+      await createPoll(pollAuthority, spreadPoll.id, "Spread: worst tech of 2025?");
+      const voters = await connection.createWallets(
+        spreadPoll.votesPerOption.reduce((total, votes) => total + votes, 0),
+        { airdropAmount: lamports(1_000_000_000n) }
+      );
+      let voterIndex = 0;
+      for (const [option, votes] of spreadPoll.votesPerOption.entries()) {
+        for (let vote = 0; vote < votes; vote++) {
+          await castVote(
+            voters[voterIndex],
+            `Voter ${voterIndex}`,
+            pollAuthority,
+            spreadPoll.id,
+            option
+          );
+          voterIndex++;
+        }
+      }
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getFreezePollInstructionAsync({
+            authority: pollAuthority,
+            id: spreadPoll.id,
+          }),
+        ],
+      });
+
+      const computationOffset = getRandomBigInt();
+      const revealSpreadInstruction = await getRevealSpreadInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("reveal_spread", computationOffset)),
+        id: spreadPoll.id,
+      });
+      const finalizeSignature = await sendAndAwaitComputation(
+        pollAuthority,
+        [revealSpreadInstruction],
+        computationOffset
+      );
+
+      // Layout: [discriminator: 8][spread: u64]
+      const spreadEvent = await getEventData(finalizeSignature, "SpreadEvent");
+      assert.equal(spreadEvent.readBigUInt64LE(8), spreadPoll.spread);
+    }
+  });
+
+  test("a referendum passes only if its yes share reaches the threshold", async () => {
+    const NO = 0;
+    const YES = 1;
+    const referendums = [
+      // Exactly 60% yes
+      { id: 453, passThresholdPct: 60, votes: [YES, YES, YES, NO, NO], passed: true, yesPct: 60 },
+      // 66.7% yes, just below 67%
+      { id: 454, passThresholdPct: 67, votes: [YES, YES, NO], passed: false, yesPct: 66 },
+      // 66.7% yes, just above 66%
+      { id: 455, passThresholdPct: 66, votes: [YES, YES, NO], passed: true, yesPct: 66 },
+      // Nobody voted
+      { id: 456, passThresholdPct: 60, votes: [], passed: false, yesPct: 0 },
+    ];
+
+    for (const referendum of referendums) {
+      await createPoll(pollAuthority, referendum.id, "Referendum: ban AI pins?", 2, {
+        passThresholdPct: referendum.passThresholdPct,
+      });
+      const voters = await connection.createWallets(referendum.votes.length, {
+        airdropAmount: lamports(1_000_000_000n),
+      });
+      for (const [index, vote] of referendum.votes.entries()) {
+        await castVote(voters[index], `Voter ${index}`, pollAuthority, referendum.id, vote);
+      }
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [
+          await getFreezePollInstructionAsync({
+            authority: pollAuthority,
+            id: referendum.id,
+          }),
+        ],
+      });
+
+      const computationOffset = getRandomBigInt();
+      const revealReferendumInstruction = await getRevealReferendumInstructionAsync({
+        payer: pollAuthority,
+        ...(await getQueueComputationAccounts("reveal_referendum", computationOffset)),
+        id: referendum.id,
+      });
+      const finalizeSignature = await sendAndAwaitComputation(
+        pollAuthority,
+        [revealReferendumInstruction],
+        computationOffset
+      );
+
+      // Layout: [discriminator: 8][passed: bool][yes_pct: u8]
+      const referendumEvent = await getEventData(finalizeSignature, "ReferendumEvent");
+      assert.equal(referendumEvent[8] === 1, referendum.passed);
+      assert.equal(referendumEvent[9], referendum.yesPct);
+    }
+
+    // Referendums are yes/no, so they need exactly two options
+    const computationOffset = getRandomBigInt();
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: 457,
+      question: "Referendum: ban AI pins?",
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: { ...DEFAULT_POLL_SETTINGS, passThresholdPct: 60 },
+    });
+
+    await assertInstructionFails(pollAuthority, createPollInstruction, "InvalidReferendum");
+  });
+
+  test("polls can be cloned to a new id, keeping their votes", async () => {
+    const sourcePollId = 450;
+    const clonePollId = 451;
+    await createPoll(
+      pollAuthority,
+      sourcePollId,
+      "Clone: worst tech of 2025?",
+      OPTION_NAMES.length
+    );
+    const [voter] = await connection.createWallets(1, {
+      airdropAmount: lamports(1_000_000_000n),
+    });
+    await castVote(voter, "Voter", pollAuthority, sourcePollId, VoteOption.HumaneAIPIN);
+
+    await connection.sendTransactionFromInstructions({
+      feePayer: pollAuthority,
+      instructions: [
+        await getClonePollInstructionAsync({
+          authority: pollAuthority,
+          sourceId: sourcePollId,
+          newId: clonePollId,
+        }),
+      ],
+    });
+
+    const clonedPoll = await fetchPoll(
+      connection.rpc,
+      await getPollAddress(pollAuthority.address, clonePollId)
+    );
+    assert.equal(clonedPoll.data.id, clonePollId);
+    assert.equal(clonedPoll.data.question, "Clone: worst tech of 2025?");
+    assert.equal(clonedPoll.data.totalVotes, 1n);
+
+    // The source's voter records don't carry over, so the clone can't be voted on
+    const computationOffset = getRandomBigInt();
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const nonce = randomBytes(16);
+    const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.HumaneAIPIN)],
+      nonce
+    );
+    const voteInstruction = await getVoteInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: clonePollId,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(voter, voteInstruction, "VotingClosed");
+
+    const { winner } = await revealResult(pollAuthority, clonePollId);
+    assert.equal(winner, VoteOption.HumaneAIPIN);
+  });
+
+  test("an underfunded voter gets a clear error instead of a failed computation", async () => {
+    const underfundedPollId = 464;
+    await createPoll(pollAuthority, underfundedPollId, "Underfunded: worst tech of 2025?");
+
+    // Enough for the voter record's rent, but not for the computation
+    const [underfundedVoter] = await connection.createWallets(1, {
+      airdropAmount: lamports(5_000_000n),
+    });
+    const computationOffset = getRandomBigInt();
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const nonce = randomBytes(16);
+    const ciphertext = new RescueCipher(voterKeys.sharedSecret).encrypt(
+      [BigInt(VoteOption.NeoRobot)],
+      nonce
+    );
+    const voteInstruction = await getVoteInstructionAsync({
+      payer: underfundedVoter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: pollAuthority.address,
+      pollId: underfundedPollId,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+    });
+    await assertInstructionFails(underfundedVoter, voteInstruction, "InsufficientFunds");
+  });
+
+  test("creating a poll with a question that's too long fails", async () => {
+    const computationOffset = getRandomBigInt();
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: 423,
+      question: "Q".repeat(MAX_QUESTION_LENGTH + 1),
+      numOptions: OPTION_NAMES.length,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+
+    await assertInstructionFails(
+      pollAuthority,
+      createPollInstruction,
+      "QuestionTooLong"
+    );
+  });
+
+  test("creating a poll with too many options fails", async () => {
+    const computationOffset = getRandomBigInt();
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: pollAuthority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id: 424,
+      question: "Too many options?",
+      numOptions: MAX_OPTIONS + 1,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: DEFAULT_POLL_SETTINGS,
+    });
+
+    await assertInstructionFails(
+      pollAuthority,
+      createPollInstruction,
+      "TooManyOptions"
+    );
+  });
+
+  /**
+   * Builds the Arcium accounts every queued computation needs.
+   *
+   * @param circuitName - The name of the circuit the computation runs
+   * @param computationOffset - The offset identifying this computation
+   * @returns The accounts shared by every `queue_computation` instruction
+   */
+  const getQueueComputationAccounts = async (
+    circuitName: string,
+    computationOffset: bigint
+  ) => {
+    return {
+      computationAccount: await getComputationAccountAddress(
+        connection,
+        arciumClusterOffset,
+        computationOffset
+      ),
+      clusterAccount: await getClusterAccountAddress(
+        connection,
+        arciumClusterOffset
+      ),
+      mxeAccount: await getMXEAccountAddress(connection, ELECTION_PROGRAM_ID),
+      mempoolAccount: await getMempoolAccountAddress(
+        connection,
+        arciumClusterOffset
+      ),
+      executingPool: await getExecutingPoolAccountAddress(
+        connection,
+        arciumClusterOffset
+      ),
+      compDefAccount: await getComputationDefinitionAccountAddress(
+        connection,
+        ELECTION_PROGRAM_ID,
+        getComputationDefinitionAccountOffset(circuitName)
+      ),
+      computationOffset,
+    };
+  };
+
+  /**
+   * Derives the poll PDA from the same seeds the program uses.
+   *
+   * @param authority - The poll authority's address
+   * @param id - The poll ID
+   * @returns The poll account address
+   */
+  const getPollAddress = async (
+    authority: Address,
+    id: number
+  ): Promise<Address> => {
+    const { pda } = await connection.getPDAAndBump(ELECTION_PROGRAM_ID, [
+      "poll",
+      authority,
+      serializeLE(BigInt(id), 4),
+    ]);
+    return pda;
+  };
+
+  /**
+   * Sends instructions and waits for the computation they queued to finalize.
+   *
+   * @param feePayer - The signer paying for the transaction
+   * @param instructions - The instructions to send
+   * @param computationOffset - The offset of the computation the instructions queue
+   * @returns The signature of the transaction that finalized the computation (and ran its callback)
+   */
+  const sendAndAwaitComputation = async (
+    feePayer: KeyPairSigner,
+    instructions: Array<Instruction>,
+    computationOffset: bigint
+  ): Promise<string> => {
+    await connection.sendTransactionFromInstructions({
+      feePayer,
+      instructions,
+      skipPreflight: true,
+    });
+
+    return awaitComputationFinalization(
+      computationOffset,
+      ELECTION_PROGRAM_ID,
+      "confirmed"
+    );
+  };
+
+  /**
+   * Creates a poll and waits for its encrypted vote counters to be initialized.
+   *
+   * @param authority - The poll authority (also the payer)
+   * @param id - The poll ID
+   * @param question - The poll question
+   * @param numOptions - How many options voters can choose between
+   * @param settings - Overrides for the default poll settings
+   */
+  const createPoll = async (
+    authority: KeyPairSigner,
+    id: number,
+    question: string,
+    numOptions: number = OPTION_NAMES.length,
+    settings: Partial<PollSettingsArgs> = {}
+  ) => {
+    const computationOffset = getRandomBigInt();
+
+    const createPollInstruction = await getCreatePollInstructionAsync({
+      payer: authority,
+      ...(await getQueueComputationAccounts("create_poll", computationOffset)),
+      id,
+      question,
+      numOptions,
+      nonce: deserializeLE(randomBytes(16)),
+      settings: { ...DEFAULT_POLL_SETTINGS, ...settings },
+    });
+
+    await sendAndAwaitComputation(
+      authority,
+      [createPollInstruction],
+      computationOffset
+    );
+  };
+
+  /**
+   * Encrypts a choice with a fresh client-side key and builds the vote instruction for it.
+   *
+   * @param voter - The voter (also the payer)
+   * @param authority - The poll authority
+   * @param id - The poll ID
+   * @param choice - The plaintext choice to encrypt
+   * @param weightTokenAccount - The voter's token account, for token-weighted polls
+   * @param feeVault - The poll's fee vault, for polls with a vote fee
+   * @returns The instruction, its computation offset, and the encrypted choice and the key
+   * and nonce it was encrypted with
+   */
+  const buildVote = async (
+    voter: KeyPairSigner,
+    authority: KeyPairSigner,
+    id: number,
+    choice: number,
+    weightTokenAccount?: Address,
+    feeVault?: Address
+  ) => {
+    const voterKeys = await makeClientSideKeys(ELECTION_PROGRAM_ID);
+    const cipher = new RescueCipher(voterKeys.sharedSecret);
+
+    const plaintext = [BigInt(choice)];
+    const nonce = randomBytes(16);
+    const ciphertext = cipher.encrypt(plaintext, nonce);
+
+    const computationOffset = getRandomBigInt();
+
+    const voteInstruction = await getVoteInstructionAsync({
+      payer: voter,
+      ...(await getQueueComputationAccounts("vote", computationOffset)),
+      authority: authority.address,
+      pollId: id,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+      scheme: EncScheme.X25519Rescue,
+      comment: null,
+      weightTokenAccount,
+      feeVault,
+    });
+
+    return {
+      voteInstruction,
+      computationOffset,
+      choice: [new Uint8Array(ciphertext[0])],
+      voteEncryptionPubkey: [voterKeys.publicKey],
+      voteNonce: deserializeLE(nonce),
+    };
+  };
+
+  /**
+   * Encrypts a choice with a fresh client-side key and casts it.
+   *
+   * @param voter - The voter (also the payer)
+   * @param voterName - Human-readable name for logging
+   * @param authority - The poll authority
+   * @param id - The poll ID
+   * @param choice - The plaintext choice to encrypt
+   * @param weightTokenAccount - The voter's token account, for token-weighted polls
+   * @param feeVault - The poll's fee vault, for polls with a vote fee
+   * @returns The encrypted choice and the key and nonce it was encrypted with
+   */
+  const castVote = async (
+    voter: KeyPairSigner,
+    voterName: string,
+    authority: KeyPairSigner,
+    id: number,
+    choice: number,
+    weightTokenAccount?: Address,
+    feeVault?: Address
+  ) => {
+    const { voteInstruction, computationOffset, ...encryptedVote } = await buildVote(
+      voter,
+      authority,
+      id,
+      choice,
+      weightTokenAccount,
+      feeVault
+    );
+
+    await sendAndAwaitComputation(voter, [voteInstruction], computationOffset);
+
+    console.log(
+      `🗳️  ${voterName} voted ${getOptionName(choice)} (${choice}) for poll ${id}`
+    );
+
+    return encryptedVote;
+  };
+
+  /**
+   * Reveals the winner of a poll and waits for the reveal computation to finalize.
+   *
+   * @param authority - The poll authority
+   * @param id - The poll ID
+   * @param revealer - Who reveals (and pays), the authority unless the poll allows public reveals
+   * @param resultsAccount - The poll's `ResultsAccount`, for the reveal to write the result to
+   * @returns The winning option, whether it won a tie, how many votes were spoiled,
+   * the winner's count if the poll reveals it, and the bitmask of options that reached
+   * their quota
+   */
+  const revealResult = async (
+    authority: KeyPairSigner,
+    id: number,
+    revealer: KeyPairSigner = authority,
+    resultsAccount?: Address
+  ) => {
+    // Only frozen or closed polls can be revealed. Public reveals happen after voting closes.
+    const poll = await fetchPoll(connection.rpc, await getPollAddress(authority.address, id));
+    if (revealer === authority && poll.data.status === PollStatus.Open) {
+      await connection.sendTransactionFromInstructions({
+        feePayer: authority,
+        instructions: [await getFreezePollInstructionAsync({ authority, id })],
+      });
+    }
+
+    const computationOffset = getRandomBigInt();
+
+    const revealResultInstruction = await getRevealResultInstructionAsync({
+      payer: revealer,
+      ...(await getQueueComputationAccounts("reveal_result", computationOffset)),
+      authority: authority.address,
+      id,
+      resultsAccount,
+    });
+
+    const finalizeSignature = await sendAndAwaitComputation(
+      revealer,
+      [revealResultInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][output: u8][is_tie: bool][spoiled: u64]
+    // [winner_count: Option<u64>][full_options: u16]
+    const revealResultEvent = await getEventData(
+      finalizeSignature,
+      "RevealResultEvent"
+    );
+    const hasWinnerCount = revealResultEvent[18] === 1;
+    return {
+      winner: revealResultEvent[8],
+      isTie: revealResultEvent[9] === 1,
+      spoiled: revealResultEvent.readBigUInt64LE(10),
+      winnerCount: hasWinnerCount ? revealResultEvent.readBigUInt64LE(19) : null,
+      fullOptions: revealResultEvent.readUInt16LE(hasWinnerCount ? 27 : 19),
+    };
+  };
+
+  /**
+   * Hands a shared mode poll's tally to its recipient and decrypts it.
+   *
+   * @param authority - The poll authority
+   * @param id - The poll ID
+   * @param recipientSharedSecret - The shared secret of the poll's `tallyRecipient` key
+   * @returns The decrypted tally: each option's count, then the total weight and spoiled count
+   */
+  const revealTallyShared = async (
+    authority: KeyPairSigner,
+    id: number,
+    recipientSharedSecret: Uint8Array
+  ): Promise<Array<bigint>> => {
+    const computationOffset = getRandomBigInt();
+    const revealTallySharedInstruction = await getRevealTallySharedInstructionAsync({
+      payer: authority,
+      ...(await getQueueComputationAccounts("reveal_tally_shared", computationOffset)),
+      id,
+      recipientNonce: deserializeLE(randomBytes(16)),
+    });
+    const finalizeSignature = await sendAndAwaitComputation(
+      authority,
+      [revealTallySharedInstruction],
+      computationOffset
+    );
+
+    // Layout: [discriminator: 8][poll: 32][encryption_key: 32][nonce: u128][ciphertexts: 32 each]
+    const sharedTallyEvent = await getEventData(finalizeSignature, "SharedTallyEvent");
+    const nonce = sharedTallyEvent.subarray(72, 88);
+    const ciphertexts: Array<Array<number>> = [];
+    for (let offset = 88; offset < sharedTallyEvent.length; offset += 32) {
+      ciphertexts.push([...sharedTallyEvent.subarray(offset, offset + 32)]);
+    }
+    return new RescueCipher(recipientSharedSecret).decrypt(ciphertexts, nonce);
+  };
+
+  /**
+   * Computes the nonce a poll with `deriveNonces` hands out next, matching `Poll::derive_nonce`.
+   *
+   * @param pollAddress - The poll's address
+   * @param nonceCounter - The poll's current `nonceCounter`
+   * @returns The 16-byte nonce
+   */
+  const deriveNonce = (pollAddress: Address, nonceCounter: bigint): Buffer => {
+    const NONCE_LENGTH = 16;
+    const counterBytes = Buffer.alloc(8);
+    counterBytes.writeBigUInt64LE(nonceCounter);
+    return createHash("sha256")
+      .update("nonce")
+      .update(getAddressEncoder().encode(pollAddress))
+      .update(counterBytes)
+      .digest()
+      .subarray(0, NONCE_LENGTH);
+  };
+
+  /**
+   * Finds an Anchor event emitted by a transaction.
+   *
+   * @param signature - The transaction signature
+   * @param eventName - The event struct name, e.g. "AggregateEvent"
+   * @returns The raw event data, starting with its 8-byte discriminator
+   */
+  const getEventData = async (
+    signature: string,
+    eventName: string
+  ): Promise<Buffer> => {
+    // Anchor event discriminators are the first 8 bytes of sha256("event:<EventName>")
+    const EVENT_DISCRIMINATOR_LENGTH = 8;
+    const discriminator = createHash("sha256")
+      .update(`event:${eventName}`)
+      .digest()
+      .subarray(0, EVENT_DISCRIMINATOR_LENGTH);
+
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+
+    const eventData = parseAnchorEventFromLogs(
+      transaction?.meta?.logMessages ?? [],
+      new Uint8Array(discriminator)
+    );
+    assert.ok(eventData, `${eventName} not found in transaction ${signature}`);
+    return eventData;
+  };
+
+  /**
+   * Finds an event emitted with `emit_cpi!` in a transaction's inner instructions.
+   *
+   * @param signature - The transaction signature
+   * @param eventName - The event's name, e.g. "VoteEvent"
+   * @returns The event data, starting with its discriminator, as `getEventData` returns it
+   */
+  const getCpiEventData = async (
+    signature: string,
+    eventName: string
+  ): Promise<Buffer> => {
+    const discriminator = createHash("sha256")
+      .update(`event:${eventName}`)
+      .digest()
+      .subarray(0, 8);
+
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+
+    const eventData = parseAnchorCpiEvent(
+      transaction?.meta?.innerInstructions ?? [],
+      new Uint8Array(discriminator)
+    );
+    assert.ok(eventData, `${eventName} CPI event not found in transaction ${signature}`);
+    return eventData;
+  };
+
+  /**
+   * Calls `get_program_stats` and decodes the stats from the transaction's return data.
+   *
+   * @param payer - Who pays for the transaction
+   * @returns The deployment's poll, reveal and vote counters
+   */
+  const getProgramStats = async (payer: KeyPairSigner) => {
+    const signature = await connection.sendTransactionFromInstructions({
+      feePayer: payer,
+      instructions: [await getGetProgramStatsInstructionAsync({})],
+    });
+
+    const transaction = await connection.rpc
+      .getTransaction(signature as Parameters<typeof connection.rpc.getTransaction>[0], {
+        commitment: "confirmed",
+        encoding: "json",
+        maxSupportedTransactionVersion: 0,
+      })
+      .send();
+    const returnData = transaction?.meta?.returnData;
+    assert.ok(returnData, `No return data in transaction ${signature}`);
+    return getProgramStatsDecoder().decode(Buffer.from(returnData.data[0], "base64"));
+  };
+
+  /**
+   * Waits until the validator's clock has (comfortably) passed a unix timestamp.
+   *
+   * @param unixTimestamp - The unix timestamp, in seconds, to wait for
+   */
+  const waitUntilPassed = async (unixTimestamp: bigint) => {
+    // The validator's clock can lag the local clock by a moment
+    const CLOCK_MARGIN_MS = 2_000;
+    const waitMs = Number(unixTimestamp) * 1000 - Date.now() + CLOCK_MARGIN_MS;
+    if (waitMs > 0) {
+      await new Promise((resolve) => setTimeout(resolve, waitMs));
+    }
+  };
+
+  /**
+   * Waits until the validator has reached a slot.
+   *
+   * @param slot - The slot to wait for
+   */
+  const waitUntilSlot = async (slot: bigint) => {
+    const SLOT_POLL_MS = 400;
+    while ((await connection.rpc.getSlot({ commitment: "confirmed" }).send()) < slot) {
+      await new Promise((resolve) => setTimeout(resolve, SLOT_POLL_MS));
+    }
+  };
+
+  /**
+   * Asserts that sending an instruction fails with the given program error.
+   *
+   * @param feePayer - The signer paying for the transaction
+   * @param instruction - The instruction expected to fail
+   * @param errorName - The `ErrorCode` variant name expected in the logs
+   */
+  const assertInstructionFails = async (
+    feePayer: KeyPairSigner,
+    instruction: Instruction,
+    errorName: string
+  ) => {
+    await assert.rejects(
+      connection.sendTransactionFromInstructions({
+        feePayer,
+        instructions: [instruction],
+      }),
+      new RegExp(errorName)
+    );
+  };
+
+  /**
+   * Builds an Ed25519 program instruction checking one signature, with the signature, public
+   * key and message all in its own data.
+   *
+   * @param publicKey - The signer's 32-byte public key
+   * @param message - The signed message
+   * @param signature - The 64-byte signature
+   */
+  const getEd25519Instruction = (
+    publicKey: Uint8Array,
+    message: Uint8Array,
+    signature: Uint8Array
+  ): Instruction => {
+    // A signature count and a padding byte, then seven u16 offsets, then the data they locate
+    const signatureOffset = 16;
+    const publicKeyOffset = signatureOffset + 64;
+    const messageOffset = publicKeyOffset + 32;
+    const data = Buffer.alloc(messageOffset + message.length);
+    data.writeUInt8(1, 0);
+    // Instruction index u16::MAX means "this instruction"
+    const offsets = [
+      signatureOffset,
+      0xffff,
+      publicKeyOffset,
+      0xffff,
+      messageOffset,
+      message.length,
+      0xffff,
+    ];
+    offsets.forEach((value, index) => data.writeUInt16LE(value, 2 + 2 * index));
+    data.set(signature, signatureOffset);
+    data.set(publicKey, publicKeyOffset);
+    data.set(message, messageOffset);
+    return { programAddress: ED25519_PROGRAM_ADDRESS, data };
+  };
+
+  type GetInitCompDefInstruction = (params: {
+    payer: KeyPairSigner;
+    mxeAccount: Address;
+    compDefAccount: Address;
+    addressLookupTable: Address;
+    lutProgram: Address;
+  }) => Instruction;
+
+  /**
+   * Every circuit in encrypted-ixs/src/lib.rs, with the instruction that registers it.
+   * New circuits must be added here so deployments (and the tests) initialize them.
+   */
+  const COMP_DEFS: Array<{
+    circuitName: string;
+    getInitInstruction: GetInitCompDefInstruction;
+    displayName: string;
+    needsComputeBudget?: boolean;
+  }> = [
+    {
+      circuitName: "create_poll",
+      getInitInstruction: getInitCreatePollCompDefInstruction,
+      displayName: "Create poll",
+      needsComputeBudget: true,
+    },
+    { circuitName: "vote", getInitInstruction: getInitVoteCompDefInstruction, displayName: "Vote" },
+    {
+      circuitName: "reveal_result",
+      getInitInstruction: getInitRevealResultCompDefInstruction,
+      displayName: "Reveal result",
+    },
+    {
+      circuitName: "reveal_weight_quorum",
+      getInitInstruction: getInitRevealWeightQuorumCompDefInstruction,
+      displayName: "Reveal weight quorum",
+    },
+    {
+      circuitName: "aggregate_child",
+      getInitInstruction: getInitAggregateChildCompDefInstruction,
+      displayName: "Aggregate child",
+    },
+    {
+      circuitName: "reveal_decided",
+      getInitInstruction: getInitRevealDecidedCompDefInstruction,
+      displayName: "Reveal decided",
+    },
+    {
+      circuitName: "reveal_top_k",
+      getInitInstruction: getInitRevealTopKCompDefInstruction,
+      displayName: "Reveal top k",
+    },
+    {
+      circuitName: "reveal_tally_shared",
+      getInitInstruction: getInitRevealTallySharedCompDefInstruction,
+      displayName: "Reveal tally shared",
+    },
+    {
+      circuitName: "reveal_referendum",
+      getInitInstruction: getInitRevealReferendumCompDefInstruction,
+      displayName: "Reveal referendum",
+    },
+    {
+      circuitName: "reveal_to",
+      getInitInstruction: getInitRevealToCompDefInstruction,
+      displayName: "Reveal to",
+    },
+    {
+      circuitName: "reveal_spread",
+      getInitInstruction: getInitRevealSpreadCompDefInstruction,
+      displayName: "Reveal spread",
+    },
+    {
+      circuitName: "vote_quadratic",
+      getInitInstruction: getInitVoteQuadraticCompDefInstruction,
+      displayName: "Vote quadratic",
+    },
+    {
+      circuitName: "reveal_with_interval",
+      getInitInstruction: getInitRevealWithIntervalCompDefInstruction,
+      displayName: "Reveal with interval",
+    },
+    {
+      circuitName: "reveal_bool",
+      getInitInstruction: getInitRevealBoolCompDefInstruction,
+      displayName: "Reveal bool",
+    },
+    {
+      circuitName: "reveal_full_ranking",
+      getInitInstruction: getInitRevealFullRankingCompDefInstruction,
+      displayName: "Reveal full ranking",
+    },
+    {
+      circuitName: "rotate_nonce",
+      getInitInstruction: getInitRotateNonceCompDefInstruction,
+      displayName: "Rotate nonce",
+    },
+    {
+      circuitName: "reveal_sample",
+      getInitInstruction: getInitRevealSampleCompDefInstruction,
+      displayName: "Reveal sample",
+    },
+    {
+      circuitName: "vote_many",
+      getInitInstruction: getInitVoteManyCompDefInstruction,
+      displayName: "Vote many",
+    },
+    {
+      circuitName: "verify_vote_claim",
+      getInitInstruction: getInitVerifyVoteClaimCompDefInstruction,
+      displayName: "Verify vote claim",
+    },
+    {
+      circuitName: "reveal_comment",
+      getInitInstruction: getInitRevealCommentCompDefInstruction,
+      displayName: "Reveal comment",
+    },
+    {
+      circuitName: "reveal_delta",
+      getInitInstruction: getInitRevealDeltaCompDefInstruction,
+      displayName: "Reveal delta",
+    },
+  ];
+
+  /**
+   * Initializes every circuit's computation definition, skipping any that already exist.
+   *
+   * Each computation definition gets its own transactions: initializing one allocates a large
+   * account and is followed by a separate finalize instruction, so several don't fit in one
+   * transaction's compute and account limits. Safe to rerun after a partial deployment.
+   *
+   * @param payer - The keypair signer paying for the accounts
+   */
+  const initAllCompDefs = async (payer: KeyPairSigner) => {
+    for (const compDef of COMP_DEFS) {
+      await initCompDef(
+        compDef.circuitName,
+        payer,
+        false,
+        false,
+        compDef.getInitInstruction,
+        compDef.displayName,
+        compDef.needsComputeBudget ?? false
+      );
+    }
+  };
+
+  /**
+   * Initializes a computation definition for a given circuit.
+   * This helper consolidates the logic for initializing every circuit; see `initAllCompDefs`.
+   *
+   * @param circuitName - The name of the circuit (e.g. "create_poll", "vote", or "reveal_result")
+   * @param pollAuthority - The keypair signer for the poll authority
+   * @param uploadRawCircuit - Whether to upload the raw circuit file
+   * @param offchainSource - Whether the circuit source is stored offchain
+   * @param getInitInstruction - Function to get the initialization instruction for this circuit
+   * @param displayName - Human-readable name for logging
+   * @param needsComputeBudget - Whether this circuit needs additional compute budget (only create_poll does)
+   * @returns Promise resolving to the transaction signature (or empty string if skipped)
+   */
+  const initCompDef = async (
+    circuitName: string,
+    pollAuthority: KeyPairSigner,
+    uploadRawCircuit: boolean,
+    offchainSource: boolean,
+    getInitInstruction: GetInitCompDefInstruction,
+    displayName: string,
+    needsComputeBudget: boolean = false
+  ): Promise<string> => {
+    const offset = getComputationDefinitionAccountOffset(circuitName);
+    const compDefPDA = await getComputationDefinitionAccountAddress(
+      connection,
+      ELECTION_PROGRAM_ID,
+      offset
+    );
+
+    // Check if the comp def account already exists
+    const existingAccount = await connection.rpc
+      .getAccountInfo(compDefPDA)
+      .send();
+
+    let transactionSignature: string;
+    if (existingAccount.value) {
+      console.log(
+        `${displayName} computation definition already exists, skipping initialization`
+      );
+      return "";
+    } else {
+      const initInstruction = getInitInstruction({
+        payer: pollAuthority,
+        mxeAccount: await getMXEAccountAddress(connection, ELECTION_PROGRAM_ID),
+        compDefAccount: compDefPDA,
+        addressLookupTable: await getMXELutAccountAddress(connection, ELECTION_PROGRAM_ID),
+        lutProgram: getLutProgramAddress(),
+      });
+
+      const instructions: Array<Instruction> = [];
+
+      if (needsComputeBudget) {
+        // create_poll circuit requires higher compute budget due to initialization complexity
+        const COMPUTE_UNIT_LIMIT = 1_400_000;
+        const computeBudgetInstruction = getSetComputeUnitLimitInstruction({
+          units: COMPUTE_UNIT_LIMIT,
+        });
+        instructions.push(computeBudgetInstruction);
+      }
+
+      instructions.push(initInstruction);
+
+      transactionSignature = await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions,
+        skipPreflight: true,
+      });
+
+      if (needsComputeBudget) {
+        console.log("Transaction sent:", transactionSignature);
+      }
+    }
+
+    if (uploadRawCircuit) {
+      const circuitPath = path.join(
+        process.cwd(),
+        "build",
+        `${circuitName}.arcis`
+      );
+      const rawCircuit = await fs.readFile(circuitPath);
+
+      const uploadSigs = await uploadCircuit(
+        connection,
+        pollAuthority,
+        circuitName,
+        ELECTION_PROGRAM_ID,
+        rawCircuit,
+        true,
+        500
+      );
+
+      console.log(
+        `Uploaded ${circuitName} circuit in ${uploadSigs.length} transactions`
+      );
+    } else if (!offchainSource) {
+      const finalizeInstruction = await buildFinalizeCompDefInstruction(
+        connection,
+        pollAuthority,
+        Buffer.from(offset).readUInt32LE(),
+        ELECTION_PROGRAM_ID
+      );
+
+      await connection.sendTransactionFromInstructions({
+        feePayer: pollAuthority,
+        instructions: [finalizeInstruction],
+        skipPreflight: true,
+      });
+    }
+    return transactionSignature;
   };
 });